@@ -1,9 +1,8 @@
-use super::{
-    new_node, Config, Element, ElementMerge, Encode, EncodeChildren, EncodeError, XMLNode,
-};
+use super::{new_node, Config, Element, ElementMerge, Encode, EncodeError, XMLNode};
 
 use crate::{
-    config::{change_case, format_number},
+    config::{change_case, format_styled},
+    registerproperties::encode_properties,
     svd::{Cluster, ClusterInfo},
 };
 
@@ -51,13 +50,18 @@ impl Encode for ClusterInfo {
 
         e.children.push(new_node(
             "addressOffset",
-            format_number(self.address_offset, config.cluster_address_offset),
+            format_styled(
+                self.address_offset,
+                self.number_styles.get("addressOffset"),
+                config.cluster_address_offset,
+            ),
         ));
 
-        e.children.extend(
-            self.default_register_properties
-                .encode_with_config(config)?,
-        );
+        e.children.extend(encode_properties(
+            &self.default_register_properties,
+            &self.number_styles,
+            config,
+        )?);
 
         for c in &self.children {
             e.children
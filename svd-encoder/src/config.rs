@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
 use convert_case::{Boundary, Case, Casing};
+use xmltree::EmitterConfig;
 
-use crate::svd::BitRangeType;
+use crate::svd::{BitRangeType, NumberStyle};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum IdentifierFormat {
@@ -130,7 +131,7 @@ where
         NumberFormat::UpperHex => format!("{:#X}", value),
         NumberFormat::UpperHex8 => format!("{:#010X}", value),
         NumberFormat::UpperHex16 => {
-            if value.into() > std::u32::MAX as u64 {
+            if value.into() > u32::MAX as u64 {
                 format!("{:#018X}", value)
             } else {
                 format!("{:#010X}", value)
@@ -139,7 +140,7 @@ where
         NumberFormat::LowerHex => format!("{:#x}", value),
         NumberFormat::LowerHex8 => format!("{:#010x}", value),
         NumberFormat::LowerHex16 => {
-            if value.into() > std::u32::MAX as u64 {
+            if value.into() > u32::MAX as u64 {
                 format!("{:#018x}", value)
             } else {
                 format!("{:#010x}", value)
@@ -150,6 +151,26 @@ where
     }
 }
 
+/// Format `value` as it was written originally if its `style` is known and in `format` otherwise
+pub(crate) fn format_styled<T>(
+    value: T,
+    style: Option<&NumberStyle>,
+    format: NumberFormat,
+) -> String
+where
+    T: std::fmt::UpperHex
+        + std::fmt::LowerHex
+        + std::fmt::Display
+        + std::fmt::Binary
+        + Into<u64>
+        + Copy,
+{
+    match style {
+        Some(style) => style.format(value.into()),
+        None => format_number(value, format),
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct FieldBitRangeFormat(pub BitRangeType);
 
@@ -166,6 +187,39 @@ impl FromStr for FieldBitRangeFormat {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Indentation of nested elements
+pub enum Indent {
+    /// Write the whole document on one line
+    None,
+    /// Indent with given number of spaces per level
+    Spaces(u8),
+    /// Indent with a tab per level
+    Tab,
+}
+
+impl Indent {
+    fn as_string(self) -> Option<String> {
+        match self {
+            Self::None => None,
+            Self::Spaces(n) => Some(" ".repeat(n.into())),
+            Self::Tab => Some("\t".into()),
+        }
+    }
+}
+
+impl FromStr for Indent {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" => Ok(Self::None),
+            "Tab" => Ok(Self::Tab),
+            _ => s.parse().map(Self::Spaces).map_err(|_| ()),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 /// Apply a kind of sorting
@@ -367,6 +421,12 @@ pub struct Config {
 
     /// Format of dimArrayIndex's headerEnumName element
     pub dim_array_index_header_enum_name: Option<IdentifierFormat>,
+
+    /// Indentation of nested elements
+    pub indent: Indent,
+
+    /// Write empty elements as `<tag/>` instead of `<tag></tag>`
+    pub self_closing: bool,
 }
 
 impl Default for Config {
@@ -395,6 +455,8 @@ impl Default for Config {
             dim_dim: NumberFormat::Dec,
             dim_increment: NumberFormat::UpperHex,
             dim_array_index_header_enum_name: None,
+            indent: Indent::Spaces(2),
+            self_closing: true,
         }
     }
 }
@@ -430,6 +492,8 @@ impl Config {
             "dim_array_index_header_enum_name" => {
                 self.dim_array_index_header_enum_name = Some(value.parse().unwrap())
             }
+            "indent" => self.indent = value.parse().unwrap(),
+            "self_closing" => self.self_closing = value.parse().unwrap(),
             _ => {
                 eprintln!("Unknown config key: {}", name);
             }
@@ -597,4 +661,30 @@ impl Config {
         self.dim_increment = val;
         self
     }
+
+    /// Indentation of nested elements
+    ///
+    /// format: `None`, `Tab` or number of spaces
+    pub fn indent(mut self, val: Indent) -> Self {
+        self.indent = val;
+        self
+    }
+
+    /// Write empty elements as `<tag/>` instead of `<tag></tag>`
+    pub fn self_closing(mut self, val: bool) -> Self {
+        self.self_closing = val;
+        self
+    }
+
+    /// Settings of the XML writer
+    pub(crate) fn emitter_config(&self) -> EmitterConfig {
+        let mut cfg = EmitterConfig::new();
+        if let Some(indent) = self.indent.as_string() {
+            cfg.perform_indent = true;
+            cfg.indent_string = indent.into();
+        }
+        cfg.pad_self_closing = false;
+        cfg.normalize_empty_elements = self.self_closing;
+        cfg
+    }
 }
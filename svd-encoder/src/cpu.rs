@@ -4,7 +4,7 @@ use crate::svd::Cpu;
 impl Encode for Cpu {
     type Error = EncodeError;
 
-    fn encode_with_config(&self, _config: &Config) -> Result<Element, EncodeError> {
+    fn encode_with_config(&self, config: &Config) -> Result<Element, EncodeError> {
         let mut children = vec![
             new_node("name", self.name.clone()),
             new_node("revision", self.revision.clone()),
@@ -48,6 +48,9 @@ impl Encode for Cpu {
         if let Some(v) = &self.sau_num_regions {
             children.push(new_node("sauNumRegions", format!("{}", v)));
         }
+        if let Some(v) = &self.sau_regions_config {
+            children.push(v.encode_node_with_config(config)?);
+        }
 
         let mut elem = Element::new("cpu");
         elem.children = children;
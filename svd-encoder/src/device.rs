@@ -1,8 +1,9 @@
 use svd_rs::Peripheral;
 
-use super::{new_node, Config, Element, Encode, EncodeChildren, EncodeError, XMLNode};
+use super::{new_node, Config, Element, Encode, EncodeError, XMLNode};
 use crate::{
     config::{DerivableSorting, Sorting},
+    registerproperties::encode_properties,
     svd::Device,
 };
 
@@ -24,11 +25,13 @@ impl Encode for Device {
             elem.children.push(new_node("series", v.clone()));
         }
 
-        elem.children
-            .push(new_node("version", self.version.clone()));
+        if let Some(v) = &self.version {
+            elem.children.push(new_node("version", v.clone()));
+        }
 
-        elem.children
-            .push(new_node("description", self.description.clone()));
+        if let Some(v) = &self.description {
+            elem.children.push(new_node("description", v.clone()));
+        }
 
         if let Some(v) = &self.license_text {
             elem.children.push(new_node("licenseText", v.clone()));
@@ -63,10 +66,11 @@ impl Encode for Device {
         elem.children
             .push(new_node("width", format!("{}", self.width)));
 
-        elem.children.extend(
-            self.default_register_properties
-                .encode_with_config(config)?,
-        );
+        elem.children.extend(encode_properties(
+            &self.default_register_properties,
+            &self.number_styles,
+            config,
+        )?);
 
         let peripherals: Result<Vec<_>, _> =
             if config.peripheral_sorting == DerivableSorting::Unchanged(None) {
@@ -16,12 +16,21 @@ impl Encode for crate::svd::DimElement {
         ));
 
         if let Some(di) = &self.dim_index {
-            e.children
-                .push(if let Some(range) = self.indexes_as_range() {
-                    new_node("dimIndex", format!("{}-{}", range.start(), range.end()))
+            e.children.push(
+                if let Some((range, width)) = self.indexes_as_padded_range() {
+                    new_node(
+                        "dimIndex",
+                        format!(
+                            "{:0width$}-{:0width$}",
+                            range.start(),
+                            range.end(),
+                            width = width
+                        ),
+                    )
                 } else {
                     new_node("dimIndex", di.join(","))
-                });
+                },
+            );
         }
 
         if let Some(dim_name) = &self.dim_name {
@@ -1,7 +1,7 @@
 use super::{new_node, Config, Element, Encode, EncodeError};
 
 use crate::{
-    config::{change_case, format_number},
+    config::{change_case, format_styled},
     svd::EnumeratedValue,
 };
 
@@ -20,10 +20,15 @@ impl Encode for EnumeratedValue {
         };
 
         if let Some(v) = &self.value {
-            base.children.push(new_node(
-                "value",
-                format_number(*v, config.enumerated_value_value),
-            ));
+            let value = match self.dont_care {
+                Some(mask) if mask != 0 => format_dont_care(*v, mask),
+                _ => format_styled(
+                    *v,
+                    self.number_styles.get("value"),
+                    config.enumerated_value_value,
+                ),
+            };
+            base.children.push(new_node("value", value));
         };
 
         if let Some(v) = &self.is_default {
@@ -33,3 +38,17 @@ impl Encode for EnumeratedValue {
         Ok(base)
     }
 }
+
+/// Binary form with `x` in place of don't care bits
+fn format_dont_care(value: u64, mask: u64) -> String {
+    let width = 64 - (value | mask).leading_zeros();
+    let bits: String = (0..width)
+        .rev()
+        .map(|i| match (mask >> i & 1, value >> i & 1) {
+            (1, _) => 'x',
+            (_, 1) => '1',
+            _ => '0',
+        })
+        .collect();
+    format!("#{}", bits)
+}
@@ -15,6 +15,10 @@ impl Encode for EnumeratedValues {
             ));
         };
 
+        if let Some(v) = &self.header_enum_name {
+            base.children.push(new_node("headerEnumName", v.clone()));
+        };
+
         if let Some(v) = &self.usage {
             base.children.push(v.encode_node()?);
         };
@@ -4,14 +4,19 @@
 use svd_rs as svd;
 
 use crate::svd::Device;
-use xmltree::{Element, EmitterConfig, XMLNode};
+use std::io::Write;
+use xmltree::{Element, XMLNode};
 
 pub use crate::config::{
-    Config, DerivableSorting, IdentifierFormat, NumberFormat, RcSorting, Sorting,
+    Config, DerivableSorting, IdentifierFormat, Indent, NumberFormat, RcSorting, Sorting,
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
-pub enum EncodeError {}
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum EncodeError {
+    /// Writing the document failed
+    #[error("I/O error: {0}")]
+    Io(String),
+}
 
 /// Encode trait allows SVD objects to be encoded into XML elements.
 pub trait Encode {
@@ -51,15 +56,28 @@ pub fn encode(d: &Device) -> Result<String, EncodeError> {
 
 /// Encodes a device object to an SVD (XML) string
 pub fn encode_with_config(d: &Device, config: &Config) -> Result<String, EncodeError> {
-    let root = d.encode_with_config(config)?;
     let mut wr = Vec::new();
-    let mut cfg = EmitterConfig::new();
-    cfg.perform_indent = true;
-    cfg.pad_self_closing = false;
-    root.write_with_config(&mut wr, cfg).unwrap();
+    encode_to_with_config(d, config, &mut wr)?;
+    // the writer only produces UTF-8
     Ok(String::from_utf8(wr).unwrap())
 }
 
+/// Writes SVD (XML) of a device object to `w` without collecting it in a string
+pub fn encode_to<W: Write>(d: &Device, w: W) -> Result<(), EncodeError> {
+    encode_to_with_config(d, &Config::default(), w)
+}
+
+/// Writes SVD (XML) of a device object to `w` without collecting it in a string
+pub fn encode_to_with_config<W: Write>(
+    d: &Device,
+    config: &Config,
+    w: W,
+) -> Result<(), EncodeError> {
+    let root = d.encode_with_config(config)?;
+    root.write_with_config(w, config.emitter_config())
+        .map_err(|e| EncodeError::Io(e.to_string()))
+}
+
 /// Defines extensions for implementation over xmltree::Element
 trait ElementMerge {
     fn merge(&mut self, n: &Self);
@@ -105,5 +123,6 @@ mod registercluster;
 mod registerproperties;
 #[cfg(feature = "unstable-riscv")]
 mod riscv;
+mod sauregion;
 mod usage;
 mod writeconstraint;
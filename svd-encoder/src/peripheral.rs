@@ -1,11 +1,10 @@
 use svd_rs::RegisterCluster;
 
-use super::{
-    new_node, Config, Element, ElementMerge, Encode, EncodeChildren, EncodeError, XMLNode,
-};
+use super::{new_node, Config, Element, ElementMerge, Encode, EncodeError, XMLNode};
 
 use crate::{
-    config::{change_case, format_number, DerivableSorting, RcSorting, Sorting},
+    config::{change_case, format_styled, DerivableSorting, RcSorting, Sorting},
+    registerproperties::encode_properties,
     svd::{Peripheral, PeripheralInfo},
 };
 
@@ -79,15 +78,24 @@ impl Encode for PeripheralInfo {
             ));
         }
 
+        if let Some(v) = &self.disable_condition {
+            elem.children.push(new_node("disableCondition", v.clone()));
+        }
+
         elem.children.push(new_node(
             "baseAddress",
-            format_number(self.base_address, config.peripheral_base_address),
+            format_styled(
+                self.base_address,
+                self.number_styles.get("baseAddress"),
+                config.peripheral_base_address,
+            ),
         ));
 
-        elem.children.extend(
-            self.default_register_properties
-                .encode_with_config(config)?,
-        );
+        elem.children.extend(encode_properties(
+            &self.default_register_properties,
+            &self.number_styles,
+            config,
+        )?);
 
         if let Some(v) = &self.address_block {
             for ab in v {
@@ -1,11 +1,10 @@
 use svd_rs::Field;
 
-use super::{
-    new_node, Config, Element, ElementMerge, Encode, EncodeChildren, EncodeError, XMLNode,
-};
+use super::{new_node, Config, Element, ElementMerge, Encode, EncodeError, XMLNode};
 
 use crate::{
-    config::{change_case, format_number, DerivableSorting, Sorting},
+    config::{change_case, format_styled, DerivableSorting, Sorting},
+    registerproperties::encode_properties,
     svd::{Register, RegisterInfo},
 };
 
@@ -57,11 +56,18 @@ impl Encode for RegisterInfo {
 
         elem.children.push(new_node(
             "addressOffset",
-            format_number(self.address_offset, config.register_address_offset),
+            format_styled(
+                self.address_offset,
+                self.number_styles.get("addressOffset"),
+                config.register_address_offset,
+            ),
         ));
 
-        elem.children
-            .extend(self.properties.encode_with_config(config)?);
+        elem.children.extend(encode_properties(
+            &self.properties,
+            &self.number_styles,
+            config,
+        )?);
 
         if let Some(v) = &self.datatype {
             elem.children.push(v.encode_node_with_config(config)?);
@@ -0,0 +1,46 @@
+use crate::config::{format_number, NumberFormat};
+
+use super::{new_node, Config, Element, Encode, EncodeError};
+
+use crate::svd::{SauRegion, SauRegionsConfig};
+
+impl Encode for SauRegionsConfig {
+    type Error = EncodeError;
+
+    fn encode_with_config(&self, config: &Config) -> Result<Element, EncodeError> {
+        let mut elem = Element::new("sauRegionsConfig");
+        if let Some(v) = &self.enabled {
+            elem.attributes
+                .insert(String::from("enabled"), format!("{}", v));
+        }
+        if let Some(v) = &self.protection_when_disabled {
+            elem.attributes.insert(
+                String::from("protectionWhenDisabled"),
+                v.as_str().to_string(),
+            );
+        }
+        for region in &self.regions {
+            elem.children.push(region.encode_node_with_config(config)?);
+        }
+        Ok(elem)
+    }
+}
+
+impl Encode for SauRegion {
+    type Error = EncodeError;
+
+    fn encode_with_config(&self, _config: &Config) -> Result<Element, EncodeError> {
+        let mut elem = Element::new("region");
+        elem.attributes
+            .insert(String::from("enabled"), format!("{}", self.enabled));
+        if let Some(v) = &self.name {
+            elem.attributes.insert(String::from("name"), v.clone());
+        }
+        elem.children = vec![
+            new_node("base", format_number(self.base, NumberFormat::UpperHex8)),
+            new_node("limit", format_number(self.limit, NumberFormat::UpperHex8)),
+            new_node("access", self.access.as_str().to_string()),
+        ];
+        Ok(elem)
+    }
+}
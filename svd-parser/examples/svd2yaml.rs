@@ -27,7 +27,7 @@ fn main() {
         .expect("Failed to read SVD input file to a String");
 
     // Use the 'svd_parser' crate to parse the file.
-    let device = svd::parse(&mut svd_xml).expect("Failed to parse the SVD file into Rust structs");
+    let device = svd::parse(&svd_xml).expect("Failed to parse the SVD file into Rust structs");
 
     // Convert the parsed data into YAML format.
     let v: Value =
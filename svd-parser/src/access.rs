@@ -9,6 +9,6 @@ impl Parse for Access {
     fn parse(tree: &Node, _config: &Self::Config) -> Result<Self, Self::Error> {
         let text = tree.get_text()?;
 
-        Self::parse_str(text).ok_or_else(|| SVDError::UnknownAccessType(text.into()).at(tree.id()))
+        Self::parse_str(&text).ok_or_else(|| SVDError::UnknownAccessType(text.into()).at(tree.id()))
     }
 }
@@ -28,6 +28,7 @@ impl Parse for AddressBlockUsage {
     fn parse(tree: &Node, _config: &Self::Config) -> Result<Self, Self::Error> {
         let text = tree.get_text()?;
 
-        Self::parse_str(text).ok_or_else(|| SVDError::UnknownAddressBlockUsageVariant.at(tree.id()))
+        Self::parse_str(&text)
+            .ok_or_else(|| SVDError::UnknownAddressBlockUsageVariant(text.into()).at(tree.id()))
     }
 }
@@ -0,0 +1,114 @@
+//! Key-value annotations taken from `vendorExtensions`.
+//!
+//! Every element with text content placed directly in `<vendorExtensions>` of a peripheral,
+//! cluster, register or field becomes an annotation of this SVD element. For example,
+//!
+//! ```xml
+//! <register>
+//!   <name>CTRL</name>
+//!   <vendorExtensions><introducedIn>B0</introducedIn></vendorExtensions>
+//!   ...
+//! ```
+//! in peripheral `TIMER` gives annotation `introducedIn = B0` for path `TIMER.CTRL`.
+
+use super::ElementExt;
+use roxmltree::Node;
+use std::collections::BTreeMap;
+
+/// Annotations of SVD elements keyed by dot separated path, like `PERIPHERAL.REGISTER.FIELD`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnnotationTable {
+    entries: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl AnnotationTable {
+    /// Get annotation `key` of element at `path`
+    pub fn get(&self, path: &str, key: &str) -> Option<&str> {
+        self.entries.get(path)?.get(key).map(String::as_str)
+    }
+
+    /// Get all annotations of element at `path`
+    pub fn get_all(&self, path: &str) -> Option<&BTreeMap<String, String>> {
+        self.entries.get(path)
+    }
+
+    /// Iterate over annotated paths with their annotations
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BTreeMap<String, String>)> {
+        self.entries.iter().map(|(p, a)| (p.as_str(), a))
+    }
+
+    /// Returns `true` if there are no annotations
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Group paths of elements by value of annotation `key`.
+    /// Elements without this annotation are skipped.
+    pub fn group_by(&self, key: &str) -> BTreeMap<&str, Vec<&str>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for (path, annotations) in &self.entries {
+            if let Some(value) = annotations.get(key) {
+                groups
+                    .entry(value.as_str())
+                    .or_default()
+                    .push(path.as_str());
+            }
+        }
+        groups
+    }
+
+    /// Add annotation
+    pub fn insert(
+        &mut self,
+        path: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        self.entries
+            .entry(path.into())
+            .or_default()
+            .insert(key.into(), value.into());
+    }
+}
+
+pub(crate) fn collect(xmldevice: &Node) -> AnnotationTable {
+    let mut table = AnnotationTable::default();
+    if let Some(peripherals) = xmldevice.get_child("peripherals") {
+        for p in peripherals
+            .children()
+            .filter(|c| c.has_tag_name("peripheral"))
+        {
+            collect_element(&mut table, &p, "");
+        }
+    }
+    table
+}
+
+fn collect_element(table: &mut AnnotationTable, node: &Node, parent: &str) {
+    let name = match node.get_child_text("name") {
+        Ok(name) => name,
+        Err(_) => return,
+    };
+    let path = if parent.is_empty() {
+        name
+    } else {
+        format!("{}.{}", parent, name)
+    };
+    if let Some(ext) = node.get_child("vendorExtensions") {
+        for e in ext.children().filter(|e| e.is_element()) {
+            let is_leaf = e.children().all(|c| !c.is_element());
+            if let (true, Ok(value)) = (is_leaf, e.get_text()) {
+                table.insert(&path, e.tag_name().name(), value);
+            }
+        }
+    }
+    // clusters are placed directly in parent cluster, other elements are in containers
+    let containers = node
+        .children()
+        .filter(|c| c.has_tag_name("registers") || c.has_tag_name("fields"));
+    for c in node.children().chain(containers.flat_map(|c| c.children())) {
+        if matches!(c.tag_name().name(), "cluster" | "register" | "field") {
+            collect_element(table, &c, &path);
+        }
+    }
+}
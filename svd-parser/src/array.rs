@@ -4,12 +4,25 @@ use svd_rs::{DimElement, MaybeArray, Name};
 pub fn parse_array<T>(tag: &str, tree: &Node, config: &Config) -> Result<MaybeArray<T>, SVDErrorAt>
 where
     T: Parse<Object = T, Error = SVDErrorAt, Config = Config> + Name,
+{
+    parse_array_with(tag, tree, config, |t| T::parse(t, config))
+}
+
+/// Same as [`parse_array`], but parses the element info with `parse_info`
+pub fn parse_array_with<T>(
+    tag: &str,
+    tree: &Node,
+    config: &Config,
+    parse_info: impl FnOnce(&Node) -> Result<T, SVDErrorAt>,
+) -> Result<MaybeArray<T>, SVDErrorAt>
+where
+    T: Name,
 {
     if !tree.has_tag_name(tag) {
-        return Err(SVDError::NotExpectedTag(tag.into()).at(tree.id()));
+        return Err(SVDError::not_expected(tag, tree));
     }
 
-    let info = T::parse(tree, config)?;
+    let info = parse_info(tree)?;
 
     if tree.get_child("dimIncrement").is_some() {
         let array_info = DimElement::parse(tree, config)?;
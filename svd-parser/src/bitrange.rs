@@ -7,7 +7,13 @@ pub enum InvalidBitRange {
     ParseError,
     MsbLsb,
     Empty,
-    Size,
+    /// Most significant bit is less than least significant bit
+    Reversed {
+        msb: u32,
+        lsb: u32,
+    },
+    /// Several specifications with different ranges
+    Conflicting(String),
 }
 
 impl Parse for BitRange {
@@ -15,82 +21,118 @@ impl Parse for BitRange {
     type Error = SVDErrorAt;
     type Config = Config;
 
-    fn parse(tree: &Node, _config: &Self::Config) -> Result<Self, Self::Error> {
-        let (end, start, range_type): (u32, u32, BitRangeType) =
-            if let Some(range) = tree.get_child("bitRange") {
-                let text = range.text().ok_or_else(|| {
-                    SVDError::InvalidBitRange(InvalidBitRange::Empty).at(tree.id())
-                })?;
-                if !text.starts_with('[') {
-                    return Err(SVDError::InvalidBitRange(InvalidBitRange::Syntax).at(tree.id()));
-                    // TODO: Maybe have a MissingOpen/MissingClosing variant
-                }
-                if !text.ends_with(']') {
-                    return Err(SVDError::InvalidBitRange(InvalidBitRange::Syntax).at(tree.id()));
-                    // TODO: Maybe have a MissingOpen/MissingClosing variant
-                }
-
-                let mut parts = text[1..text.len() - 1].split(':');
-                (
-                    parts
-                        .next()
-                        .ok_or_else(|| {
-                            SVDError::InvalidBitRange(InvalidBitRange::Syntax).at(tree.id())
-                        })?
-                        .parse::<u32>()
-                        .map_err(|_| {
-                            SVDError::InvalidBitRange(InvalidBitRange::ParseError).at(tree.id())
-                        })?,
-                    parts
-                        .next()
-                        .ok_or_else(|| {
-                            SVDError::InvalidBitRange(InvalidBitRange::Syntax).at(tree.id())
-                        })?
-                        .parse::<u32>()
-                        .map_err(|_| {
-                            SVDError::InvalidBitRange(InvalidBitRange::ParseError).at(tree.id())
-                        })?,
-                    BitRangeType::BitRange,
-                )
-            // TODO: Consider matching instead so we can say which of these tags are missing
-            } else if let (Some(lsb), Some(msb)) = (tree.get_child("lsb"), tree.get_child("msb")) {
-                (
-                    // TODO: `u32::parse` should not hide it's errors
-                    u32::parse(&msb, &()).map_err(|_| {
-                        SVDError::InvalidBitRange(InvalidBitRange::MsbLsb).at(tree.id())
-                    })?,
-                    u32::parse(&lsb, &()).map_err(|_| {
-                        SVDError::InvalidBitRange(InvalidBitRange::MsbLsb).at(tree.id())
-                    })?,
-                    BitRangeType::MsbLsb,
-                )
-            } else if let (Some(offset), Some(width)) =
-                (tree.get_child("bitOffset"), tree.get_child("bitWidth"))
-            {
-                // Special case because offset and width are directly provided
-                // (ie. do not need to be calculated as in the final step)
-                return Ok(BitRange {
-                    // TODO: capture that error comes from offset/width tag
-                    // TODO: `u32::parse` should not hide it's errors
-                    offset: u32::parse(&offset, &()).map_err(|_| {
-                        SVDError::InvalidBitRange(InvalidBitRange::ParseError).at(tree.id())
-                    })?,
-                    width: u32::parse(&width, &()).map_err(|_| {
-                        SVDError::InvalidBitRange(InvalidBitRange::ParseError).at(tree.id())
-                    })?,
-                    range_type: BitRangeType::OffsetWidth,
-                });
-            } else {
-                return Err(SVDError::InvalidBitRange(InvalidBitRange::Syntax).at(tree.id()));
-            };
+    /// Parse bit range of a field.
+    ///
+    /// The range is read from `bitRange`, `lsb` and `msb` or `bitOffset` and `bitWidth`.
+    /// If there are several of them, the first one in this order is used. Different
+    /// ranges in them are an error in strict mode.
+    fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
+        let specs = specifications(tree)?;
+        let range = *specs
+            .first()
+            .ok_or_else(|| SVDError::InvalidBitRange(InvalidBitRange::Syntax).at(tree.id()))?;
+        if config.validate_level.is_strict() && is_conflicting(&specs) {
+            return Err(
+                SVDError::InvalidBitRange(InvalidBitRange::Conflicting(describe(&specs)))
+                    .at(tree.id()),
+            );
+        }
+        Ok(range)
+    }
+}
 
-        if start > end {
-            return Err(SVDError::InvalidBitRange(InvalidBitRange::Size).at(tree.id()));
+/// Parse all bit range specifications of field `tree` in order of precedence
+fn specifications(tree: &Node) -> Result<Vec<BitRange>, SVDErrorAt> {
+    let error = |e| SVDError::InvalidBitRange(e).at(tree.id());
+    let msb_lsb = |msb: u32, lsb: u32, range_type| {
+        if lsb > msb {
+            return Err(error(InvalidBitRange::Reversed { msb, lsb }));
         }
-        Ok(Self {
-            offset: start,
-            width: end - start + 1,
+        Ok(BitRange {
+            offset: lsb,
+            width: msb - lsb + 1,
             range_type,
         })
+    };
+    let mut specs = Vec::new();
+    if let Some(range) = tree.get_child("bitRange") {
+        let text = range
+            .text()
+            .ok_or_else(|| error(InvalidBitRange::Empty))?
+            .trim();
+        let text = text
+            .strip_prefix('[')
+            .and_then(|t| t.strip_suffix(']'))
+            .ok_or_else(|| error(InvalidBitRange::Syntax))?;
+        let (msb, lsb) = text
+            .split_once(':')
+            .ok_or_else(|| error(InvalidBitRange::Syntax))?;
+        let bit = |text: &str| {
+            text.trim()
+                .parse::<u32>()
+                .map_err(|_| error(InvalidBitRange::ParseError))
+        };
+        specs.push(msb_lsb(bit(msb)?, bit(lsb)?, BitRangeType::BitRange)?);
+    }
+    if let (Some(lsb), Some(msb)) = (tree.get_child("lsb"), tree.get_child("msb")) {
+        // TODO: `u32::parse` should not hide it's errors
+        let bit = |node| u32::parse(&node, &()).map_err(|_| error(InvalidBitRange::MsbLsb));
+        specs.push(msb_lsb(bit(msb)?, bit(lsb)?, BitRangeType::MsbLsb)?);
+    }
+    if let (Some(offset), Some(width)) = (tree.get_child("bitOffset"), tree.get_child("bitWidth")) {
+        // TODO: capture that error comes from offset/width tag
+        let value = |node| u32::parse(&node, &()).map_err(|_| error(InvalidBitRange::ParseError));
+        specs.push(BitRange {
+            offset: value(offset)?,
+            width: value(width)?,
+            range_type: BitRangeType::OffsetWidth,
+        });
+    }
+    Ok(specs)
+}
+
+fn is_conflicting(specs: &[BitRange]) -> bool {
+    specs
+        .iter()
+        .any(|s| (s.offset, s.width) != (specs[0].offset, specs[0].width))
+}
+
+/// Values of all `specs` with bits they describe
+fn describe(specs: &[BitRange]) -> String {
+    let specs: Vec<String> = specs
+        .iter()
+        .map(|s| {
+            let value = match s.range_type {
+                BitRangeType::BitRange => format!("bitRange {}", s.bit_range()),
+                BitRangeType::MsbLsb => format!("lsb {} msb {}", s.lsb(), s.msb()),
+                BitRangeType::OffsetWidth => {
+                    format!("bitOffset {} bitWidth {}", s.offset, s.width)
+                }
+            };
+            if s.width == 0 {
+                format!("{} (no bits)", value)
+            } else {
+                format!("{} (bits {}..={})", value, s.lsb(), s.msb())
+            }
+        })
+        .collect();
+    specs.join(", ")
+}
+
+/// Check `node` for several bit range specifications for parse warnings.
+///
+/// Only reported in lenient modes, where different ranges are not an error.
+pub(crate) fn warning(node: Node, config: &Config) -> Option<String> {
+    if config.validate_level.is_strict() || !node.has_tag_name("field") {
+        return None;
     }
+    let specs = specifications(&node).ok().filter(|s| s.len() > 1)?;
+    Some(if is_conflicting(&specs) {
+        format!(
+            "Conflicting bit range specifications: {}; using the first",
+            describe(&specs)
+        )
+    } else {
+        format!("Redundant bit range specification: {}", describe(&specs))
+    })
 }
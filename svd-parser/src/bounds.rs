@@ -0,0 +1,140 @@
+//! Allowed ranges of numeric elements.
+//!
+//! The schema limits values of some numeric elements. Values out of these bounds
+//! are errors with [`ValidateLevel::Strict`](crate::svd::ValidateLevel::Strict) and
+//! [`ParseWarning`](crate::ParseWarning)s of [`parse_full`](crate::parse_full) otherwise.
+
+use super::{Config, Node, Parse, SVDError, SVDErrorAt};
+use crate::svd::BitRange;
+use core::fmt;
+
+/// Allowed values of an element
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Allowed {
+    /// Inclusive range
+    Range(u64, u64),
+    /// One of listed values
+    OneOf(&'static [u64]),
+}
+
+impl Allowed {
+    fn contains(&self, value: u64) -> bool {
+        match self {
+            Self::Range(min, max) => (*min..=*max).contains(&value),
+            Self::OneOf(values) => values.contains(&value),
+        }
+    }
+}
+
+impl fmt::Display for Allowed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Range(min, max) if *max == u32::MAX as u64 => write!(f, "at least {}", min),
+            Self::Range(min, max) => write!(f, "{}..={}", min, max),
+            Self::OneOf(values) => {
+                let values: Vec<_> = values.iter().map(u64::to_string).collect();
+                write!(f, "one of {}", values.join(", "))
+            }
+        }
+    }
+}
+
+const DIM_PARENTS: &[&str] = &["peripheral", "cluster", "register", "field"];
+const SIZE_PARENTS: &[&str] = &["device", "peripheral", "cluster", "register"];
+
+/// Element tag, tags of parent elements it is checked in and its allowed values
+pub(crate) const BOUNDS: &[(&str, &[&str], Allowed)] = &[
+    ("dim", DIM_PARENTS, Allowed::Range(1, u32::MAX as u64)),
+    (
+        "dimIncrement",
+        DIM_PARENTS,
+        Allowed::Range(1, u32::MAX as u64),
+    ),
+    ("bitWidth", &["field"], Allowed::Range(1, 64)),
+    ("size", SIZE_PARENTS, Allowed::Range(1, 1024)),
+    ("addressUnitBits", &["device"], Allowed::OneOf(&[8, 16, 32])),
+    ("width", &["device"], Allowed::OneOf(&[8, 16, 32, 64])),
+    ("nvicPrioBits", &["cpu"], Allowed::Range(2, 8)),
+];
+
+/// Register sizes which don't produce a warning
+const USUAL_SIZES: &[u64] = &[8, 16, 32, 64];
+
+fn allowed(parent: &str, tag: &str) -> Option<Allowed> {
+    BOUNDS
+        .iter()
+        .find(|(t, parents, _)| *t == tag && parents.contains(&parent))
+        .map(|(_, _, allowed)| *allowed)
+}
+
+/// Check `value` of child element `tag` of `tree`. Only fails in strict mode
+pub(crate) fn check_child(
+    tree: &Node,
+    tag: &str,
+    value: u64,
+    config: &Config,
+) -> Result<(), SVDErrorAt> {
+    if !config.validate_level.is_strict() {
+        return Ok(());
+    }
+    match allowed(tree.tag_name().name(), tag) {
+        Some(allowed) if !allowed.contains(value) => {
+            let node = tree
+                .children()
+                .find(|c| c.has_tag_name(tag))
+                .unwrap_or(*tree);
+            Err(SVDError::OutOfBounds(tag.into(), value, allowed.to_string()).at(node.id()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that field bit range fits in 64 bits. Only fails in strict mode
+pub(crate) fn check_bit_range(
+    tree: &Node,
+    range: &BitRange,
+    config: &Config,
+) -> Result<(), SVDErrorAt> {
+    match bit_range_error(range) {
+        Some(e) if config.validate_level.is_strict() => Err(e.at(tree.id())),
+        _ => Ok(()),
+    }
+}
+
+fn bit_range_error(range: &BitRange) -> Option<SVDError> {
+    let end = range.offset as u64 + range.width as u64;
+    (end > 64).then(|| {
+        SVDError::OutOfBounds(
+            "bitOffset + bitWidth".into(),
+            end,
+            Allowed::Range(1, 64).to_string(),
+        )
+    })
+}
+
+/// Find value of element `node` out of bounds for parse warnings.
+///
+/// Register sizes other than 8, 16, 32 or 64 bits are reported in any mode.
+pub(crate) fn warning(node: Node, config: &Config) -> Option<String> {
+    let strict = config.validate_level.is_strict();
+    let parent = node.parent_element()?;
+    let tag = node.tag_name().name();
+    if tag == "field" {
+        if strict {
+            return None;
+        }
+        let e = BitRange::parse(&node, config)
+            .ok()
+            .and_then(|r| bit_range_error(&r))?;
+        return Some(e.to_string());
+    }
+    let allowed = allowed(parent.tag_name().name(), tag)?;
+    let value = u64::parse(&node, &()).ok()?;
+    if !allowed.contains(value) {
+        (!strict).then(|| SVDError::OutOfBounds(tag.into(), value, allowed.to_string()).to_string())
+    } else if tag == "size" && !USUAL_SIZES.contains(&value) {
+        Some(format!("Unusual register size {}", value))
+    } else {
+        None
+    }
+}
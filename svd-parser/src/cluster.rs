@@ -1,5 +1,5 @@
 use super::*;
-use crate::svd::{Cluster, ClusterInfo, RegisterCluster, RegisterProperties};
+use crate::svd::{Cluster, ClusterInfo, RegisterProperties};
 
 impl Parse for Cluster {
     type Object = Self;
@@ -7,35 +7,57 @@ impl Parse for Cluster {
     type Config = Config;
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
-        parse_array("cluster", tree, config)
+        parse_cluster(tree, config, None)
     }
 }
 
+/// Parse a cluster, leaving out invalid children in `skips` if given
+pub(crate) fn parse_cluster(
+    tree: &Node,
+    config: &Config,
+    skips: Option<&Skips>,
+) -> Result<Cluster, SVDErrorAt> {
+    parse_array_with("cluster", tree, config, |t| {
+        parse_cluster_info(t, config, skips)
+    })
+}
+
 impl Parse for ClusterInfo {
     type Object = Self;
     type Error = SVDErrorAt;
     type Config = Config;
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
-        ClusterInfo::builder()
-            .name(tree.get_child_text("name")?)
-            .description(tree.get_child_text_opt("description")?)
-            .alternate_cluster(tree.get_child_text_opt("alternateCluster")?)
-            .header_struct_name(tree.get_child_text_opt("headerStructName")?)
-            .address_offset(tree.get_child_u32("addressOffset")?)
-            .default_register_properties(RegisterProperties::parse(tree, config)?)
-            .children({
-                let children: Result<Vec<_>, _> = tree
-                    .children()
-                    .filter(|t| {
-                        t.is_element() && (t.has_tag_name("register") || t.has_tag_name("cluster"))
-                    })
-                    .map(|t| RegisterCluster::parse(&t, config))
-                    .collect();
-                children?
-            })
-            .derived_from(tree.attribute("derivedFrom").map(|s| s.to_owned()))
-            .build(config.validate_level)
-            .map_err(|e| SVDError::from(e).at(tree.id()))
+        parse_cluster_info(tree, config, None)
     }
 }
+
+fn parse_cluster_info(
+    tree: &Node,
+    config: &Config,
+    skips: Option<&Skips>,
+) -> Result<ClusterInfo, SVDErrorAt> {
+    let mut cluster = ClusterInfo::builder()
+        .name(tree.get_child_text("name")?)
+        .description(tree.get_child_text_opt("description")?)
+        .alternate_cluster(tree.get_child_text_opt("alternateCluster")?)
+        .header_struct_name(tree.get_child_text_opt("headerStructName")?)
+        .address_offset(tree.get_child_u32("addressOffset")?)
+        .default_register_properties(RegisterProperties::parse(tree, config)?)
+        .children(parse_children(
+            tree.children().filter(|t| {
+                t.is_element() && (t.has_tag_name("register") || t.has_tag_name("cluster"))
+            }),
+            skips,
+            |t| registercluster::parse_register_cluster(t, config, skips),
+        )?)
+        .derived_from(tree.attribute("derivedFrom").map(|s| s.to_owned()))
+        .build(config.validate_level)
+        .map_err(|e| SVDError::from(e).at(tree.id()))?;
+    cluster.number_styles = number_styles(
+        tree,
+        &["addressOffset", "size", "resetValue", "resetMask"],
+        config,
+    );
+    Ok(cluster)
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::svd::{Cpu, Endian};
+use crate::svd::{Cpu, Endian, SauRegionsConfig};
 use crate::types::BoolParse;
 
 impl Parse for Cpu {
@@ -9,9 +9,11 @@ impl Parse for Cpu {
 
     fn parse(tree: &Node, config: &Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("cpu") {
-            return Err(SVDError::NotExpectedTag("cpu".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("cpu", tree));
         }
 
+        let nvic_priority_bits = tree.get_child_u32("nvicPrioBits")?;
+        bounds::check_child(tree, "nvicPrioBits", nvic_priority_bits.into(), config)?;
         Cpu::builder()
             .name(tree.get_child_text("name")?)
             .revision(tree.get_child_text("revision")?)
@@ -25,10 +27,15 @@ impl Parse for Cpu {
             .itcm_present(optional::<BoolParse>("itcmPresent", tree, &())?)
             .dtcm_present(optional::<BoolParse>("dtcmPresent", tree, &())?)
             .vtor_present(optional::<BoolParse>("vtorPresent", tree, &())?)
-            .nvic_priority_bits(tree.get_child_u32("nvicPrioBits")?)
+            .nvic_priority_bits(nvic_priority_bits)
             .has_vendor_systick(tree.get_child_bool("vendorSystickConfig")?)
             .device_num_interrupts(optional::<u32>("deviceNumInterrupts", tree, &())?)
             .sau_num_regions(optional::<u32>("sauNumRegions", tree, &())?)
+            .sau_regions_config(optional::<SauRegionsConfig>(
+                "sauRegionsConfig",
+                tree,
+                config,
+            )?)
             .build(config.validate_level)
             .map_err(|e| SVDError::from(e).at(tree.id()))
     }
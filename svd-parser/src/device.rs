@@ -1,7 +1,7 @@
 use super::*;
 #[cfg(feature = "unstable-riscv")]
 use crate::svd::riscv::Riscv;
-use crate::svd::{cpu::Cpu, peripheral::Peripheral, registerproperties::RegisterProperties};
+use crate::svd::{cpu::Cpu, registerproperties::RegisterProperties};
 
 /// Parses a SVD file
 impl Parse for Device {
@@ -10,58 +10,67 @@ impl Parse for Device {
     type Config = Config;
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
-        if !tree.has_tag_name("device") {
-            return Err(SVDError::NotExpectedTag("device".to_string()).at(tree.id()));
-        }
+        parse_device(tree, config, None)
+    }
+}
+
+/// Parse a device, leaving out invalid children in `skips` if given
+pub(crate) fn parse_device(
+    tree: &Node,
+    config: &Config,
+    skips: Option<&Skips>,
+) -> Result<Device, SVDErrorAt> {
+    if !tree.has_tag_name("device") {
+        return Err(SVDError::not_expected("device", tree));
+    }
 
-        let mut device = Device::builder()
-            .vendor(tree.get_child_text_opt("vendor")?)
-            .vendor_id(tree.get_child_text_opt("vendorID")?)
-            .name(tree.get_child_text("name")?)
-            .series(tree.get_child_text_opt("series")?)
-            .license_text(tree.get_child_text_opt("licenseText")?)
-            .cpu(optional::<Cpu>("cpu", tree, config)?)
-            .header_system_filename(tree.get_child_text_opt("headerSystemFilename")?)
-            .header_definitions_prefix(tree.get_child_text_opt("headerDefinitionsPrefix")?)
-            .default_register_properties(RegisterProperties::parse(tree, config)?)
-            .peripherals({
-                let ps: Result<Vec<_>, _> = tree
-                    .get_child_elem("peripherals")?
+    let mut device = Device::builder()
+        .vendor(tree.get_child_text_opt("vendor")?)
+        .vendor_id(tree.get_child_text_opt("vendorID")?)
+        .name(tree.get_child_text("name")?)
+        .series(tree.get_child_text_opt("series")?)
+        .version(tree.get_child_text_opt("version")?)
+        .description(tree.get_child_text_opt("description")?)
+        .license_text(tree.get_child_text_opt("licenseText")?)
+        .cpu(optional::<Cpu>("cpu", tree, config)?)
+        .header_system_filename(tree.get_child_text_opt("headerSystemFilename")?)
+        .header_definitions_prefix(tree.get_child_text_opt("headerDefinitionsPrefix")?)
+        .default_register_properties(RegisterProperties::parse(tree, config)?)
+        .peripherals({
+            parse_children(
+                tree.get_child_elem("peripherals")?
                     .children()
-                    .filter(Node::is_element)
-                    .map(|t| Peripheral::parse(&t, config))
-                    .collect();
-                ps?
-            });
-        #[cfg(feature = "unstable-riscv")]
-        if let Some(riscv) = optional::<Riscv>("riscv", tree, config)? {
-            device = device.riscv(riscv);
-        }
-        if let Some(version) = tree.get_child_text_opt("version")? {
-            device = device.version(version)
-        }
-        if let Some(description) = tree.get_child_text_opt("description")? {
-            device = device.description(description)
-        }
-        if let Some(bits) = optional::<u32>("addressUnitBits", tree, &())? {
-            device = device.address_unit_bits(bits)
-        }
-        if let Some(width) = optional::<u32>("width", tree, &())? {
-            device = device.width(width)
-        }
-        // TODO: accept namespace other than `xs`
-        // Now assert `xs` exists and `noNamespaceSchemaLocation` is under `xs`
-        if let Some(xmlns_xs) = tree.lookup_namespace_uri(Some("xs")) {
-            device = device.xmlns_xs(xmlns_xs.to_string());
-            if let Some(location) = tree.attribute((xmlns_xs, "noNamespaceSchemaLocation")) {
-                device = device.no_namespace_schema_location(location.to_string());
-            }
-        }
-        if let Some(schema_version) = tree.attribute("schemaVersion") {
-            device = device.schema_version(schema_version.to_string());
+                    .filter(Node::is_element),
+                skips,
+                |t| peripheral::parse_peripheral(t, config, skips),
+            )?
+        });
+    #[cfg(feature = "unstable-riscv")]
+    if let Some(riscv) = optional::<Riscv>("riscv", tree, config)? {
+        device = device.riscv(riscv);
+    }
+    if let Some(bits) = optional::<u32>("addressUnitBits", tree, &())? {
+        bounds::check_child(tree, "addressUnitBits", bits.into(), config)?;
+        device = device.address_unit_bits(bits)
+    }
+    if let Some(width) = optional::<u32>("width", tree, &())? {
+        bounds::check_child(tree, "width", width.into(), config)?;
+        device = device.width(width)
+    }
+    // TODO: accept namespace other than `xs`
+    // Now assert `xs` exists and `noNamespaceSchemaLocation` is under `xs`
+    if let Some(xmlns_xs) = tree.lookup_namespace_uri(Some("xs")) {
+        device = device.xmlns_xs(xmlns_xs.to_string());
+        if let Some(location) = tree.attribute((xmlns_xs, "noNamespaceSchemaLocation")) {
+            device = device.no_namespace_schema_location(location.to_string());
         }
-        device
-            .build(config.validate_level)
-            .map_err(|e| SVDError::from(e).at(tree.id()))
     }
+    if let Some(schema_version) = tree.attribute("schemaVersion") {
+        device = device.schema_version(schema_version.to_string());
+    }
+    let mut device = device
+        .build(config.validate_level)
+        .map_err(|e| SVDError::from(e).at(tree.id()))?;
+    device.number_styles = number_styles(tree, &["size", "resetValue", "resetMask"], config);
+    Ok(device)
 }
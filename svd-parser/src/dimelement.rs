@@ -1,5 +1,5 @@
 use super::*;
-use crate::svd::{DimArrayIndex, DimElement, EnumeratedValue};
+use crate::svd::{dimelement, DimArrayIndex, DimElement, EnumeratedValue};
 
 impl Parse for DimElement {
     type Object = Self;
@@ -7,10 +7,18 @@ impl Parse for DimElement {
     type Config = Config;
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
+        let dim = tree.get_child_u32("dim")?;
+        bounds::check_child(tree, "dim", dim.into(), config)?;
+        let dim_increment = tree.get_child_u32("dimIncrement")?;
+        bounds::check_child(tree, "dimIncrement", dim_increment.into(), config)?;
         DimElement::builder()
-            .dim(tree.get_child_u32("dim")?)
-            .dim_increment(tree.get_child_u32("dimIncrement")?)
-            .dim_index(optional::<DimIndex>("dimIndex", tree, config)?)
+            .dim(dim)
+            .dim_increment(dim_increment)
+            .dim_index(
+                tree.get_child("dimIndex")
+                    .map(|t| parse_dim_index(&t, dim))
+                    .transpose()?,
+            )
             .dim_name(tree.get_child_text_opt("dimName")?)
             .dim_array_index(optional::<DimArrayIndex>("dimArrayIndex", tree, config)?)
             .build(config.validate_level)
@@ -34,7 +42,7 @@ impl Parse for DimArrayIndex {
                         if t.has_tag_name("enumeratedValue") {
                             EnumeratedValue::parse(&t, config)
                         } else {
-                            Err(SVDError::NotExpectedTag("enumeratedValue".to_string()).at(t.id()))
+                            Err(SVDError::not_expected("enumeratedValue", &t))
                         }
                     })
                     .collect();
@@ -44,15 +52,19 @@ impl Parse for DimArrayIndex {
     }
 }
 
-struct DimIndex;
-
-impl Parse for DimIndex {
-    type Object = Vec<String>;
-    type Error = SVDErrorAt;
-    type Config = Config;
-
-    fn parse(tree: &Node, _config: &Self::Config) -> Result<Vec<String>, Self::Error> {
-        DimElement::parse_indexes(tree.get_text()?)
-            .ok_or_else(|| SVDError::DimIndexParse.at(tree.id()))
-    }
+/// Parse `dimIndex` of an array with `dim` elements.
+///
+/// Lists longer than `dim` are rejected before their ranges are expanded, so
+/// `0-4294967295` doesn't allocate all indexes.
+fn parse_dim_index(tree: &Node, dim: u32) -> Result<Vec<String>, SVDErrorAt> {
+    let text = tree.get_text()?;
+    DimElement::parse_indexes_max(&text, dim).map_err(|e| {
+        match e {
+            dimelement::Error::IndexCount(dim, found) => {
+                SVDError::IncorrectDimIndexesCount(dim as usize, found)
+            }
+            _ => SVDError::DimIndexParse,
+        }
+        .at(tree.id())
+    })
 }
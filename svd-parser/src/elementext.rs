@@ -2,13 +2,14 @@
 //! This module is extends roxmltree::Element objects with convenience methods
 
 use roxmltree::Node;
+use std::borrow::Cow;
 
 use super::types::BoolParse;
 use super::{Parse, SVDError, SVDErrorAt};
 
 /// Defines extensions for implementation over roxmltree::Node
 pub trait ElementExt {
-    fn get_child<K>(&self, k: K) -> Option<Node>
+    fn get_child<K>(&self, k: K) -> Option<Node<'_, '_>>
     where
         K: AsRef<str>;
     fn get_child_text_opt<K>(&self, k: K) -> Result<Option<String>, SVDErrorAt>
@@ -18,9 +19,9 @@ pub trait ElementExt {
     where
         K: AsRef<str>;
 
-    fn get_text(&self) -> Result<&str, SVDErrorAt>;
+    fn get_text(&self) -> Result<Cow<'_, str>, SVDErrorAt>;
 
-    fn get_child_elem(&self, n: &str) -> Result<Node, SVDErrorAt>;
+    fn get_child_elem(&self, n: &str) -> Result<Node<'_, '_>, SVDErrorAt>;
     fn get_child_u32(&self, n: &str) -> Result<u32, SVDErrorAt>;
     fn get_child_u64(&self, n: &str) -> Result<u64, SVDErrorAt>;
     fn get_child_bool(&self, n: &str) -> Result<bool, SVDErrorAt>;
@@ -30,7 +31,7 @@ pub trait ElementExt {
 
 /// Implements extensions for roxmltree::Node
 impl<'a, 'input> ElementExt for Node<'a, 'input> {
-    fn get_child<K>(&self, k: K) -> Option<Node>
+    fn get_child<K>(&self, k: K) -> Option<Node<'_, '_>>
     where
         K: AsRef<str>,
     {
@@ -67,20 +68,31 @@ impl<'a, 'input> ElementExt for Node<'a, 'input> {
             .ok_or_else(|| SVDError::MissingTag(k.to_string()).at(self.id()))
     }
 
-    /// Get text contained by an XML Element
-    fn get_text(&self) -> Result<&str, SVDErrorAt> {
-        match self.text() {
+    /// Get text contained by an XML Element.
+    ///
+    /// Leading and trailing whitespace is trimmed. Text split by comments or
+    /// processing instructions is joined.
+    fn get_text(&self) -> Result<Cow<'_, str>, SVDErrorAt> {
+        let mut texts = self
+            .children()
+            .filter(|c| c.is_text())
+            .filter_map(|c| c.text());
+        match (texts.next(), texts.next()) {
             // TODO: return error on `strict`
-            Some(s) => Ok(s.trim()),
+            (Some(s), None) => Ok(Cow::Borrowed(s.trim())),
+            (Some(first), Some(second)) => {
+                let text: String = [first, second].into_iter().chain(texts).collect();
+                Ok(Cow::Owned(text.trim().to_string()))
+            }
             // FIXME: Doesn't look good because SVDError doesn't format by itself. We already
             // capture the element and this information can be used for getting the name
             // This would fix ParseError
-            None => Err(SVDError::EmptyTag(self.tag_name().name().to_string()).at(self.id())),
+            (None, _) => Err(SVDError::EmptyTag(self.tag_name().name().to_string()).at(self.id())),
         }
     }
 
     /// Get a named child element from an XML Element
-    fn get_child_elem(&self, n: &str) -> Result<Node, SVDErrorAt> {
+    fn get_child_elem(&self, n: &str) -> Result<Node<'_, '_>, SVDErrorAt> {
         self.get_child(n)
             .ok_or_else(|| SVDError::MissingTag(n.to_string()).at(self.id()))
     }
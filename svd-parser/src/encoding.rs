@@ -0,0 +1,120 @@
+//! Decoding SVD files from bytes.
+
+use super::SVDError;
+use std::borrow::Cow;
+
+/// Encoding of the input of [`parse_bytes`](crate::parse_bytes)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EncodingHint {
+    /// Detect by byte order mark, then by XML declaration.
+    /// UTF-8 is used if neither is present, Latin-1 if the input is not valid UTF-8
+    #[default]
+    Auto,
+    /// UTF-8, fail on invalid input
+    Utf8Strict,
+    /// UTF-16 little endian
+    Utf16Le,
+    /// UTF-16 big endian
+    Utf16Be,
+    /// ISO-8859-1
+    Latin1,
+}
+
+/// Decode `bytes`. Returns the text with a note on how it was decoded,
+/// like `UTF-16LE (byte order mark)`.
+///
+/// Valid UTF-8 is not copied. Byte order mark is kept in UTF-8 text so offsets in it
+/// match offsets in `bytes`.
+pub fn decode(bytes: &[u8], hint: EncodingHint) -> Result<(Cow<'_, str>, String), SVDError> {
+    let note = |encoding: &str, reason: &str| format!("{} ({})", encoding, reason);
+    match hint {
+        EncodingHint::Utf8Strict => Ok((utf8(bytes)?, note("UTF-8", "requested"))),
+        EncodingHint::Utf16Le => Ok((
+            utf16(bytes, u16::from_le_bytes)?,
+            note("UTF-16LE", "requested"),
+        )),
+        EncodingHint::Utf16Be => Ok((
+            utf16(bytes, u16::from_be_bytes)?,
+            note("UTF-16BE", "requested"),
+        )),
+        EncodingHint::Latin1 => Ok((latin1(bytes), note("Latin-1", "requested"))),
+        EncodingHint::Auto => {
+            if bytes.starts_with(&[0xef, 0xbb, 0xbf]) {
+                Ok((utf8(bytes)?, note("UTF-8", "byte order mark")))
+            } else if bytes.starts_with(&[0xff, 0xfe]) {
+                Ok((
+                    utf16(&bytes[2..], u16::from_le_bytes)?,
+                    note("UTF-16LE", "byte order mark"),
+                ))
+            } else if bytes.starts_with(&[0xfe, 0xff]) {
+                Ok((
+                    utf16(&bytes[2..], u16::from_be_bytes)?,
+                    note("UTF-16BE", "byte order mark"),
+                ))
+            } else if bytes.starts_with(b"<\0") {
+                Ok((
+                    utf16(bytes, u16::from_le_bytes)?,
+                    note("UTF-16LE", "byte pattern"),
+                ))
+            } else if bytes.starts_with(b"\0<") {
+                Ok((
+                    utf16(bytes, u16::from_be_bytes)?,
+                    note("UTF-16BE", "byte pattern"),
+                ))
+            } else if declared_latin1(bytes) {
+                Ok((latin1(bytes), note("Latin-1", "XML declaration")))
+            } else {
+                match core::str::from_utf8(bytes) {
+                    Ok(text) => Ok((Cow::Borrowed(text), "UTF-8".into())),
+                    Err(_) => Ok((latin1(bytes), note("Latin-1", "invalid UTF-8"))),
+                }
+            }
+        }
+    }
+}
+
+fn utf8(bytes: &[u8]) -> Result<Cow<'_, str>, SVDError> {
+    core::str::from_utf8(bytes)
+        .map(Cow::Borrowed)
+        .map_err(|e| SVDError::Encoding(e.to_string()))
+}
+
+fn utf16(bytes: &[u8], read: fn([u8; 2]) -> u16) -> Result<Cow<'_, str>, SVDError> {
+    if bytes.len() % 2 != 0 {
+        return Err(SVDError::Encoding(
+            "odd number of bytes in UTF-16 input".into(),
+        ));
+    }
+    let units = bytes.chunks_exact(2).map(|c| read([c[0], c[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map(Cow::Owned)
+        .map_err(|e| SVDError::Encoding(e.to_string()))
+}
+
+fn latin1(bytes: &[u8]) -> Cow<'_, str> {
+    if bytes.is_ascii() {
+        // ASCII is valid UTF-8
+        Cow::Borrowed(core::str::from_utf8(bytes).unwrap())
+    } else {
+        Cow::Owned(bytes.iter().map(|&b| b as char).collect())
+    }
+}
+
+/// Check `encoding` attribute of the XML declaration
+fn declared_latin1(bytes: &[u8]) -> bool {
+    let end = bytes
+        .iter()
+        .position(|&b| b == b'>')
+        .unwrap_or(bytes.len())
+        .min(200);
+    let declaration = String::from_utf8_lossy(&bytes[..end]).to_lowercase();
+    if !declaration.starts_with("<?xml") {
+        return false;
+    }
+    ["iso-8859-1", "latin1", "latin-1"].iter().any(|e| {
+        declaration.contains(&format!("encoding=\"{}\"", e))
+            || declaration.contains(&format!("encoding='{}'", e))
+    })
+}
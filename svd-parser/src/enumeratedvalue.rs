@@ -1,5 +1,6 @@
 use super::*;
-use crate::svd::EnumeratedValue;
+use crate::svd::{BitRange, EnumeratedValue};
+use crate::types::parse_scaled_u64;
 
 impl Parse for EnumeratedValue {
     type Object = Self;
@@ -8,15 +9,60 @@ impl Parse for EnumeratedValue {
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("enumeratedValue") {
-            return Err(SVDError::NotExpectedTag("enumeratedValue".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("enumeratedValue", tree));
         }
 
-        EnumeratedValue::builder()
+        let (value, dont_care) = match tree.get_child("value") {
+            Some(v) => {
+                let (value, dont_care) =
+                    parse_value(&v.get_text()?, field_width(tree, config), config)
+                        .map_err(|e| e.at(v.id()))?;
+                (Some(value), dont_care)
+            }
+            None => (None, None),
+        };
+
+        let mut ev = EnumeratedValue::builder()
             .name(tree.get_child_text("name")?)
             .description(tree.get_child_text_opt("description")?)
-            .value(optional::<u64>("value", tree, &())?)
+            .value(value)
+            .dont_care(dont_care)
             .is_default(tree.get_child_bool("isDefault").ok())
             .build(config.validate_level)
-            .map_err(|e| SVDError::from(e).at(tree.id()))
+            .map_err(|e| SVDError::from(e).at(tree.id()))?;
+        ev.number_styles = number_styles(tree, &["value"], config);
+        Ok(ev)
+    }
+}
+
+/// Width of the field the enumerated value belongs to, if any
+fn field_width(tree: &Node, config: &Config) -> Option<u32> {
+    let field = tree.ancestors().find(|n| n.has_tag_name("field"))?;
+    BitRange::parse(&field, config).ok().map(|br| br.width)
+}
+
+/// Parse enumerated value with optional mask of don't care bits.
+///
+/// `-1` is read as all ones of `width` bits, all 64 if the width is unknown
+fn parse_value(
+    text: &str,
+    width: Option<u32>,
+    config: &Config,
+) -> Result<(u64, Option<u64>), SVDError> {
+    if let Some(bin) = text.strip_prefix('#').or_else(|| text.strip_prefix("0b")) {
+        let bin = bin.to_lowercase();
+        if bin.contains('x') {
+            let value = u64::from_str_radix(&bin.replace('x', "0"), 2)?;
+            let mask = u64::from_str_radix(&bin.replace(['0', '1'], "0").replace('x', "1"), 2)?;
+            return Ok((value, Some(mask)));
+        }
+    }
+    if text == "-1" && config.minus_one_as_all_ones {
+        let all_ones = match width {
+            Some(width) if width < 64 => (1 << width) - 1,
+            _ => u64::MAX,
+        };
+        return Ok((all_ones, None));
     }
+    Ok((parse_scaled_u64(text)?, None))
 }
@@ -8,10 +8,11 @@ impl Parse for EnumeratedValues {
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("enumeratedValues") {
-            return Err(SVDError::NotExpectedTag("enumeratedValues".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("enumeratedValues", tree));
         }
         EnumeratedValues::builder()
             .name(tree.get_child_text_opt("name")?)
+            .header_enum_name(tree.get_child_text_opt("headerEnumName")?)
             .usage(optional::<Usage>("usage", tree, config)?)
             .derived_from(tree.attribute("derivedFrom").map(|s| s.to_owned()))
             .values({
@@ -25,7 +26,7 @@ impl Parse for EnumeratedValues {
                         if t.has_tag_name("enumeratedValue") {
                             EnumeratedValue::parse(&t, config)
                         } else {
-                            Err(SVDError::NotExpectedTag("enumeratedValue".to_string()).at(t.id()))
+                            Err(SVDError::not_expected("enumeratedValue", &t))
                         }
                     })
                     .collect();
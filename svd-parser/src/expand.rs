@@ -6,9 +6,22 @@ use std::fmt;
 use std::mem::take;
 use svd_rs::{
     array::names, cluster, field, peripheral, register, Cluster, ClusterInfo, DeriveFrom, Device,
-    EnumeratedValues, Field, Peripheral, Register, RegisterCluster, RegisterProperties,
+    EnumeratedValues, Field, Peripheral, ProvenanceRecorder, Register, RegisterCluster,
+    RegisterProperties,
 };
 
+/// Name of [`expand`] in [`ProvenanceRecorder`]
+const EXPAND: &str = "expand";
+/// Name of [`expand_properties`] in [`ProvenanceRecorder`]
+const EXPAND_PROPERTIES: &str = "expand_properties";
+
+/// Record in `provenance` that element `path` was derived from `dpath`
+fn record_derive(provenance: &mut ProvenanceRecorder, path: &str, dpath: &str) {
+    provenance.record(EXPAND, || {
+        (path.into(), format!("derived from `{}`", dpath))
+    });
+}
+
 /// Path to `peripheral` or `cluster` element
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
 pub struct BlockPath {
@@ -341,15 +354,23 @@ impl<'a> Index<'a> {
     }
 }
 
+/// `in_path` is path of the parent in the source device, `provenance` is moved to
+/// paths of the expanded elements
 fn expand_register_cluster(
     regs: &mut Vec<RegisterCluster>,
     rc: RegisterCluster,
     path: &BlockPath,
     index: &Index,
+    in_path: &str,
+    provenance: &mut ProvenanceRecorder,
 ) -> Result<()> {
     match rc {
-        RegisterCluster::Cluster(c) => expand_cluster_array(regs, c, path, index)?,
-        RegisterCluster::Register(r) => expand_register_array(regs, r, path, index)?,
+        RegisterCluster::Cluster(c) => {
+            expand_cluster_array(regs, c, path, index, in_path, provenance)?
+        }
+        RegisterCluster::Register(r) => {
+            expand_register_array(regs, r, path, index, in_path, provenance)?
+        }
     }
     Ok(())
 }
@@ -359,25 +380,41 @@ fn expand_cluster_array(
     mut c: Cluster,
     path: &BlockPath,
     index: &Index,
+    in_path: &str,
+    provenance: &mut ProvenanceRecorder,
 ) -> Result<()> {
+    let c_path = format!("{}.{}", in_path, c.name);
     let mut cpath = None;
     let dpath = c.derived_from.take();
     if let Some(dpath) = dpath {
+        record_derive(provenance, &c_path, &dpath);
         cpath = derive_cluster(&mut c, &dpath, path, index)?;
     }
     let cpath = cpath.unwrap_or_else(|| path.new_cluster(&c.name));
 
     for rc in take(&mut c.children) {
-        expand_register_cluster(&mut c.children, rc, &cpath, index)?;
+        expand_register_cluster(&mut c.children, rc, &cpath, index, &c_path, provenance)?;
     }
 
-    match c {
-        Cluster::Single(c) => expand_cluster(regs, c),
+    let clusters: Vec<ClusterInfo> = match c {
+        Cluster::Single(c) => vec![c],
         Cluster::Array(info, dim) => {
-            for c in cluster::expand(&info, &dim) {
-                expand_cluster(regs, c);
-            }
+            dim.check_offsets(info.address_offset)?;
+            cluster::expand(&info, &dim).collect()
         }
+    };
+    if let Some(first) = clusters.first().filter(|_| provenance.is_enabled()) {
+        // registers of clusters are moved to the parent
+        for rc in &first.children {
+            let to: Vec<String> = clusters
+                .iter()
+                .map(|c| format!("{}.{}_{}", in_path, c.name, rc.name()))
+                .collect();
+            provenance.rekey(&format!("{}.{}", c_path, rc.name()), &to);
+        }
+    }
+    for c in clusters {
+        expand_cluster(regs, c);
     }
     Ok(())
 }
@@ -482,6 +519,8 @@ fn expand_cluster(regs: &mut Vec<RegisterCluster>, c: ClusterInfo) {
             RegisterCluster::Register(mut r) => {
                 r.name = format!("{}_{}", c.name, r.name);
                 r.address_offset += c.address_offset;
+                // the cluster is gone, keep its properties in the register
+                r.properties = r.properties.derive_from(&c.default_register_properties);
                 regs.push(r.into());
             }
         }
@@ -493,17 +532,21 @@ fn expand_register_array(
     mut r: Register,
     path: &BlockPath,
     index: &Index,
+    in_path: &str,
+    provenance: &mut ProvenanceRecorder,
 ) -> Result<()> {
+    let r_path = format!("{}.{}", in_path, r.name);
     let mut rpath = None;
     let dpath = r.derived_from.take();
     if let Some(dpath) = dpath {
+        record_derive(provenance, &r_path, &dpath);
         rpath = derive_register(&mut r, &dpath, path, index)?;
     }
     let rpath = rpath.unwrap_or_else(|| path.new_register(&r.name));
 
     if let Some(field) = r.fields.as_mut() {
         for f in take(field) {
-            expand_field(field, f, &rpath, index)?;
+            expand_field(field, f, &rpath, index, &r_path, provenance)?;
         }
     }
 
@@ -512,21 +555,46 @@ fn expand_register_array(
             regs.push(r.into());
         }
         Register::Array(info, dim) => {
+            dim.check_offsets(info.address_offset)?;
+            let start = regs.len();
             regs.extend(register::expand(&info, &dim).map(|r| r.single().into()));
+            rekey_elements(
+                provenance,
+                &r_path,
+                in_path,
+                regs[start..].iter().map(|r| r.name()),
+            );
         }
     }
     Ok(())
 }
 
+/// Move history of array at `from` to its elements named `names` in `parent`
+fn rekey_elements<'a>(
+    provenance: &mut ProvenanceRecorder,
+    from: &str,
+    parent: &str,
+    names: impl Iterator<Item = &'a String>,
+) {
+    if provenance.is_enabled() {
+        let to: Vec<String> = names.map(|n| format!("{}.{}", parent, n)).collect();
+        provenance.rekey(from, &to);
+    }
+}
+
 fn expand_field(
     fields: &mut Vec<Field>,
     mut f: Field,
     rpath: &RegisterPath,
     index: &Index,
+    in_path: &str,
+    provenance: &mut ProvenanceRecorder,
 ) -> Result<()> {
+    let f_path = format!("{}.{}", in_path, f.name);
     let mut fpath = None;
     let dpath = f.derived_from.take();
     if let Some(dpath) = dpath {
+        record_derive(provenance, &f_path, &dpath);
         fpath = derive_field(&mut f, &dpath, rpath, index)?;
     }
     let fpath = fpath.unwrap_or_else(|| rpath.new_field(&f.name));
@@ -534,6 +602,12 @@ fn expand_field(
     for ev in &mut f.enumerated_values {
         let dpath = ev.derived_from.take();
         if let Some(dpath) = dpath {
+            provenance.record(EXPAND, || {
+                (
+                    f_path.clone(),
+                    format!("enumerated values derived from `{}`", dpath),
+                )
+            });
             derive_enumerated_values(ev, &dpath, &fpath, index)?;
         }
     }
@@ -543,7 +617,14 @@ fn expand_field(
             fields.push(f);
         }
         Field::Array(info, dim) => {
+            let start = fields.len();
             fields.extend(field::expand(&info, &dim).map(Field::Single));
+            rekey_elements(
+                provenance,
+                &f_path,
+                in_path,
+                fields[start..].iter().map(|f| &f.name),
+            );
         }
     }
 
@@ -558,22 +639,16 @@ pub fn derive_enumerated_values(
 ) -> Result<EnumPath> {
     let mut v: Vec<&str> = dpath.split('.').collect();
     let dname = v.pop().unwrap();
-    let d = if v.is_empty() {
+    let (d, tried) = if v.is_empty() {
         // Only EVNAME: Must be in one of fields in same register
         let rdpath = &fpath.register;
-        if let Some(r) = index.registers.get(rdpath) {
-            let mut found = None;
-            for f in r.fields() {
+        let found = index.registers.get(rdpath).and_then(|r| {
+            r.fields().find_map(|f| {
                 let epath = EnumPath::new(rdpath.new_field(&f.name), dname);
-                if let Some(d) = index.evs.get(&epath) {
-                    found = Some((d, epath));
-                    break;
-                }
-            }
-            found
-        } else {
-            None
-        }
+                index.evs.get(&epath).map(|d| (d, epath))
+            })
+        });
+        (found, format!("fields of register {}", rdpath))
     } else {
         let fdname = v.pop().unwrap();
         let fdpath = if v.is_empty() {
@@ -591,7 +666,8 @@ pub fn derive_enumerated_values(
             FieldPath::new(rdpath, fdname)
         };
         let epath = EnumPath::new(fdpath, dname);
-        index.evs.get(&epath).map(|d| (d, epath))
+        let tried = epath.to_string();
+        (index.evs.get(&epath).map(|d| (d, epath)), tried)
     };
 
     if let Some((d, epath)) = d {
@@ -603,9 +679,10 @@ pub fn derive_enumerated_values(
         }
     } else {
         Err(anyhow!(
-            "enumeratedValues {} not found, parent field: {:?}",
+            "enumeratedValues {} of field {} not found, looked for {}",
             dpath,
             fpath,
+            tried,
         ))
     }
 }
@@ -633,22 +710,27 @@ pub fn derive_peripheral(
 
 /// Creates clone of device with expanded arrays of peripherals, clusters, registers and fields.
 /// Also resolves all `derivedFrom` reference pathes
+///
+/// History in [`Device::provenance`] is moved to paths of the expanded elements.
 pub fn expand(indevice: &Device) -> Result<Device> {
     let mut device = indevice.clone();
+    let mut provenance = take(&mut device.provenance);
 
     let index = Index::create(indevice);
 
     let peripherals = take(&mut device.peripherals);
     for mut p in peripherals {
+        let p_path = p.name.clone();
         let mut path = None;
         let dpath = p.derived_from.take();
         if let Some(dpath) = dpath {
+            record_derive(&mut provenance, &p_path, &dpath);
             path = derive_peripheral(&mut p, &dpath, &index)?;
         }
         let path = path.unwrap_or_else(|| BlockPath::new(&p.name));
         if let Some(regs) = p.registers.as_mut() {
             for rc in take(regs) {
-                expand_register_cluster(regs, rc, &path, &index)?;
+                expand_register_cluster(regs, rc, &path, &index, &p_path, &mut provenance)?;
             }
         }
         match p {
@@ -656,32 +738,46 @@ pub fn expand(indevice: &Device) -> Result<Device> {
                 device.peripherals.push(p);
             }
             Peripheral::Array(info, dim) => {
-                device
-                    .peripherals
-                    .extend(peripheral::expand(&info, &dim).map(Peripheral::Single));
+                let elements: Vec<Peripheral> = peripheral::expand(&info, &dim)
+                    .map(Peripheral::Single)
+                    .collect();
+                if provenance.is_enabled() {
+                    let to: Vec<String> = elements.iter().map(|p| p.name.clone()).collect();
+                    provenance.rekey(&p_path, &to);
+                }
+                device.peripherals.extend(elements);
             }
         }
     }
 
+    device.provenance = provenance;
     Ok(device)
 }
 
-/// Takes register `size`, `access`, `reset_value` and `reset_mask`
-/// from peripheral or device properties if absent in register
+/// Takes register `size`, `access`, `protection`, `reset_value` and `reset_mask`
+/// from cluster, peripheral or device properties if absent in register
 pub fn expand_properties(device: &mut Device) {
+    let mut provenance = take(&mut device.provenance);
     let default = device.default_register_properties;
     for p in &mut device.peripherals {
         if p.derived_from.is_some() {
             continue;
         }
+        let p = &mut **p;
         let default = p.default_register_properties.derive_from(&default);
         if let Some(regs) = p.registers.as_mut() {
-            expand_properties_registers(regs, &default);
+            expand_properties_registers(regs, &default, &p.name, &mut provenance);
         }
     }
+    device.provenance = provenance;
 }
 
-fn expand_properties_registers(regs: &mut [RegisterCluster], default: &RegisterProperties) {
+fn expand_properties_registers(
+    regs: &mut [RegisterCluster],
+    default: &RegisterProperties,
+    path: &str,
+    provenance: &mut ProvenanceRecorder,
+) {
     for rc in regs {
         match rc {
             RegisterCluster::Cluster(c) => {
@@ -689,14 +785,133 @@ fn expand_properties_registers(regs: &mut [RegisterCluster], default: &RegisterP
                     continue;
                 }
                 let default = c.default_register_properties.derive_from(default);
-                expand_properties_registers(&mut c.children, &default);
+                let path = format!("{}.{}", path, c.name);
+                expand_properties_registers(&mut c.children, &default, &path, provenance);
             }
             RegisterCluster::Register(r) => {
                 if r.derived_from.is_some() {
                     continue;
                 }
+                let old = r.properties;
                 r.properties = r.properties.derive_from(default);
+                if old != r.properties {
+                    provenance.record(EXPAND_PROPERTIES, || {
+                        let new = &r.properties;
+                        let inherited = [
+                            ("size", old.size.is_none() && new.size.is_some()),
+                            ("access", old.access.is_none() && new.access.is_some()),
+                            (
+                                "protection",
+                                old.protection.is_none() && new.protection.is_some(),
+                            ),
+                            (
+                                "resetValue",
+                                old.reset_value.is_none() && new.reset_value.is_some(),
+                            ),
+                            (
+                                "resetMask",
+                                old.reset_mask.is_none() && new.reset_mask.is_some(),
+                            ),
+                        ];
+                        let inherited: Vec<&str> = inherited
+                            .iter()
+                            .filter(|(_, i)| *i)
+                            .map(|(name, _)| *name)
+                            .collect();
+                        (
+                            format!("{}.{}", path, r.name),
+                            format!("inherited {}", inherited.join(", ")),
+                        )
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Reverse of [expand_properties].
+///
+/// Register `size`, `access`, `protection`, `reset_value` and `reset_mask` shared by all
+/// registers of a peripheral are moved to peripheral properties, and those shared by all
+/// peripherals are moved to device properties.
+/// Registers of the result inherit the same properties as before.
+pub fn compress_properties(device: &mut Device) {
+    expand_properties(device);
+    let mut peripherals = Vec::new();
+    for p in &mut device.peripherals {
+        if p.derived_from.is_some() {
+            continue;
+        }
+        let p = &mut **p;
+        let mut regs = Vec::new();
+        let mut clusters = Vec::new();
+        if let Some(rcs) = p.registers.as_mut() {
+            collect_properties(rcs, &mut regs, &mut clusters);
+        }
+        let parent = &mut p.default_register_properties;
+        hoist(parent, &mut regs, &mut clusters, |p| &mut p.size);
+        hoist(parent, &mut regs, &mut clusters, |p| &mut p.access);
+        hoist(parent, &mut regs, &mut clusters, |p| &mut p.protection);
+        hoist(parent, &mut regs, &mut clusters, |p| &mut p.reset_value);
+        hoist(parent, &mut regs, &mut clusters, |p| &mut p.reset_mask);
+        if !regs.is_empty() {
+            peripherals.push(parent);
+        }
+    }
+    let parent = &mut device.default_register_properties;
+    let clusters = &mut Vec::new();
+    hoist(parent, &mut peripherals, clusters, |p| &mut p.size);
+    hoist(parent, &mut peripherals, clusters, |p| &mut p.access);
+    hoist(parent, &mut peripherals, clusters, |p| &mut p.protection);
+    hoist(parent, &mut peripherals, clusters, |p| &mut p.reset_value);
+    hoist(parent, &mut peripherals, clusters, |p| &mut p.reset_mask);
+}
+
+fn collect_properties<'a>(
+    rcs: &'a mut [RegisterCluster],
+    regs: &mut Vec<&'a mut RegisterProperties>,
+    clusters: &mut Vec<&'a mut RegisterProperties>,
+) {
+    for rc in rcs {
+        match rc {
+            RegisterCluster::Cluster(c) => {
+                if c.derived_from.is_some() {
+                    continue;
+                }
+                let c = &mut **c;
+                clusters.push(&mut c.default_register_properties);
+                collect_properties(&mut c.children, regs, clusters);
+            }
+            RegisterCluster::Register(r) => {
+                if r.derived_from.is_some() {
+                    continue;
+                }
+                regs.push(&mut r.properties);
             }
         }
     }
 }
+
+/// If all `children` have the same value, move it to `parent`.
+/// Values in `intermediate` levels are cleared to not shadow it.
+fn hoist<T: Copy + PartialEq>(
+    parent: &mut RegisterProperties,
+    children: &mut [&mut RegisterProperties],
+    intermediate: &mut [&mut RegisterProperties],
+    field: fn(&mut RegisterProperties) -> &mut Option<T>,
+) {
+    let value = match children.first_mut() {
+        Some(first) => *field(first),
+        None => return,
+    };
+    if value.is_none() || children.iter_mut().any(|c| *field(c) != value) {
+        return;
+    }
+    *field(parent) = value;
+    for c in children.iter_mut() {
+        *field(c) = None;
+    }
+    for c in intermediate.iter_mut() {
+        *field(c) = None;
+    }
+}
@@ -0,0 +1,300 @@
+//! Loading families of similar devices.
+//!
+//! Devices of one family usually share most of their peripherals: the SVD files
+//! differ in memory sizes, a few extra peripherals and interrupt numbers. A
+//! [`FamilySet`] stores every distinct peripheral body once and keeps only names,
+//! base addresses and interrupts per device, so loading many devices of a family
+//! costs little more than loading one of them.
+
+use super::{parse_bytes_full, Config, EncodingHint, SVDError};
+use crate::svd::{Device, Interrupt, Peripheral, Register};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Options of [`load`]
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct FamilyLoadOptions {
+    /// Parser options used for every file
+    pub config: Config,
+    /// Parse files in at most as many threads as the system can run in parallel
+    pub parallel: bool,
+}
+
+impl FamilyLoadOptions {
+    /// Set parser options used for every file
+    pub fn config(mut self, val: Config) -> Self {
+        self.config = val;
+        self
+    }
+
+    /// Parse files in at most as many threads as the system can run in parallel
+    pub fn parallel(mut self, val: bool) -> Self {
+        self.parallel = val;
+        self
+    }
+}
+
+/// Peripheral of a device in a [`FamilySet`]
+#[derive(Clone, Debug)]
+struct PeripheralRef {
+    name: String,
+    base_address: u64,
+    interrupt: Vec<Interrupt>,
+    body: usize,
+}
+
+#[derive(Clone, Debug)]
+struct FamilyDevice {
+    /// Device without peripherals
+    device: Device,
+    peripherals: Vec<PeripheralRef>,
+}
+
+/// Deduplication statistics of a [`FamilySet`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FamilyStats {
+    /// Number of devices
+    pub devices: usize,
+    /// Number of peripherals in all devices
+    pub peripherals: usize,
+    /// Number of stored peripheral bodies
+    pub unique_peripherals: usize,
+}
+
+impl FamilyStats {
+    /// Part of peripherals which share storage with a peripheral of another device
+    pub fn shared_ratio(&self) -> f32 {
+        if self.peripherals == 0 {
+            0.
+        } else {
+            1. - self.unique_peripherals as f32 / self.peripherals as f32
+        }
+    }
+}
+
+/// Devices with identical peripherals stored once
+#[derive(Clone, Debug, Default)]
+pub struct FamilySet {
+    bodies: Vec<Arc<Peripheral>>,
+    devices: Vec<FamilyDevice>,
+    by_fingerprint: HashMap<u64, Vec<usize>>,
+}
+
+/// Parse SVD files at `paths` into a [`FamilySet`]
+pub fn load(paths: &[PathBuf], options: FamilyLoadOptions) -> Result<FamilySet, SVDError> {
+    let parse = |path: &PathBuf| -> Result<Device, SVDError> {
+        let bytes =
+            std::fs::read(path).map_err(|e| SVDError::Io(format!("{}: {}", path.display(), e)))?;
+        parse_bytes_full(&bytes, EncodingHint::Auto, &options.config)
+            .map(|output| output.device)
+            .map_err(|e| SVDError::InvalidFile(path.display().to_string(), format!("{:#}", e)))
+    };
+    let devices: Vec<Result<Device, SVDError>> = if options.parallel {
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(paths.len());
+        // workers take the next path from the queue until it is empty
+        let next = AtomicUsize::new(0);
+        let mut devices: Vec<_> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut parsed = Vec::new();
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            match paths.get(i) {
+                                Some(path) => parsed.push((i, parse(path))),
+                                None => return parsed,
+                            }
+                        }
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("SVD parser panicked"))
+                .collect()
+        });
+        devices.sort_by_key(|(i, _)| *i);
+        devices.into_iter().map(|(_, device)| device).collect()
+    } else {
+        paths.iter().map(parse).collect()
+    };
+
+    let mut set = FamilySet::default();
+    for device in devices {
+        set.insert(device?);
+    }
+    Ok(set)
+}
+
+fn fingerprint(body: &Peripheral) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl FamilySet {
+    /// Create empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `device`, sharing its peripherals with identical ones already in the set.
+    ///
+    /// Peripherals are identical if they only differ in name, base address and interrupts.
+    pub fn insert(&mut self, mut device: Device) {
+        let mut peripherals = Vec::with_capacity(device.peripherals.len());
+        for mut body in core::mem::take(&mut device.peripherals) {
+            let name = core::mem::take(&mut body.name);
+            let base_address = core::mem::take(&mut body.base_address);
+            let interrupt = core::mem::take(&mut body.interrupt);
+            peripherals.push(PeripheralRef {
+                name,
+                base_address,
+                interrupt,
+                body: self.intern(body),
+            });
+        }
+        self.devices.push(FamilyDevice {
+            device,
+            peripherals,
+        });
+    }
+
+    fn intern(&mut self, body: Peripheral) -> usize {
+        let candidates = self.by_fingerprint.entry(fingerprint(&body)).or_default();
+        if let Some(&i) = candidates.iter().find(|&&i| *self.bodies[i] == body) {
+            return i;
+        }
+        candidates.push(self.bodies.len());
+        self.bodies.push(Arc::new(body));
+        self.bodies.len() - 1
+    }
+
+    /// View of device `name`
+    pub fn device(&self, name: &str) -> Option<FamilyDeviceView<'_>> {
+        self.devices
+            .iter()
+            .find(|d| d.device.name == name)
+            .map(|device| FamilyDeviceView { set: self, device })
+    }
+
+    /// Views of all devices in order they were added
+    pub fn devices(&self) -> impl Iterator<Item = FamilyDeviceView<'_>> {
+        self.devices
+            .iter()
+            .map(move |device| FamilyDeviceView { set: self, device })
+    }
+
+    /// Deduplication statistics
+    pub fn stats(&self) -> FamilyStats {
+        FamilyStats {
+            devices: self.devices.len(),
+            peripherals: self.devices.iter().map(|d| d.peripherals.len()).sum(),
+            unique_peripherals: self.bodies.len(),
+        }
+    }
+}
+
+/// Read-only device of a [`FamilySet`]
+#[derive(Clone, Copy, Debug)]
+pub struct FamilyDeviceView<'a> {
+    set: &'a FamilySet,
+    device: &'a FamilyDevice,
+}
+
+impl<'a> FamilyDeviceView<'a> {
+    /// Device name
+    pub fn name(&self) -> &'a str {
+        &self.device.device.name
+    }
+
+    /// Device properties. `peripherals` of it is always empty
+    pub fn properties(&self) -> &'a Device {
+        &self.device.device
+    }
+
+    /// Peripherals of the device
+    pub fn peripherals(&self) -> impl Iterator<Item = FamilyPeripheralView<'a>> + 'a {
+        let set = self.set;
+        self.device
+            .peripherals
+            .iter()
+            .map(move |p| FamilyPeripheralView { set, peripheral: p })
+    }
+
+    /// Get peripheral by name
+    pub fn get_peripheral(&self, name: &str) -> Option<FamilyPeripheralView<'a>> {
+        self.peripherals().find(|p| p.name() == name)
+    }
+
+    /// Get register `name` of peripheral `peripheral`
+    pub fn get_register(&self, peripheral: &str, name: &str) -> Option<&'a Register> {
+        self.get_peripheral(peripheral)?.body().get_register(name)
+    }
+
+    /// Peripheral at `address` and offset in it
+    pub fn peripheral_at(&self, address: u64) -> Option<(FamilyPeripheralView<'a>, u64)> {
+        self.peripherals().find_map(|p| {
+            let offset = address.checked_sub(p.base_address())?;
+            p.body()
+                .address_block
+                .iter()
+                .flatten()
+                .any(|b| (b.offset as u64..b.offset as u64 + b.size as u64).contains(&offset))
+                .then_some((p, offset))
+        })
+    }
+
+    /// Make a standalone copy of the device, for example to validate it
+    pub fn to_device(&self) -> Device {
+        let mut device = self.device.device.clone();
+        device.peripherals = self.peripherals().map(|p| p.to_peripheral()).collect();
+        device
+    }
+}
+
+/// Read-only peripheral of a [`FamilyDeviceView`]
+#[derive(Clone, Copy, Debug)]
+pub struct FamilyPeripheralView<'a> {
+    set: &'a FamilySet,
+    peripheral: &'a PeripheralRef,
+}
+
+impl<'a> FamilyPeripheralView<'a> {
+    /// Peripheral name
+    pub fn name(&self) -> &'a str {
+        &self.peripheral.name
+    }
+
+    /// Base address of the peripheral
+    pub fn base_address(&self) -> u64 {
+        self.peripheral.base_address
+    }
+
+    /// Interrupts of the peripheral
+    pub fn interrupts(&self) -> &'a [Interrupt] {
+        &self.peripheral.interrupt
+    }
+
+    /// Shared body of the peripheral with empty name, zero base address and no interrupts
+    pub fn body(&self) -> &'a Arc<Peripheral> {
+        &self.set.bodies[self.peripheral.body]
+    }
+
+    /// Make a standalone copy of the peripheral
+    pub fn to_peripheral(&self) -> Peripheral {
+        let mut p = Peripheral::clone(self.body());
+        p.name = self.peripheral.name.clone();
+        p.base_address = self.peripheral.base_address;
+        p.interrupt = self.peripheral.interrupt.clone();
+        p
+    }
+}
@@ -1,7 +1,7 @@
 use super::*;
 use crate::svd::{
-    Access, BitRange, EnumeratedValues, Field, FieldInfo, ModifiedWriteValues, ReadAction,
-    WriteConstraint,
+    Access, BitRange, BitRangeType, EnumeratedValues, Field, FieldInfo, ModifiedWriteValues,
+    ReadAction, WriteConstraint,
 };
 
 impl Parse for Field {
@@ -21,10 +21,14 @@ impl Parse for FieldInfo {
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("field") {
-            return Err(SVDError::NotExpectedTag("field".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("field", tree));
         }
 
         let bit_range = BitRange::parse(tree, config)?;
+        if bit_range.range_type == BitRangeType::OffsetWidth {
+            bounds::check_child(tree, "bitWidth", bit_range.width.into(), config)?;
+        }
+        bounds::check_bit_range(tree, &bit_range, config)?;
         FieldInfo::builder()
             .name(tree.get_child_text("name")?)
             .description(tree.get_child_text_opt("description")?)
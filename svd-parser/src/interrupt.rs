@@ -8,7 +8,7 @@ impl Parse for Interrupt {
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("interrupt") {
-            return Err(SVDError::NotExpectedTag("interrupt".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("interrupt", tree));
         }
         let name = tree.get_child_text("name")?;
 
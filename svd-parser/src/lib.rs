@@ -28,6 +28,9 @@ pub use svd_rs as svd;
 
 pub use anyhow::Context;
 use roxmltree::{Document, Node, NodeId};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
 // ElementExt extends XML elements with useful methods
 pub mod elementext;
 use crate::elementext::ElementExt;
@@ -49,6 +52,16 @@ pub struct Config {
     pub expand_properties: bool,
     /// Skip parsing and emitting `enumeratedValues` and `writeConstraint` in `Field`
     pub ignore_enums: bool,
+    /// Read `-1` in enumerated value `value` as all ones of the field width instead of failing
+    pub minus_one_as_all_ones: bool,
+    /// Record byte ranges of `peripheral` elements in [`ParseOutput::spans`]
+    pub peripheral_spans: bool,
+    /// Collect `vendorExtensions` values in [`ParseOutput::annotations`]
+    pub annotations: bool,
+    /// Skip invalid peripherals, clusters, registers and fields, see [`Config::skip_invalid`]
+    pub skip_invalid: bool,
+    /// Record how numbers are written, see [`Config::number_styles`]
+    pub number_styles: bool,
 }
 
 impl Config {
@@ -78,6 +91,48 @@ impl Config {
         self.ignore_enums = val;
         self
     }
+
+    /// Read `-1` in enumerated value `value` as all ones of the field width instead of failing.
+    ///
+    /// The spec forbids negative values, but some vendor files use `-1` this way.
+    /// Each occurrence is reported in [`ParseOutput::warnings`].
+    pub fn minus_one_as_all_ones(mut self, val: bool) -> Self {
+        self.minus_one_as_all_ones = val;
+        self
+    }
+
+    /// Record byte ranges of `peripheral` elements in [`ParseOutput::spans`]
+    pub fn peripheral_spans(mut self, val: bool) -> Self {
+        self.peripheral_spans = val;
+        self
+    }
+
+    /// Collect `vendorExtensions` values in [`ParseOutput::annotations`]
+    pub fn annotations(mut self, val: bool) -> Self {
+        self.annotations = val;
+        self
+    }
+
+    /// Skip peripherals, clusters, registers and fields which fail to parse instead of
+    /// failing the whole device, and report their errors in [`ParseOutput::skipped`].
+    ///
+    /// The innermost of these elements containing the error is skipped, so a bad field
+    /// only removes the field. Errors outside of them still fail the device.
+    ///
+    /// Only the `parse*` functions of this crate skip elements, [`Parse::parse`] of an
+    /// element still fails on the first error.
+    pub fn skip_invalid(mut self, val: bool) -> Self {
+        self.skip_invalid = val;
+        self
+    }
+
+    /// Record how base addresses, offsets, register properties and enumerated values
+    /// are written in `number_styles` of the elements, so that the encoder writes them
+    /// back in the same form
+    pub fn number_styles(mut self, val: bool) -> Self {
+        self.number_styles = val;
+        self
+    }
 }
 
 /// Parse trait allows SVD objects to be parsed from XML elements.
@@ -110,62 +165,172 @@ where
     }
 }
 
-use crate::svd::Device;
+/// Styles of numeric children `tags` of `e`, recorded only with [`Config::number_styles`]
+pub(crate) fn number_styles(e: &Node, tags: &[&'static str], config: &Config) -> NumberStyles {
+    let mut styles = NumberStyles::default();
+    if config.number_styles {
+        for &tag in tags {
+            let style = e
+                .get_child(tag)
+                .and_then(|c| NumberStyle::detect(&c.get_text().ok()?));
+            if let Some(style) = style {
+                styles.insert(tag, style);
+            }
+        }
+    }
+    styles
+}
+
+use crate::svd::{Device, NumberStyle, NumberStyles};
+
+/// Byte ranges of `<peripheral>` elements in the source SVD keyed by peripheral name
+/// and number of the peripheral among the ones with this name.
+///
+/// Range starts at `<` of the opening tag and ends after `>` of the closing tag.
+/// Repeated names are numbered `1`, `2`, ... in order of appearance, the first one is `0`.
+pub type PeripheralSpans = BTreeMap<(String, usize), Range<usize>>;
+
+/// Something suspicious found in the SVD which doesn't prevent parsing
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseWarning {
+    /// Description of the problem
+    pub message: String,
+    /// Line in the source, starting from 1
+    pub line: u32,
+    /// Column in the source, starting from 1
+    pub column: u32,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}:{}", self.message, self.line, self.column)
+    }
+}
+
+/// Parsed device with everything else collected during parsing
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ParseOutput {
+    /// Parsed device
+    pub device: Device,
+    /// Warnings, only collected by [`parse_full`] and [`parse_bytes_full`]
+    pub warnings: Vec<ParseWarning>,
+    /// Peripheral spans, if [`Config::peripheral_spans`] is set
+    pub spans: Option<PeripheralSpans>,
+    /// Annotations, if [`Config::annotations`] is set
+    pub annotations: Option<AnnotationTable>,
+    /// How the input was decoded, if parsed with [`parse_bytes_full`]
+    pub decoding: Option<String>,
+    /// Elements skipped with [`Config::skip_invalid`], in order of skipping
+    pub skipped: Vec<Skipped>,
+}
+
+/// Element left out by [`Config::skip_invalid`]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Skipped {
+    /// Error which made the element fail
+    pub error: SVDErrorAt,
+    /// Error with names of the element and its parents
+    pub message: String,
+    /// Line of the skipped element, starting from 1
+    pub line: u32,
+    /// Column of the skipped element, starting from 1
+    pub column: u32,
+}
+
+impl std::fmt::Display for Skipped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}:{}", self.message, self.line, self.column)
+    }
+}
+
+/// Elements left out by [`Config::skip_invalid`] with their errors
+type Skips = RefCell<Vec<(NodeId, SVDErrorAt)>>;
+
+/// Parse child elements `nodes` with `parse`.
+///
+/// With `skips` elements which fail are left out and recorded there instead of failing
+/// the parent.
+fn parse_children<'a, 'input: 'a, T>(
+    nodes: impl Iterator<Item = Node<'a, 'input>>,
+    skips: Option<&Skips>,
+    parse: impl Fn(&Node) -> Result<T, SVDErrorAt>,
+) -> Result<Vec<T>, SVDErrorAt> {
+    let skips = match skips {
+        Some(skips) => skips,
+        None => return nodes.map(|t| parse(&t)).collect(),
+    };
+    let mut children = Vec::new();
+    for node in nodes {
+        match parse(&node) {
+            Ok(child) => children.push(child),
+            Err(e) => skips.borrow_mut().push((node.id(), e)),
+        }
+    }
+    Ok(children)
+}
+
 /// Parses the contents of an SVD (XML) string
 pub fn parse(xml: &str) -> anyhow::Result<Device> {
     parse_with_config(xml, &Config::default())
 }
 /// Parses the contents of an SVD (XML) string
 pub fn parse_with_config(xml: &str, config: &Config) -> anyhow::Result<Device> {
-    fn get_name<'a>(node: &'a Node) -> Option<&'a str> {
-        node.children()
-            .find(|t| t.has_tag_name("name"))
-            .and_then(|t| t.text())
-    }
+    parse_output(xml, config, false).map(|output| output.device)
+}
+/// Parses the contents of an SVD (XML) string and returns device with warnings and other
+/// information requested in `config`
+pub fn parse_full(xml: &str, config: &Config) -> anyhow::Result<ParseOutput> {
+    parse_output(xml, config, true)
+}
 
+/// Parse `xml`, looking for warnings if `with_warnings` is set
+fn parse_output(xml: &str, config: &Config, with_warnings: bool) -> anyhow::Result<ParseOutput> {
+    let bom_len = xml.len() - trim_utf8_bom(xml).len();
     let xml = trim_utf8_bom(xml);
-    let tree = Document::parse(xml)?;
+    let tree = Document::parse(xml).map_err(|e| SVDError::InvalidXml(xml_error(xml, &e)))?;
     let root = tree.root();
     let xmldevice = root
         .get_child("device")
         .ok_or_else(|| SVDError::MissingTag("device".to_string()).at(root.id()))?;
 
+    let skips = config.skip_invalid.then(Skips::default);
     #[allow(unused_mut)]
-    let mut device = match Device::parse(&xmldevice, config) {
-        Ok(o) => Ok(o),
+    let mut device = match device::parse_device(&xmldevice, config, skips.as_ref()) {
+        Ok(device) => device,
         Err(e) => {
-            let id = e.id;
-            let node = tree.get_node(id).unwrap();
-            let pos = tree.text_pos_at(node.range().start);
-            let tagname = node.tag_name().name();
-            let mut res = Err(e.into());
-            if tagname.is_empty() {
-                res = res.with_context(|| format!("at {}", pos))
-            } else if let Some(name) = get_name(&node) {
-                res = res.with_context(|| format!("Parsing {} `{}` at {}", tagname, name, pos))
-            } else {
-                res = res.with_context(|| format!("Parsing unknown {} at {}", tagname, pos))
-            }
-            for parent in node.ancestors().skip(1) {
-                if parent.id() == NodeId::new(0) {
-                    break;
-                }
-                let tagname = parent.tag_name().name();
-                match tagname {
-                    "device" | "peripheral" | "register" | "field" | "enumeratedValue"
-                    | "interrupt" => {
-                        if let Some(name) = get_name(&parent) {
-                            res = res.with_context(|| format!("In {} `{}`", tagname, name));
-                        } else {
-                            res = res.with_context(|| format!("In unknown {}", tagname));
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            res
+            let node = tree.get_node(e.id).unwrap();
+            return Err(error_with_context(&tree, node, e));
         }
-    }?;
+    };
+    let skipped = skips
+        .unwrap_or_default()
+        .into_inner()
+        .into_iter()
+        .map(|(id, error)| {
+            let skipped = tree.get_node(id).unwrap();
+            let node = tree.get_node(error.id).unwrap();
+            let pos = tree.text_pos_at(skipped.range().start);
+            Skipped {
+                message: format!("{:#}", error_with_context(&tree, node, error.clone())),
+                error,
+                line: pos.row,
+                column: pos.col,
+            }
+        })
+        .collect();
+
+    let warnings = if with_warnings {
+        warnings(&tree, config)
+    } else {
+        Vec::new()
+    };
+    let spans = config
+        .peripheral_spans
+        .then(|| peripheral_spans(&xmldevice, bom_len));
+    let annotations = config.annotations.then(|| annotations::collect(&xmldevice));
 
     #[cfg(feature = "expand")]
     if config.expand_properties {
@@ -176,7 +341,125 @@ pub fn parse_with_config(xml: &str, config: &Config) -> anyhow::Result<Device> {
     if config.expand {
         device = expand::expand(&device)?;
     }
-    Ok(device)
+    Ok(ParseOutput {
+        device,
+        warnings,
+        spans,
+        annotations,
+        decoding: None,
+        skipped,
+    })
+}
+
+/// Add position of failed `node` and names of its parents to `e`
+fn error_with_context(tree: &Document, node: Node, e: SVDErrorAt) -> anyhow::Error {
+    fn get_name<'a>(node: &'a Node) -> Option<&'a str> {
+        node.children()
+            .find(|t| t.has_tag_name("name"))
+            .and_then(|t| t.text())
+    }
+
+    let pos = tree.text_pos_at(node.range().start);
+    let tagname = node.tag_name().name();
+    let mut error = anyhow::Error::from(e);
+    if tagname.is_empty() {
+        error = error.context(format!("at {}", pos))
+    } else if let Some(name) = get_name(&node) {
+        error = error.context(format!("Parsing {} `{}` at {}", tagname, name, pos))
+    } else {
+        error = error.context(format!("Parsing unknown {} at {}", tagname, pos))
+    }
+    for parent in node.ancestors().skip(1) {
+        if parent.id() == NodeId::new(0) {
+            break;
+        }
+        let tagname = parent.tag_name().name();
+        match tagname {
+            "device" | "cpu" | "peripheral" | "cluster" | "register" | "field"
+            | "enumeratedValues" | "enumeratedValue" | "interrupt" => {
+                if let Some(name) = get_name(&parent) {
+                    error = error.context(format!("In {} `{}`", tagname, name));
+                } else {
+                    error = error.context(format!("In unknown {}", tagname));
+                }
+            }
+            _ => {}
+        }
+    }
+    error
+}
+
+/// Parses SVD (XML) from bytes in given encoding
+pub fn parse_bytes(bytes: &[u8], encoding: EncodingHint) -> anyhow::Result<Device> {
+    let (xml, _) = encoding::decode(bytes, encoding)?;
+    parse(&xml)
+}
+/// Parses SVD (XML) from bytes in given encoding and returns device with warnings,
+/// used decoding and other information requested in `config`.
+///
+/// Spans are offsets in decoded text, which only match offsets in `bytes` for UTF-8 input.
+pub fn parse_bytes_full(
+    bytes: &[u8],
+    encoding: EncodingHint,
+    config: &Config,
+) -> anyhow::Result<ParseOutput> {
+    let (xml, decoding) = encoding::decode(bytes, encoding)?;
+    let mut output = parse_full(&xml, config)?;
+    output.decoding = Some(decoding);
+    Ok(output)
+}
+
+fn warnings(tree: &Document, config: &Config) -> Vec<ParseWarning> {
+    let mut warnings = Vec::new();
+    let mut warn = |node: Node, message: String| {
+        let pos = tree.text_pos_at(node.range().start);
+        warnings.push(ParseWarning {
+            message,
+            line: pos.row,
+            column: pos.col,
+        });
+    };
+    // one walk over the document for all checks
+    for node in tree.descendants().filter(Node::is_element) {
+        if config.minus_one_as_all_ones
+            && node.has_tag_name("value")
+            && node
+                .parent()
+                .map_or(false, |p| p.has_tag_name("enumeratedValue"))
+            && node.text().map(str::trim) == Some("-1")
+        {
+            warn(node, "Negative value `-1` read as all ones".into());
+        }
+        if let Some(message) = bounds::warning(node, config) {
+            warn(node, message);
+        }
+        if let Some(message) = bitrange::warning(node, config) {
+            warn(node, message);
+        }
+        if let Some(message) = schema::warning(node) {
+            warn(node, message);
+        }
+    }
+    warnings
+}
+
+fn peripheral_spans(xmldevice: &Node, offset: usize) -> PeripheralSpans {
+    let mut spans = BTreeMap::new();
+    let mut counts = HashMap::<String, usize>::new();
+    let peripherals = xmldevice
+        .get_child("peripherals")
+        .into_iter()
+        .flat_map(|ps| ps.children())
+        .filter(|p| p.has_tag_name("peripheral"));
+    for p in peripherals {
+        let name = p.get_child_text("name").unwrap_or_default();
+        let count = counts.entry(name.clone()).or_default();
+        let key = (name, *count);
+        *count += 1;
+        let range = p.range();
+        spans.insert(key, range.start + offset..range.end + offset);
+    }
+    spans
 }
 
 /// Return the &str trimmed UTF-8 BOM if the input &str contains the BOM.
@@ -189,11 +472,21 @@ fn trim_utf8_bom(s: &str) -> &str {
 }
 
 mod array;
-use array::parse_array;
+use array::{parse_array, parse_array_with};
+
+pub mod annotations;
+pub use annotations::AnnotationTable;
+
+pub mod encoding;
+pub use encoding::EncodingHint;
+
+pub mod family;
+pub use family::{FamilyLoadOptions, FamilySet};
 
 mod access;
 mod addressblock;
 mod bitrange;
+mod bounds;
 mod cluster;
 mod cpu;
 mod datatype;
@@ -213,6 +506,8 @@ mod registercluster;
 mod registerproperties;
 #[cfg(feature = "unstable-riscv")]
 mod riscv;
+mod sauregion;
+mod schema;
 mod usage;
 mod writeconstraint;
 
@@ -220,18 +515,29 @@ mod writeconstraint;
 pub mod expand;
 
 #[cfg(feature = "expand")]
-pub use expand::{expand, expand_properties};
+pub use expand::{compress_properties, expand, expand_properties};
+
+#[cfg(feature = "pack")]
+pub mod pack;
 /// SVD parse Errors.
 #[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
 pub enum SVDError {
     #[error("{0}")]
-    Svd(#[from] svd::SvdError),
+    Svd(Box<svd::SvdError>),
     #[error("Expected a <{0}> tag, found none")]
     MissingTag(String),
     #[error("Expected content in <{0}> tag, found none")]
     EmptyTag(String),
     #[error("Failed to parse `{0}`")]
     ParseInt(#[from] std::num::ParseIntError),
+    #[error("Invalid number `{0}`: {1}")]
+    InvalidNumber(String, std::num::ParseIntError),
+    #[error("Negative number `{0}` is not allowed")]
+    NegativeNumber(String),
+    #[error("Number `{0}` does not fit in {1} bits")]
+    NumberOverflow(String, u32),
+    #[error("`{0}` value {1} is out of bounds, expected {2}")]
+    OutOfBounds(String, u64, String),
     #[error("Unknown endianness `{0}`")]
     UnknownEndian(String),
     #[error("unknown access variant '{0}' found")]
@@ -244,10 +550,10 @@ pub enum SVDError {
     MoreThanOneWriteConstraint,
     #[error("Unknown usage variant")]
     UnknownUsageVariant,
-    #[error("Unknown usage variant for addressBlock")]
-    UnknownAddressBlockUsageVariant,
-    #[error("Expected a <{0}>, found ...")]
-    NotExpectedTag(String),
+    #[error("Unknown addressBlock usage `{0}`, expected `registers`, `buffer` or `reserved`")]
+    UnknownAddressBlockUsageVariant(String),
+    #[error("Expected a <{0}> tag, found <{1}>")]
+    NotExpectedTag(String, String),
     #[error("Invalid RegisterCluster (expected register or cluster), found {0}")]
     InvalidRegisterCluster(String),
     #[error("Invalid datatype variant, found {0}")]
@@ -258,6 +564,8 @@ pub enum SVDError {
     InvalidReadAction(String),
     #[error("Invalid protection variant, found {0}")]
     InvalidProtection(String),
+    #[error("Invalid SAU region access `{0}`, expected `n` or `c`")]
+    InvalidSauAccess(String),
     #[error("The content of the element could not be parsed to a boolean value {0}: {1}")]
     InvalidBooleanValue(String, core::str::ParseBoolError),
     #[error("dimIndex tag must contain {0} indexes, found {1}")]
@@ -266,6 +574,16 @@ pub enum SVDError {
     DimIndexParse,
     #[error("Name `{0}` in tag `{1}` is missing a %s placeholder")]
     MissingPlaceholder(String, String),
+    #[error("Invalid XML: {0}")]
+    InvalidXml(String),
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("Failed to parse `{0}`: {1}")]
+    InvalidFile(String, String),
+    #[error("Invalid text encoding: {0}")]
+    Encoding(String),
+    #[error("Invalid CMSIS pack: {0}")]
+    InvalidPack(String),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -282,14 +600,47 @@ impl std::fmt::Display for SVDErrorAt {
 
 impl std::error::Error for SVDErrorAt {}
 
+impl From<svd::SvdError> for SVDError {
+    fn from(e: svd::SvdError) -> Self {
+        // boxed as it is much larger than other variants
+        Self::Svd(Box::new(e))
+    }
+}
+
 impl SVDError {
     pub fn at(self, id: NodeId) -> SVDErrorAt {
         SVDErrorAt { error: self, id }
     }
+
+    /// [`SVDError::NotExpectedTag`] at `node` where `<tag>` was expected
+    pub(crate) fn not_expected(tag: &str, node: &Node) -> SVDErrorAt {
+        Self::NotExpectedTag(tag.to_string(), node.tag_name().name().to_string()).at(node.id())
+    }
+}
+
+impl SVDErrorAt {
+    /// Error without its position
+    pub fn error(&self) -> &SVDError {
+        &self.error
+    }
+}
+
+/// Describe XML syntax error `e` in `xml`.
+///
+/// Errors found at the end of the input have no position, so it is added.
+fn xml_error(xml: &str, e: &roxmltree::Error) -> String {
+    match e {
+        roxmltree::Error::UnclosedRootNode | roxmltree::Error::UnexpectedEndOfStream => {
+            let line = xml.split('\n').count();
+            let column = xml.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+            format!("{} at end of input {}:{}", e, line, column)
+        }
+        _ => e.to_string(),
+    }
 }
 
 pub(crate) fn check_has_placeholder(name: &str, tag: &str) -> Result<(), SVDError> {
-    if name.contains("%s") {
+    if svd::template::has_placeholder(name) {
         Ok(())
     } else {
         Err(SVDError::MissingPlaceholder(
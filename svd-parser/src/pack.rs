@@ -0,0 +1,173 @@
+//! Reading SVD files out of CMSIS pack (`.pack`) archives.
+//!
+//! A pack is a zip archive with a `.pdsc` index describing the devices it supports.
+//! The SVD of each device is referenced by the `svd` attribute of a `<debug>` element
+//! which can be placed on the `<device>` itself or on any of its `<subFamily>`/`<family>`
+//! ancestors.
+
+use super::encoding::decode;
+use super::{parse_with_config, Config, EncodingHint, SVDError};
+use crate::svd::Device;
+use roxmltree::{Document, Node};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// Device listed in the `.pdsc` index of a pack
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PackDeviceEntry {
+    /// Name of the device (`Dname` or `Dvariant`)
+    pub device_name: String,
+    /// Path of the SVD file in the archive
+    pub svd_path_in_archive: String,
+    /// Processor core (`Dcore`)
+    pub processor: Option<String>,
+}
+
+/// Opened CMSIS pack
+pub struct Pack<R = File> {
+    archive: RefCell<ZipArchive<R>>,
+    devices: Vec<PackDeviceEntry>,
+}
+
+/// Open the pack at `path` and read its device index
+pub fn open(path: &Path) -> Result<Pack, SVDError> {
+    let file = File::open(path).map_err(|e| SVDError::Io(e.to_string()))?;
+    Pack::from_reader(file)
+}
+
+impl<R: Read + Seek> Pack<R> {
+    /// Read pack from a seekable stream
+    pub fn from_reader(reader: R) -> Result<Self, SVDError> {
+        let mut archive = ZipArchive::new(reader).map_err(zip_error)?;
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        let pdsc = names
+            .iter()
+            .filter(|n| n.to_lowercase().ends_with(".pdsc"))
+            // prefer index in the root of the archive
+            .min_by_key(|n| n.matches('/').count())
+            .ok_or_else(|| SVDError::InvalidPack("no .pdsc file found".into()))?;
+        let pdsc = read_to_string(&mut archive, pdsc)?;
+        let doc = Document::parse(pdsc.trim_start_matches('\u{feff}'))
+            .map_err(|e| SVDError::InvalidPack(e.to_string()))?;
+
+        let mut devices = Vec::new();
+        for device in doc.descendants().filter(|n| n.has_tag_name("device")) {
+            let name = device.attribute("Dname").unwrap_or_default();
+            let variants: Vec<_> = device
+                .children()
+                .filter(|n| n.has_tag_name("variant"))
+                .collect();
+            let nodes = if variants.is_empty() {
+                vec![(device, name)]
+            } else {
+                variants
+                    .into_iter()
+                    .map(|v| (v, v.attribute("Dvariant").unwrap_or(name)))
+                    .collect()
+            };
+            for (node, name) in nodes {
+                if let Some(svd) = inherited(node, "debug", "svd") {
+                    let svd = find_entry(&names, svd).ok_or_else(|| {
+                        SVDError::InvalidPack(format!("`{}` not found in archive", svd))
+                    })?;
+                    devices.push(PackDeviceEntry {
+                        device_name: name.into(),
+                        svd_path_in_archive: svd.into(),
+                        processor: inherited(node, "processor", "Dcore").map(String::from),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            archive: RefCell::new(archive),
+            devices,
+        })
+    }
+
+    /// Devices that have an SVD file in the pack
+    pub fn devices(&self) -> Vec<PackDeviceEntry> {
+        self.devices.clone()
+    }
+
+    /// Extract and parse the SVD of device `name`
+    pub fn parse_device(&self, name: &str) -> Result<Device, SVDError> {
+        self.parse_device_with_config(name, &Config::default())
+    }
+
+    /// Extract and parse the SVD of device `name` with given config.
+    ///
+    /// Parse errors are reported as [`SVDError::InvalidFile`] with the path in the archive
+    pub fn parse_device_with_config(
+        &self,
+        name: &str,
+        config: &Config,
+    ) -> Result<Device, SVDError> {
+        let entry = self
+            .devices
+            .iter()
+            .find(|d| d.device_name == name)
+            .ok_or_else(|| SVDError::InvalidPack(format!("device `{}` not found", name)))?;
+        let xml = read_to_string(&mut self.archive.borrow_mut(), &entry.svd_path_in_archive)?;
+        parse_with_config(&xml, config).map_err(|e| {
+            SVDError::InvalidFile(entry.svd_path_in_archive.clone(), format!("{:#}", e))
+        })
+    }
+}
+
+/// Get attribute of child element `tag` of `node` or of the closest ancestor having one
+fn inherited<'a>(node: Node<'a, '_>, tag: &str, attr: &str) -> Option<&'a str> {
+    node.ancestors()
+        .take_while(|n| !n.has_tag_name("devices"))
+        .find_map(|n| {
+            n.children()
+                .filter(|c| c.has_tag_name(tag))
+                .find_map(|c| c.attribute(attr))
+        })
+}
+
+/// Find archive entry for a path from the index.
+/// Paths in `.pdsc` files may use backslashes and don't always match the case of the file.
+fn find_entry<'a>(names: &'a [String], path: &str) -> Option<&'a str> {
+    let normalize = |p: &str| {
+        let p = p.replace('\\', "/").to_lowercase();
+        p.trim_start_matches("./").to_string()
+    };
+    let path = normalize(path);
+    names
+        .iter()
+        .map(String::as_str)
+        .find(|n| normalize(n) == path)
+        // index may be stored in a subdirectory, paths are relative to it
+        .or_else(|| {
+            names
+                .iter()
+                .map(String::as_str)
+                .find(|n| normalize(n).ends_with(&format!("/{}", path)))
+        })
+}
+
+fn read_to_string<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<String, SVDError> {
+    let mut bytes = Vec::new();
+    archive
+        .by_name(name)
+        .map_err(zip_error)?
+        .read_to_end(&mut bytes)
+        .map_err(|e| SVDError::Io(e.to_string()))?;
+    match decode(&bytes, EncodingHint::Auto) {
+        Ok((text, _)) => Ok(text.into_owned()),
+        Err(SVDError::Encoding(e)) => Err(SVDError::Encoding(format!("`{}`: {}", name, e))),
+        Err(e) => Err(e),
+    }
+}
+
+fn zip_error(e: zip::result::ZipError) -> SVDError {
+    SVDError::Io(e.to_string())
+}
@@ -1,6 +1,4 @@
 use super::*;
-use crate::svd::{Cluster, Register};
-
 use crate::svd::RegisterCluster;
 impl Parse for RegisterCluster {
     type Object = Self;
@@ -8,12 +6,21 @@ impl Parse for RegisterCluster {
     type Config = Config;
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
-        match tree.tag_name().name() {
-            "register" => Register::parse(tree, config).map(Into::into),
-            "cluster" => Cluster::parse(tree, config).map(Into::into),
-            _ => Err(
-                SVDError::InvalidRegisterCluster(tree.tag_name().name().to_string()).at(tree.id()),
-            ),
+        parse_register_cluster(tree, config, None)
+    }
+}
+
+/// Parse a register or cluster, leaving out invalid children in `skips` if given
+pub(crate) fn parse_register_cluster(
+    tree: &Node,
+    config: &Config,
+    skips: Option<&Skips>,
+) -> Result<RegisterCluster, SVDErrorAt> {
+    match tree.tag_name().name() {
+        "register" => register::parse_register(tree, config, skips).map(Into::into),
+        "cluster" => cluster::parse_cluster(tree, config, skips).map(Into::into),
+        _ => {
+            Err(SVDError::InvalidRegisterCluster(tree.tag_name().name().to_string()).at(tree.id()))
         }
     }
 }
@@ -7,8 +7,12 @@ impl Parse for RegisterProperties {
     type Config = Config;
 
     fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
+        let size = optional::<u32>("size", tree, &())?;
+        if let Some(size) = size {
+            bounds::check_child(tree, "size", size.into(), config)?;
+        }
         RegisterProperties::new()
-            .size(optional::<u32>("size", tree, &())?)
+            .size(size)
             .access(optional::<Access>("access", tree, config)?)
             .protection(optional::<Protection>("protection", tree, config)?)
             .reset_value(optional::<u64>("resetValue", tree, &())?)
@@ -8,7 +8,7 @@ impl Parse for Riscv {
 
     fn parse(tree: &Node, config: &Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("riscv") {
-            return Err(SVDError::NotExpectedTag("riscv".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("riscv", tree));
         }
 
         let mut builder = Riscv::builder();
@@ -62,7 +62,7 @@ impl Parse for Exception {
 
     fn parse(tree: &Node, config: &Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("exception") {
-            return Err(SVDError::NotExpectedTag("exception".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("exception", tree));
         }
 
         Exception::builder()
@@ -81,7 +81,7 @@ impl Parse for Priority {
 
     fn parse(tree: &Node, config: &Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("priority") {
-            return Err(SVDError::NotExpectedTag("priority".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("priority", tree));
         }
 
         Priority::builder()
@@ -100,7 +100,7 @@ impl Parse for Hart {
 
     fn parse(tree: &Node, config: &Config) -> Result<Self, Self::Error> {
         if !tree.has_tag_name("hart") {
-            return Err(SVDError::NotExpectedTag("hart".to_string()).at(tree.id()));
+            return Err(SVDError::not_expected("hart", tree));
         }
 
         Hart::builder()
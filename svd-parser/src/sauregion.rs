@@ -0,0 +1,74 @@
+use super::*;
+use crate::svd::{Protection, SauAccess, SauRegion, SauRegionsConfig};
+use crate::types::parse_bool;
+
+impl Parse for SauRegionsConfig {
+    type Object = Self;
+    type Error = SVDErrorAt;
+    type Config = Config;
+
+    fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
+        if !tree.has_tag_name("sauRegionsConfig") {
+            return Err(SVDError::not_expected("sauRegionsConfig", tree));
+        }
+        let enabled = tree
+            .attribute("enabled")
+            .map(parse_bool)
+            .transpose()
+            .map_err(|e| e.at(tree.id()))?;
+        let protection_when_disabled = tree
+            .attribute("protectionWhenDisabled")
+            .map(|s| {
+                Protection::parse_str(s)
+                    .ok_or_else(|| SVDError::InvalidProtection(s.into()).at(tree.id()))
+            })
+            .transpose()?;
+        let regions: Result<Vec<_>, _> = tree
+            .children()
+            .filter(|t| t.is_element())
+            .map(|t| SauRegion::parse(&t, config))
+            .collect();
+        let sau = SauRegionsConfig::new()
+            .enabled(enabled)
+            .protection_when_disabled(protection_when_disabled)
+            .regions(regions?);
+        sau.validate(config.validate_level)
+            .map_err(|e| SVDError::from(e).at(tree.id()))?;
+        Ok(sau)
+    }
+}
+
+impl Parse for SauRegion {
+    type Object = Self;
+    type Error = SVDErrorAt;
+    type Config = Config;
+
+    fn parse(tree: &Node, config: &Self::Config) -> Result<Self, Self::Error> {
+        if !tree.has_tag_name("region") {
+            return Err(SVDError::not_expected("region", tree));
+        }
+        let mut builder = SauRegion::builder()
+            .name(tree.attribute("name").map(|s| s.to_owned()))
+            .base(tree.get_child_u32("base")?)
+            .limit(tree.get_child_u32("limit")?)
+            .access(SauAccess::parse(&tree.get_child_elem("access")?, config)?);
+        if let Some(enabled) = tree.attribute("enabled") {
+            builder = builder.enabled(parse_bool(enabled).map_err(|e| e.at(tree.id()))?);
+        }
+        builder
+            .build(config.validate_level)
+            .map_err(|e| SVDError::from(e).at(tree.id()))
+    }
+}
+
+impl Parse for SauAccess {
+    type Object = Self;
+    type Error = SVDErrorAt;
+    type Config = Config;
+
+    fn parse(tree: &Node, _config: &Self::Config) -> Result<Self, Self::Error> {
+        let text = tree.get_text()?;
+
+        Self::parse_str(&text).ok_or_else(|| SVDError::InvalidSauAccess(text.into()).at(tree.id()))
+    }
+}
@@ -0,0 +1,208 @@
+//! Allowed children of elements.
+//!
+//! The parser ignores elements it doesn't know, so misspelled or misplaced tags lose
+//! their data silently. They are reported as [`ParseWarning`](crate::ParseWarning)s
+//! of [`parse_full`](crate::parse_full).
+
+use super::Node;
+
+const DIM: &[&str] = &[
+    "dim",
+    "dimIncrement",
+    "dimIndex",
+    "dimName",
+    "dimArrayIndex",
+];
+const PROPERTIES: &[&str] = &["size", "access", "protection", "resetValue", "resetMask"];
+
+/// Element tag and tags of its children. Children of other elements aren't checked
+const CHILDREN: &[(&str, &[&[&str]])] = &[
+    (
+        "device",
+        &[
+            PROPERTIES,
+            &[
+                "vendor",
+                "vendorID",
+                "name",
+                "series",
+                "version",
+                "description",
+                "licenseText",
+                "cpu",
+                "riscv",
+                "headerSystemFilename",
+                "headerDefinitionsPrefix",
+                "addressUnitBits",
+                "width",
+                "peripherals",
+                "vendorExtensions",
+            ],
+        ],
+    ),
+    (
+        "cpu",
+        &[&[
+            "name",
+            "revision",
+            "endian",
+            "mpuPresent",
+            "fpuPresent",
+            "fpuDP",
+            "dspPresent",
+            "icachePresent",
+            "dcachePresent",
+            "itcmPresent",
+            "dtcmPresent",
+            "vtorPresent",
+            "nvicPrioBits",
+            "vendorSystickConfig",
+            "deviceNumInterrupts",
+            "sauNumRegions",
+            "sauRegionsConfig",
+        ]],
+    ),
+    ("sauRegionsConfig", &[&["region"]]),
+    ("region", &[&["base", "limit", "access"]]),
+    ("peripherals", &[&["peripheral"]]),
+    (
+        "peripheral",
+        &[
+            DIM,
+            PROPERTIES,
+            &[
+                "name",
+                "version",
+                "description",
+                "alternatePeripheral",
+                "groupName",
+                "prependToName",
+                "appendToName",
+                "headerStructName",
+                "disableCondition",
+                "baseAddress",
+                "addressBlock",
+                "interrupt",
+                "registers",
+            ],
+        ],
+    ),
+    (
+        "addressBlock",
+        &[&["offset", "size", "usage", "protection"]],
+    ),
+    ("interrupt", &[&["name", "description", "value"]]),
+    ("registers", &[&["register", "cluster"]]),
+    (
+        "cluster",
+        &[
+            DIM,
+            PROPERTIES,
+            &[
+                "name",
+                "description",
+                "alternateCluster",
+                "headerStructName",
+                "addressOffset",
+                "register",
+                "cluster",
+            ],
+        ],
+    ),
+    (
+        "register",
+        &[
+            DIM,
+            PROPERTIES,
+            &[
+                "name",
+                "displayName",
+                "description",
+                "alternateGroup",
+                "alternateRegister",
+                "addressOffset",
+                "dataType",
+                "modifiedWriteValues",
+                "writeConstraint",
+                "readAction",
+                "fields",
+            ],
+        ],
+    ),
+    ("fields", &[&["field"]]),
+    (
+        "field",
+        &[
+            DIM,
+            &[
+                "name",
+                "description",
+                "bitOffset",
+                "bitWidth",
+                "lsb",
+                "msb",
+                "bitRange",
+                "access",
+                "modifiedWriteValues",
+                "writeConstraint",
+                "readAction",
+                "enumeratedValues",
+            ],
+        ],
+    ),
+    ("dimArrayIndex", &[&["headerEnumName", "enumeratedValue"]]),
+    (
+        "enumeratedValues",
+        &[&["name", "headerEnumName", "usage", "enumeratedValue"]],
+    ),
+    (
+        "enumeratedValue",
+        &[&["name", "description", "value", "isDefault"]],
+    ),
+    (
+        "writeConstraint",
+        &[&["writeAsRead", "useEnumeratedValues", "range"]],
+    ),
+    ("range", &[&["minimum", "maximum"]]),
+];
+
+/// Elements whose names make up the path in warnings
+const NAMED: &[&str] = &["peripheral", "cluster", "register", "field"];
+
+/// Check whether element `node` is unknown in its parent for parse warnings
+pub(crate) fn warning(node: Node) -> Option<String> {
+    let parent = node.parent_element()?;
+    let parent_tag = parent.tag_name().name();
+    let (_, allowed) = CHILDREN.iter().find(|(tag, _)| *tag == parent_tag)?;
+    let tag = node.tag_name().name();
+    if allowed.iter().any(|tags| tags.contains(&tag)) {
+        return None;
+    }
+    let path = path(parent);
+    Some(if path.is_empty() {
+        format!("Unknown element <{}> in <{}>", tag, parent_tag)
+    } else if NAMED.contains(&parent_tag) {
+        format!("Unknown element <{}> in <{}> `{}`", tag, parent_tag, path)
+    } else {
+        format!(
+            "Unknown element <{}> in <{}> of `{}`",
+            tag, parent_tag, path
+        )
+    })
+}
+
+/// Dot separated names of `node` and its ancestors like `PERIPHERAL.REGISTER`
+fn path(node: Node) -> String {
+    let mut names: Vec<&str> = node
+        .ancestors()
+        .filter(|n| NAMED.contains(&n.tag_name().name()))
+        .map(|n| {
+            n.children()
+                .find(|c| c.has_tag_name("name"))
+                .and_then(|c| c.text())
+                .map_or("?", str::trim)
+        })
+        .collect();
+    names.reverse();
+    names.join(".")
+}
@@ -1,10 +1,50 @@
 //! Shared primitive types for use in SVD objects.
-#![allow(clippy::manual_strip)]
 
 use roxmltree::Node;
 
 use super::{ElementExt, Parse, SVDError, SVDErrorAt};
 
+/// Parse an unsigned integer written in any of the forms allowed for `scaledNonNegativeInteger`:
+/// decimal, `0x` hexadecimal or `#`/`0b` binary, with optional leading `+`
+/// and `k`/`M`/`G`/`T` scale suffix.
+///
+/// Don't care `x` characters in binary form are replaced with 0.
+pub(crate) fn parse_scaled_u64(text: &str) -> Result<u64, SVDError> {
+    if text.starts_with('-') {
+        return Err(SVDError::NegativeNumber(text.into()));
+    }
+    let digits = text.strip_prefix('+').unwrap_or(text);
+    let (digits, shift) = match digits.as_bytes().last() {
+        Some(b'k' | b'K') => (&digits[..digits.len() - 1], 10),
+        Some(b'm' | b'M') => (&digits[..digits.len() - 1], 20),
+        Some(b'g' | b'G') => (&digits[..digits.len() - 1], 30),
+        Some(b't' | b'T') => (&digits[..digits.len() - 1], 40),
+        _ => (digits, 0),
+    };
+
+    let value = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(bin) = digits
+        .strip_prefix('#')
+        .or_else(|| digits.strip_prefix("0b"))
+    {
+        // Handle strings in the binary form of:
+        // #01101x1 or 0b01101x1
+        // along with don't care character x (replaced with 0)
+        u64::from_str_radix(&bin.to_lowercase().replace('x', "0"), 2)
+    } else {
+        digits.parse::<u64>()
+    }
+    .map_err(|e| SVDError::InvalidNumber(text.into(), e))?;
+
+    value
+        .checked_mul(1 << shift)
+        .ok_or_else(|| SVDError::NumberOverflow(text.into(), 64))
+}
+
 impl Parse for u32 {
     type Object = u32;
     type Error = SVDErrorAt;
@@ -13,25 +53,9 @@ impl Parse for u32 {
     fn parse(tree: &Node, _config: &Self::Config) -> Result<u32, Self::Error> {
         let text = tree.get_text()?;
 
-        (if text.starts_with("0x") || text.starts_with("0X") {
-            u32::from_str_radix(&text["0x".len()..], 16)
-        } else if text.starts_with('#') {
-            // Handle strings in the binary form of:
-            // #01101x1
-            // along with don't care character x (replaced with 0)
-            u32::from_str_radix(
-                &str::replace(&text.to_lowercase()["#".len()..], "x", "0"),
-                2,
-            )
-        } else if text.starts_with("0b") {
-            // Handle strings in the binary form of:
-            // 0b01101x1
-            // along with don't care character x (replaced with 0)
-            u32::from_str_radix(&str::replace(&text["0b".len()..], "x", "0"), 2)
-        } else {
-            text.parse::<u32>()
-        })
-        .map_err(|e| SVDError::from(e).at(tree.id()))
+        parse_scaled_u64(&text)
+            .and_then(|v| u32::try_from(v).map_err(|_| SVDError::NumberOverflow(text.into(), 32)))
+            .map_err(|e| e.at(tree.id()))
     }
 }
 
@@ -43,25 +67,7 @@ impl Parse for u64 {
     fn parse(tree: &Node, _config: &Self::Config) -> Result<u64, Self::Error> {
         let text = tree.get_text()?;
 
-        (if text.starts_with("0x") || text.starts_with("0X") {
-            u64::from_str_radix(&text["0x".len()..], 16)
-        } else if text.starts_with('#') {
-            // Handle strings in the binary form of:
-            // #01101x1
-            // along with don't care character x (replaced with 0)
-            u64::from_str_radix(
-                &str::replace(&text.to_lowercase()["#".len()..], "x", "0"),
-                2,
-            )
-        } else if text.starts_with("0b") {
-            // Handle strings in the binary form of:
-            // 0b01101x1
-            // along with don't care character x (replaced with 0)
-            u64::from_str_radix(&str::replace(&text["0b".len()..], "x", "0"), 2)
-        } else {
-            text.parse::<u64>()
-        })
-        .map_err(|e| SVDError::from(e).at(tree.id()))
+        parse_scaled_u64(&text).map_err(|e| e.at(tree.id()))
     }
 }
 
@@ -73,14 +79,17 @@ impl Parse for BoolParse {
     type Config = ();
 
     fn parse(tree: &Node, _config: &Self::Config) -> Result<bool, Self::Error> {
-        let text = tree.get_text()?;
-        match text {
-            "0" => Ok(false),
-            "1" => Ok(true),
-            _ => match text.parse() {
-                Ok(b) => Ok(b),
-                Err(e) => Err(SVDError::InvalidBooleanValue(text.into(), e).at(tree.id())),
-            },
-        }
+        parse_bool(&tree.get_text()?).map_err(|e| e.at(tree.id()))
+    }
+}
+
+/// Parse boolean written as `true`/`false` or `1`/`0`
+pub(crate) fn parse_bool(text: &str) -> Result<bool, SVDError> {
+    match text {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => text
+            .parse()
+            .map_err(|e| SVDError::InvalidBooleanValue(text.into(), e)),
     }
 }
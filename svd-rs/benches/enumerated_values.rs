@@ -0,0 +1,55 @@
+//! Compare indexed and linear lookup of enumerated values by value.
+//!
+//! Run with `cargo bench -p svd-rs`.
+
+use std::time::Instant;
+use svd_rs::{EnumeratedValue, EnumeratedValues, ValidateLevel};
+
+const COUNT: u64 = 200;
+const ROUNDS: usize = 10_000;
+
+fn main() {
+    let evs = EnumeratedValues::builder()
+        .values(
+            (0..COUNT)
+                .rev()
+                .map(|v| {
+                    EnumeratedValue::builder()
+                        .name(format!("V{}", v))
+                        .value(Some(v))
+                        .build(ValidateLevel::Strict)
+                        .unwrap()
+                })
+                .collect(),
+        )
+        .build(ValidateLevel::Strict)
+        .unwrap();
+
+    // sum of found values keeps lookups from being optimized out
+    let mut sum = 0;
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for v in 0..COUNT {
+            sum += evs
+                .values
+                .iter()
+                .find(|e| e.matches(v))
+                .and_then(|e| e.value)
+                .unwrap_or(0);
+        }
+    }
+    let linear = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for v in 0..COUNT {
+            sum -= evs.get_by_value(v).and_then(|e| e.value).unwrap_or(0);
+        }
+    }
+    let indexed = start.elapsed();
+    assert_eq!(sum, 0);
+
+    let lookups = (ROUNDS as u64 * COUNT) as u32;
+    println!("linear:  {:?}/lookup", linear / lookups);
+    println!("indexed: {:?}/lookup", indexed / lookups);
+}
@@ -0,0 +1,40 @@
+//! Register description placed in static tables, without any XML.
+//!
+//! Run with `cargo run -p svd-rs --example static_registers --no-default-features`
+
+use svd_rs::layout::{FieldDef, RegisterDef};
+use svd_rs::RegisterInfo;
+
+static REGISTERS: [RegisterDef; 2] = [
+    RegisterDef::new(
+        "CTRL",
+        0x0,
+        16,
+        &[
+            FieldDef::new("EN", 0, 1),
+            FieldDef::new("MODE", 4, 4),
+            FieldDef::new("PRESCALER", 8, 6),
+        ],
+    ),
+    RegisterDef::new(
+        "STATUS",
+        0x4,
+        8,
+        &[FieldDef::new("BUSY", 0, 1), FieldDef::new("ERR", 7, 1)],
+    ),
+];
+
+fn main() {
+    for (register, raw) in REGISTERS.iter().zip([0x2a51, 0x81]) {
+        register.validate().unwrap();
+        println!("{} = {:#x}", register.name, raw);
+        for (name, value) in register.decode(raw).unwrap() {
+            println!("  {} = {}", name, value);
+        }
+        print!("{}", register.render());
+        let utilization = RegisterInfo::from(register)
+            .packing(register.size)
+            .utilization;
+        println!("  {:.0}% of bits used\n", utilization * 100.);
+    }
+}
@@ -1,7 +1,15 @@
+/// Error from parsing [`Access`] with [`FromStr`](core::str::FromStr)
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Unknown access value
+    #[error("Unknown access `{0}`")]
+    Unknown(String),
+}
+
 /// Defines access rights for fields on the device, though it may be specified at a
 /// higher level than individual fields.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum Access {
     /// Read access is permitted. Write operations have an undefined effect.
     #[cfg_attr(feature = "serde", serde(rename = "read-only"))]
@@ -9,6 +17,7 @@ pub enum Access {
 
     /// Read and write accesses are permitted.
     #[cfg_attr(feature = "serde", serde(rename = "read-write"))]
+    #[default]
     ReadWrite,
 
     /// Read access is always permitted.
@@ -39,12 +48,6 @@ impl Access {
     }
 }
 
-impl Default for Access {
-    fn default() -> Self {
-        Self::ReadWrite
-    }
-}
-
 impl Access {
     /// Parse a string into an [`Access`] value, returning [`Option::None`] if the string is not valid.
     pub fn parse_str(s: &str) -> Option<Self> {
@@ -69,3 +72,17 @@ impl Access {
         }
     }
 }
+
+impl core::str::FromStr for Access {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_str(s).ok_or_else(|| Error::Unknown(s.into()))
+    }
+}
+
+impl core::fmt::Display for Access {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
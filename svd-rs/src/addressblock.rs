@@ -2,7 +2,7 @@ use super::{BuildError, Protection, SvdError, ValidateLevel};
 
 ///  An uniquely mapped address block to a peripheral
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct AddressBlock {
     /// Specifies the start address of an address block relative to the peripheral [`baseAddress`](crate::Peripheral::base_address).
@@ -25,9 +25,10 @@ pub struct AddressBlock {
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "kebab-case")
 )]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default)]
 pub enum AddressBlockUsage {
     /// Registers
+    #[default]
     Registers,
     /// Buffer / Memory
     Buffer,
@@ -35,12 +36,6 @@ pub enum AddressBlockUsage {
     Reserved,
 }
 
-impl Default for AddressBlockUsage {
-    fn default() -> Self {
-        Self::Registers
-    }
-}
-
 impl AddressBlockUsage {
     /// Parse a string into an [`AddressBlockUsage`] value, returning [`Option::None`] if the string is not valid.
     pub fn parse_str(s: &str) -> Option<Self> {
@@ -0,0 +1,151 @@
+//! Registers of a device at their absolute addresses.
+
+use super::{
+    cluster, peripheral, register, Device, EffectiveProperties, MaybeArray, RegisterCluster,
+    RegisterInfo, RegisterProperties,
+};
+use std::borrow::Cow;
+
+/// Register placed at its absolute address, see [`Device::address_map`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AddressEntry {
+    /// Dot separated path like `PERIPHERAL.CLUSTER.REGISTER`,
+    /// elements of arrays are named with their index
+    pub path: String,
+    /// Absolute address of the register
+    pub address: u64,
+    /// Size of the register in bytes
+    pub size: u32,
+    /// Properties of the register with those of its parents and the defaults filled in
+    pub properties: EffectiveProperties,
+    /// The register, a single element for arrays
+    pub register: RegisterInfo,
+    /// Index of the register in its array, if it is an element of one
+    pub index: Option<String>,
+}
+
+/// Register covering an address, see [`Device::find_at_address`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RegisterMatch {
+    /// Name of the peripheral, of the element for peripheral arrays
+    pub peripheral: String,
+    /// The register at its absolute address
+    pub entry: AddressEntry,
+    /// Offset of the address from the start of the register in bytes
+    pub offset: u32,
+}
+
+impl Device {
+    /// Every register of the device at its absolute address, sorted by address.
+    ///
+    /// Arrays of peripherals, clusters and registers are expanded and cluster
+    /// offsets are added up through nested clusters.
+    /// `derivedFrom` isn't followed, derived peripherals and registers only
+    /// contribute what they declare themselves until resolved.
+    /// Registers whose address overflows `u64` are left out.
+    pub fn address_map(&self) -> Vec<AddressEntry> {
+        let mut entries = Vec::new();
+        for p in &self.peripherals {
+            let instances: Vec<Cow<_>> = match p {
+                MaybeArray::Single(info) => vec![Cow::Borrowed(info)],
+                MaybeArray::Array(info, dim) => {
+                    peripheral::expand(info, dim).map(Cow::Owned).collect()
+                }
+            };
+            for p in instances {
+                let properties = p
+                    .default_register_properties
+                    .inherit(&self.default_register_properties);
+                collect(
+                    p.registers.as_deref().unwrap_or(&[]),
+                    &p.name,
+                    p.base_address,
+                    &properties,
+                    &mut entries,
+                );
+            }
+        }
+        entries.sort_by_key(|e| e.address);
+        entries
+    }
+
+    /// Register covering `address` according to its effective size, the first
+    /// in [`Device::address_map`] if alternate registers overlap there
+    pub fn find_at_address(&self, address: u64) -> Option<RegisterMatch> {
+        self.find_all_at_address(address).into_iter().next()
+    }
+
+    /// All registers covering `address`, see [`Device::find_at_address`]
+    pub fn find_all_at_address(&self, address: u64) -> Vec<RegisterMatch> {
+        self.address_map()
+            .into_iter()
+            .filter(|e| matches!(address.checked_sub(e.address), Some(o) if o < e.size as u64))
+            .map(|entry| RegisterMatch {
+                peripheral: entry.path.split('.').next().unwrap_or_default().to_string(),
+                offset: (address - entry.address) as u32,
+                entry,
+            })
+            .collect()
+    }
+}
+
+fn collect(
+    children: &[RegisterCluster],
+    path: &str,
+    base_address: u64,
+    inherited: &RegisterProperties,
+    entries: &mut Vec<AddressEntry>,
+) {
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => {
+                let instances: Vec<(Option<String>, Cow<_>)> = match r {
+                    MaybeArray::Single(info) => vec![(None, Cow::Borrowed(info))],
+                    MaybeArray::Array(info, dim) => dim
+                        .indexes()
+                        .map(|i| Some(i.into_owned()))
+                        .zip(register::expand(info, dim).map(Cow::Owned))
+                        .collect(),
+                };
+                for (index, r) in instances {
+                    let address = match base_address.checked_add(r.address_offset as u64) {
+                        Some(address) => address,
+                        None => continue,
+                    };
+                    let properties = r.properties.inherit(inherited).effective();
+                    entries.push(AddressEntry {
+                        path: format!("{}.{}", path, r.name),
+                        address,
+                        size: properties.size / 8 + u32::from(properties.size % 8 != 0),
+                        properties,
+                        register: r.into_owned(),
+                        index,
+                    });
+                }
+            }
+            RegisterCluster::Cluster(c) => {
+                let instances: Vec<Cow<_>> = match c {
+                    MaybeArray::Single(info) => vec![Cow::Borrowed(info)],
+                    MaybeArray::Array(info, dim) => {
+                        cluster::expand(info, dim).map(Cow::Owned).collect()
+                    }
+                };
+                for c in instances {
+                    let address = match base_address.checked_add(c.address_offset as u64) {
+                        Some(address) => address,
+                        None => continue,
+                    };
+                    collect(
+                        &c.children,
+                        &format!("{}.{}", path, c.name),
+                        address,
+                        &c.default_register_properties.inherit(inherited),
+                        entries,
+                    );
+                }
+            }
+        }
+    }
+}
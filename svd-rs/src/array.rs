@@ -1,8 +1,8 @@
-use super::{Description, DimElement, Name};
+use super::{template, Description, DimElement, Name};
 use core::ops::{Deref, DerefMut};
 
 /// A single SVD instance or array of instances
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum MaybeArray<T> {
     /// A single instance
     Single(T),
@@ -71,7 +71,7 @@ pub fn names<'a, T: Name>(info: &'a T, dim: &'a DimElement) -> impl Iterator<Ite
                     .find(|e| e.value.map(|v| v.to_string().as_str() == i.deref()) == Some(true))
             })
             .map(|n| n.name.clone())
-            .unwrap_or_else(|| name.replace("[%s]", &i).replace("%s", &i))
+            .unwrap_or_else(|| template::substitute(name, &i))
     })
 }
 
@@ -90,7 +90,7 @@ pub fn descriptions<'a, T: Description>(
                     .find(|e| e.value.map(|v| v.to_string().as_str() == i.deref()) == Some(true))
             })
             .and_then(|n| n.description.clone())
-            .or_else(|| description.map(|d| d.replace("[%s]", &i).replace("%s", &i)))
+            .or_else(|| description.map(|d| template::substitute(d, &i)))
     })
 }
 
@@ -7,7 +7,7 @@ pub enum Error {
 }
 
 /// A bit range, describing the [least significant bit](Self::lsb) and [most significant bit](Self::msb)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct BitRange {
     /// Value defining the position of the least significant bit of the field within the register
     pub offset: u32,
@@ -20,7 +20,7 @@ pub struct BitRange {
 }
 
 /// The style of bit range that describes a [BitRange]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum BitRangeType {
     /// A bit range in the format: `[<msb>:<lsb>]`
     BitRange,
@@ -32,19 +32,33 @@ pub enum BitRangeType {
 
 impl BitRange {
     /// Get the position of the least significant bit
-    pub fn lsb(&self) -> u32 {
+    pub const fn lsb(&self) -> u32 {
         self.offset
     }
     /// Get the position of the most significant bit
-    pub fn msb(&self) -> u32 {
+    pub const fn msb(&self) -> u32 {
         self.offset + self.width - 1
     }
     /// Get the bit range in the format `[<msb>:<lsb>]`
     pub fn bit_range(&self) -> String {
-        format!("[{}:{}]", self.msb(), self.lsb())
+        self.to_string()
+    }
+    /// Get the mask of bits covered by the range, shifted into position.
+    ///
+    /// Bits above bit 63 are dropped.
+    pub const fn mask(&self) -> u64 {
+        if self.width == 0 || self.offset >= 64 {
+            return 0;
+        }
+        let bits = if self.width >= 64 {
+            u64::MAX
+        } else {
+            (1 << self.width) - 1
+        };
+        bits << self.offset
     }
     /// Construct a [`BitRange`] from a offset and width
-    pub fn from_offset_width(offset: u32, width: u32) -> Self {
+    pub const fn from_offset_width(offset: u32, width: u32) -> Self {
         Self {
             offset,
             width,
@@ -53,21 +67,24 @@ impl BitRange {
     }
 
     /// Construct a [`BitRange`] from a msb and lsb
-    pub fn from_msb_lsb(msb: u32, lsb: u32) -> Self {
+    pub const fn from_msb_lsb(msb: u32, lsb: u32) -> Self {
         Self {
             offset: lsb,
             width: msb - lsb + 1,
             range_type: BitRangeType::MsbLsb,
         }
     }
-    /// Construct a [`BitRange`] from a string in the format `[<msb>:<lsb>]`
+    /// Construct a [`BitRange`] from a string in the format `[<msb>:<lsb>]`.
+    ///
+    /// Whitespace around the numbers is allowed. Returns `None` if `msb` is less than `lsb`.
     pub fn from_bit_range(text: &str) -> Option<Self> {
-        if !text.starts_with('[') || !text.ends_with(']') {
+        let text = text.trim().strip_prefix('[')?.strip_suffix(']')?;
+        let (msb, lsb) = text.split_once(':')?;
+        let msb = msb.trim().parse::<u32>().ok()?;
+        let lsb = lsb.trim().parse::<u32>().ok()?;
+        if msb < lsb {
             return None;
         }
-        let mut parts = text[1..text.len() - 1].split(':');
-        let msb = parts.next()?.parse::<u32>().ok()?;
-        let lsb = parts.next()?.parse::<u32>().ok()?;
         Some(Self {
             offset: lsb,
             width: msb - lsb + 1,
@@ -76,6 +93,12 @@ impl BitRange {
     }
 }
 
+impl core::fmt::Display for BitRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{}:{}]", self.msb(), self.lsb())
+    }
+}
+
 #[cfg(feature = "serde")]
 mod ser_de {
     use super::*;
@@ -1,11 +1,11 @@
 use super::{
     array::{descriptions, names},
     registercluster::{
-        AllRegistersIter, AllRegistersIterMut, ClusterIter, ClusterIterMut, RegisterIter,
-        RegisterIterMut,
+        register_position, AllRegistersIter, AllRegistersIterMut, ClusterIter, ClusterIterMut,
+        RegisterIter, RegisterIterMut,
     },
-    BuildError, Description, DimElement, EmptyToNone, MaybeArray, Name, Register, RegisterCluster,
-    RegisterProperties, SvdError, ValidateLevel,
+    validation, BuildError, BuilderConfig, Description, DimElement, EmptyToNone, MaybeArray, Name,
+    NumberStyles, Register, RegisterCluster, RegisterProperties, SvdError, ValidateLevel,
 };
 use std::ops::Deref;
 
@@ -26,7 +26,7 @@ pub enum Error {
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct ClusterInfo {
     /// String to identify the cluster.
@@ -71,14 +71,20 @@ pub struct ClusterInfo {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub derived_from: Option<String>,
+
+    /// How `addressOffset` and register properties of the cluster were written
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub number_styles: NumberStyles,
 }
 
-/// Return iterator over address offsets of each cluster in array
+/// Return iterator over address offsets of each cluster in array.
+///
+/// Stops at the first offset which doesn't fit in `u32`
 pub fn address_offsets<'a>(
     info: &'a ClusterInfo,
     dim: &'a DimElement,
 ) -> impl Iterator<Item = u32> + 'a {
-    (0..dim.dim).map(move |i| info.address_offset + i * dim.dim_increment)
+    (0..dim.dim).map_while(move |i| dim.offset(info.address_offset, i))
 }
 
 /// Extract `ClusterInfo` items from array
@@ -99,7 +105,7 @@ pub fn expand<'a>(
 }
 
 /// Builder for [`ClusterInfo`]
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct ClusterInfoBuilder {
     name: Option<String>,
     description: Option<String>,
@@ -109,6 +115,7 @@ pub struct ClusterInfoBuilder {
     default_register_properties: RegisterProperties,
     children: Option<Vec<RegisterCluster>>,
     derived_from: Option<String>,
+    number_styles: NumberStyles,
 }
 
 impl From<ClusterInfo> for ClusterInfoBuilder {
@@ -122,6 +129,7 @@ impl From<ClusterInfo> for ClusterInfoBuilder {
             default_register_properties: c.default_register_properties,
             children: Some(c.children),
             derived_from: c.derived_from,
+            number_styles: c.number_styles,
         }
     }
 }
@@ -167,6 +175,11 @@ impl ClusterInfoBuilder {
         self.derived_from = value;
         self
     }
+    /// Set how numbers of the cluster are written.
+    pub fn number_styles(mut self, value: NumberStyles) -> Self {
+        self.number_styles = value;
+        self
+    }
     /// Validate and build a [`ClusterInfo`].
     pub fn build(self, lvl: ValidateLevel) -> Result<ClusterInfo, SvdError> {
         let cluster = ClusterInfo {
@@ -184,10 +197,27 @@ impl ClusterInfoBuilder {
                 .children
                 .ok_or_else(|| BuildError::Uninitialized("children".to_string()))?,
             derived_from: self.derived_from,
+            number_styles: self.number_styles,
         };
         cluster.validate(lvl)?;
         Ok(cluster)
     }
+    /// Build a [`Cluster`], an array if `dim` is given, with checks of builder `config`.
+    ///
+    /// See [`BuilderProfile`](crate::BuilderProfile) for what each profile checks.
+    pub fn build_with(
+        self,
+        dim: Option<DimElement>,
+        config: &BuilderConfig,
+    ) -> Result<Cluster, SvdError> {
+        let cluster = self.build(ValidateLevel::Disabled)?.maybe_array(dim);
+        if let Cluster::Array(info, _) = &cluster {
+            super::check_array_name(&info.name, "cluster")?;
+        }
+        let node = RegisterCluster::Cluster(cluster);
+        validation::check_built(&node, config)?;
+        Ok(Cluster::try_from(node).unwrap())
+    }
 }
 
 impl ClusterInfo {
@@ -275,67 +305,57 @@ impl ClusterInfo {
     }
 
     /// Returns iterator over all descendant registers
-    #[deprecated(since = "0.12.1", note = "Please use `all_registers` instead")]
-    pub fn reg_iter(&self) -> AllRegistersIter {
-        self.all_registers()
-    }
-
-    /// Returns iterator over all descendant registers
-    pub fn all_registers(&self) -> AllRegistersIter {
+    pub fn all_registers(&self) -> AllRegistersIter<'_> {
         AllRegistersIter {
             rem: self.children.iter().rev().collect(),
         }
     }
 
     /// Returns mutable iterator over all descendant registers
-    #[deprecated(since = "0.12.1", note = "Please use `all_registers_mut` instead")]
-    pub fn reg_iter_mut(&mut self) -> AllRegistersIterMut {
-        self.all_registers_mut()
-    }
-
-    /// Returns mutable iterator over all descendant registers
-    pub fn all_registers_mut(&mut self) -> AllRegistersIterMut {
+    pub fn all_registers_mut(&mut self) -> AllRegistersIterMut<'_> {
         AllRegistersIterMut {
             rem: self.children.iter_mut().rev().collect(),
         }
     }
 
     /// Returns iterator over child registers
-    pub fn registers(&self) -> RegisterIter {
+    pub fn registers(&self) -> RegisterIter<'_> {
         RegisterIter {
             all: self.children.iter(),
         }
     }
 
     /// Returns mutable iterator over child registers
-    pub fn registers_mut(&mut self) -> RegisterIterMut {
+    pub fn registers_mut(&mut self) -> RegisterIterMut<'_> {
         RegisterIterMut {
             all: self.children.iter_mut(),
         }
     }
 
     /// Returns iterator over child clusters
-    pub fn clusters(&self) -> ClusterIter {
+    pub fn clusters(&self) -> ClusterIter<'_> {
         ClusterIter {
             all: self.children.iter(),
         }
     }
 
     /// Returns mutable iterator over child clusters
-    pub fn clusters_mut(&mut self) -> ClusterIterMut {
+    pub fn clusters_mut(&mut self) -> ClusterIterMut<'_> {
         ClusterIterMut {
             all: self.children.iter_mut(),
         }
     }
 
-    /// Get register by name
+    /// Get register by [effective name](crate::RegisterInfo::effective_name) or by name
     pub fn get_register(&self, name: &str) -> Option<&Register> {
-        self.registers().find(|f| f.name == name)
+        let i = register_position(self.registers(), name)?;
+        self.registers().nth(i)
     }
 
-    /// Get mutable register by name
+    /// Get mutable register by [effective name](crate::RegisterInfo::effective_name) or by name
     pub fn get_mut_register(&mut self, name: &str) -> Option<&mut Register> {
-        self.registers_mut().find(|f| f.name == name)
+        let i = register_position(self.registers(), name)?;
+        self.registers_mut().nth(i)
     }
 
     /// Get cluster by name
@@ -352,11 +372,50 @@ impl ClusterInfo {
 impl Cluster {
     /// Validate the [`Cluster`] recursively
     pub fn validate_all(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
-        if let Self::Array(_, dim) = self {
+        if let Self::Array(info, dim) = self {
+            if !lvl.is_disabled() {
+                super::check_array_name(&info.name, "cluster")?;
+            }
             dim.validate(lvl)?;
         }
         self.deref().validate_all(lvl)
     }
+
+    /// Clusters of the array like [`Register::expand`], or the cluster itself
+    /// if it isn't an array. Arrays among the children are expanded too.
+    pub fn expand(&self) -> Result<Vec<ClusterInfo>, SvdError> {
+        let clusters = match self {
+            Self::Single(info) => vec![info.clone()],
+            Self::Array(info, dim) => {
+                super::check_array_name(&info.name, "cluster")?;
+                dim.validate(ValidateLevel::Weak)?;
+                dim.check_offsets(info.address_offset)?;
+                expand(info, dim).collect()
+            }
+        };
+        clusters
+            .into_iter()
+            .map(|mut c| {
+                c.children = expand_children(&c.children)?;
+                Ok(c)
+            })
+            .collect()
+    }
+}
+
+fn expand_children(children: &[RegisterCluster]) -> Result<Vec<RegisterCluster>, SvdError> {
+    let mut expanded = Vec::with_capacity(children.len());
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => {
+                expanded.extend(r.expand()?.into_iter().map(|r| r.single().into()))
+            }
+            RegisterCluster::Cluster(c) => {
+                expanded.extend(c.expand()?.into_iter().map(|c| c.single().into()))
+            }
+        }
+    }
+    Ok(expanded)
 }
 
 impl Name for ClusterInfo {
@@ -0,0 +1,193 @@
+//! Registers in clusters with offsets relative to the peripheral.
+//!
+//! `addressOffset` of a register inside of a cluster is relative to the cluster.
+//! Some SVD files get it wrong and use offsets relative to the peripheral, which
+//! places registers outside of the cluster or of the peripheral address blocks.
+//! [`PeripheralInfo::suspicious_cluster_offsets`] finds such registers and
+//! [`PeripheralInfo::rebase_cluster_offsets`] makes their offsets cluster-relative.
+
+use super::{Cluster, Diagnostic, PeripheralInfo, RegisterCluster, RegisterInfo, Severity};
+
+/// Register whose offset looks relative to the peripheral instead of the cluster
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Suspicion {
+    /// Dot separated path of the register, like `PERIPHERAL.CLUSTER.REGISTER`
+    pub path: String,
+    /// Absolute address if the offset is relative to the cluster, as the specification says
+    pub cluster_relative: u64,
+    /// Absolute address if the offset is relative to the peripheral
+    pub peripheral_relative: u64,
+    /// Why the cluster-relative address is wrong
+    pub reason: String,
+}
+
+/// Position of a register in the peripheral
+#[derive(Clone, Copy, Debug, Default)]
+struct Scope {
+    /// Offset of the enclosing cluster in the peripheral, `None` outside of clusters
+    cluster: Option<u64>,
+    /// Offset of the innermost cluster array in the peripheral and its `dimIncrement`
+    array: Option<(u64, u32)>,
+    /// Inherited register size
+    size: Option<u32>,
+}
+
+impl Scope {
+    fn enter(self, c: &Cluster) -> Self {
+        let offset = self.cluster.unwrap_or(0) + c.address_offset as u64;
+        Self {
+            cluster: Some(offset),
+            array: match c {
+                Cluster::Array(_, dim) => Some((offset, dim.dim_increment)),
+                Cluster::Single(_) => self.array,
+            },
+            size: c.default_register_properties.size.or(self.size),
+        }
+    }
+}
+
+impl PeripheralInfo {
+    /// Find registers in clusters whose offsets don't fit when read relative to the
+    /// cluster, but fit when read relative to the peripheral.
+    ///
+    /// An offset doesn't fit if the register ends beyond `dimIncrement` of the
+    /// enclosing cluster array or beyond the last address block of the peripheral.
+    /// Offsets of first elements of arrays are checked, registers without size
+    /// are considered 32-bit.
+    pub fn suspicious_cluster_offsets(&self) -> Vec<Suspicion> {
+        let mut suspicions = Vec::new();
+        let scope = Scope {
+            size: self.default_register_properties.size,
+            ..Default::default()
+        };
+        walk(
+            self.registers.as_deref().unwrap_or(&[]),
+            &self.name,
+            scope,
+            &mut |path, r, scope| {
+                if let Some(s) = self.suspicion(path, r, scope) {
+                    suspicions.push(s);
+                }
+            },
+        );
+        suspicions
+    }
+
+    /// Make offsets found by [`PeripheralInfo::suspicious_cluster_offsets`]
+    /// relative to their clusters.
+    ///
+    /// Returns a diagnostic for every changed register.
+    pub fn rebase_cluster_offsets(&mut self) -> Vec<Diagnostic> {
+        let paths: Vec<String> = self
+            .suspicious_cluster_offsets()
+            .into_iter()
+            .map(|s| s.path)
+            .collect();
+        let mut diagnostics = Vec::new();
+        let scope = Scope {
+            size: self.default_register_properties.size,
+            ..Default::default()
+        };
+        walk_mut(
+            self.registers.as_deref_mut().unwrap_or(&mut []),
+            &self.name,
+            scope,
+            &mut |path, r, scope| {
+                if !paths.contains(&path) {
+                    return;
+                }
+                let cluster = scope.cluster.unwrap_or(0);
+                let old = r.address_offset;
+                r.address_offset = (old as u64 - cluster) as u32;
+                diagnostics.push(Diagnostic::new(
+                    "offset.cluster",
+                    Severity::Info,
+                    path,
+                    format!(
+                        "rebased offset {:#x} relative to peripheral to {:#x} relative to cluster",
+                        old, r.address_offset
+                    ),
+                ));
+            },
+        );
+        diagnostics
+    }
+
+    fn suspicion(&self, path: String, r: &RegisterInfo, scope: Scope) -> Option<Suspicion> {
+        let cluster = scope.cluster?;
+        let bytes = (r.properties.size.or(scope.size).unwrap_or(32) as u64 + 7) / 8;
+        let block_end = self
+            .address_block
+            .iter()
+            .flatten()
+            .map(|b| b.offset as u64 + b.size as u64)
+            .max();
+        // problem with the offset read as relative to `cluster`
+        let problem = |offset: u64| {
+            if let Some((array, increment)) = scope.array {
+                if offset < array || offset - array + bytes > increment as u64 {
+                    return Some(format!(
+                        "ends beyond `dimIncrement` {:#x} of cluster array",
+                        increment
+                    ));
+                }
+            }
+            match block_end {
+                Some(end) if offset + bytes > end => Some(format!(
+                    "ends beyond address blocks of the peripheral at offset {:#x}",
+                    end
+                )),
+                _ => None,
+            }
+        };
+        let in_cluster = cluster + r.address_offset as u64;
+        let in_peripheral = r.address_offset as u64;
+        let reason = problem(in_cluster)?;
+        (in_peripheral >= cluster && problem(in_peripheral).is_none()).then(|| Suspicion {
+            path,
+            cluster_relative: self.base_address + in_cluster,
+            peripheral_relative: self.base_address + in_peripheral,
+            reason,
+        })
+    }
+}
+
+fn walk<'a>(
+    children: &'a [RegisterCluster],
+    path: &str,
+    scope: Scope,
+    f: &mut impl FnMut(String, &'a RegisterInfo, Scope),
+) {
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => f(format!("{}.{}", path, r.name), r, scope),
+            RegisterCluster::Cluster(c) => {
+                walk(
+                    &c.children,
+                    &format!("{}.{}", path, c.name),
+                    scope.enter(c),
+                    f,
+                );
+            }
+        }
+    }
+}
+
+fn walk_mut(
+    children: &mut [RegisterCluster],
+    path: &str,
+    scope: Scope,
+    f: &mut impl FnMut(String, &mut RegisterInfo, Scope),
+) {
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => f(format!("{}.{}", path, r.name), r, scope),
+            RegisterCluster::Cluster(c) => {
+                let scope = scope.enter(c);
+                let path = format!("{}.{}", path, c.name);
+                walk_mut(&mut c.children, &path, scope, f);
+            }
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! Device layout of the Python `cmsis-svd` package.
+//!
+//! [`Device::to_cmsis_svd`] converts a device to the shape of the dictionaries of the
+//! Python package, so scripts working with its JSON can read devices exported by this
+//! crate with any serde serializer:
+//!
+//! - keys are in `snake_case` and absent values are `null`
+//! - numbers like `base_address` are integers
+//! - arrays of peripherals, registers and fields are expanded
+//! - registers of clusters are listed in their peripheral with the cluster name as
+//!   prefix, like `CH0_CCR`, and addresses relative to the peripheral
+//! - register properties and field access are inherited from parents
+//!
+//! `derivedFrom` is not resolved, expand the device with the parser first if needed.
+
+use super::{
+    cluster, field, peripheral, register, AddressBlock, Cluster, Cpu, Device, EnumeratedValue,
+    Field, FieldInfo, Interrupt, MaybeArray, Peripheral, PeripheralInfo, Register, RegisterCluster,
+    RegisterInfo, RegisterProperties,
+};
+use std::borrow::Cow;
+
+/// Device
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisDevice {
+    pub vendor: Option<String>,
+    pub vendor_id: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub cpu: Option<CmsisCpu>,
+    pub address_unit_bits: u32,
+    pub width: u32,
+    pub size: Option<u32>,
+    pub access: Option<&'static str>,
+    pub protection: Option<&'static str>,
+    pub reset_value: Option<u64>,
+    pub reset_mask: Option<u64>,
+    pub peripherals: Vec<CmsisPeripheral>,
+}
+
+/// Processor
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisCpu {
+    pub name: String,
+    pub revision: String,
+    pub endian: &'static str,
+    pub mpu_present: bool,
+    pub fpu_present: bool,
+    pub fpu_dp: Option<bool>,
+    pub icache_present: Option<bool>,
+    pub dcache_present: Option<bool>,
+    pub itcm_present: Option<bool>,
+    pub dtcm_present: Option<bool>,
+    pub vtor_present: Option<bool>,
+    pub nvic_prio_bits: u32,
+    pub vendor_systick_config: bool,
+    pub device_num_interrupts: Option<u32>,
+    pub sau_num_regions: Option<u32>,
+}
+
+/// Peripheral with expanded registers
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisPeripheral {
+    pub name: String,
+    pub version: Option<String>,
+    pub derived_from: Option<String>,
+    pub description: Option<String>,
+    pub group_name: Option<String>,
+    pub prepend_to_name: Option<String>,
+    pub append_to_name: Option<String>,
+    pub header_struct_name: Option<String>,
+    pub disable_condition: Option<String>,
+    pub base_address: u64,
+    pub address_block: Option<CmsisAddressBlock>,
+    pub interrupts: Vec<CmsisInterrupt>,
+    pub registers: Vec<CmsisRegister>,
+    pub size: Option<u32>,
+    pub access: Option<&'static str>,
+    pub protection: Option<&'static str>,
+    pub reset_value: Option<u64>,
+    pub reset_mask: Option<u64>,
+}
+
+/// Address block of a peripheral
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisAddressBlock {
+    pub offset: u32,
+    pub size: u32,
+    pub usage: &'static str,
+}
+
+/// Interrupt
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisInterrupt {
+    pub name: String,
+    pub value: u32,
+    pub description: Option<String>,
+}
+
+/// Register
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisRegister {
+    pub name: String,
+    pub derived_from: Option<String>,
+    pub description: Option<String>,
+    pub address_offset: u32,
+    pub size: Option<u32>,
+    pub access: Option<&'static str>,
+    pub protection: Option<&'static str>,
+    pub reset_value: Option<u64>,
+    pub reset_mask: Option<u64>,
+    pub fields: Vec<CmsisField>,
+    pub display_name: Option<String>,
+    pub alternate_group: Option<String>,
+    pub modified_write_values: Option<&'static str>,
+    pub read_action: Option<&'static str>,
+}
+
+/// Field
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisField {
+    pub name: String,
+    pub derived_from: Option<String>,
+    pub description: Option<String>,
+    pub bit_offset: u32,
+    pub bit_width: u32,
+    pub access: Option<&'static str>,
+    pub enumerated_values: Vec<CmsisEnumeratedValue>,
+    pub modified_write_values: Option<&'static str>,
+    pub read_action: Option<&'static str>,
+}
+
+/// Enumerated value of a field
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[allow(missing_docs)]
+pub struct CmsisEnumeratedValue {
+    pub name: String,
+    pub description: Option<String>,
+    pub value: Option<u64>,
+    pub is_default: Option<bool>,
+}
+
+impl Device {
+    /// Convert the device to the layout of the Python `cmsis-svd` package
+    pub fn to_cmsis_svd(&self) -> CmsisDevice {
+        let p = &self.default_register_properties;
+        CmsisDevice {
+            vendor: self.vendor.clone(),
+            vendor_id: self.vendor_id.clone(),
+            name: self.name.clone(),
+            version: self.version.clone(),
+            description: self.description.clone(),
+            cpu: self.cpu.as_ref().map(cpu),
+            address_unit_bits: self.address_unit_bits,
+            width: self.width,
+            size: p.size,
+            access: p.access.map(|a| a.as_str()),
+            protection: p.protection.map(|p| p.as_str()),
+            reset_value: p.reset_value,
+            reset_mask: p.reset_mask,
+            peripherals: self
+                .peripherals
+                .iter()
+                .flat_map(expand_peripheral)
+                .map(|info| peripheral(&info, p))
+                .collect(),
+        }
+    }
+}
+
+fn cpu(cpu: &Cpu) -> CmsisCpu {
+    CmsisCpu {
+        name: cpu.name.clone(),
+        revision: cpu.revision.clone(),
+        endian: cpu.endian.as_str(),
+        mpu_present: cpu.mpu_present,
+        fpu_present: cpu.fpu_present,
+        fpu_dp: cpu.fpu_double_precision,
+        icache_present: cpu.icache_present,
+        dcache_present: cpu.dcache_present,
+        itcm_present: cpu.itcm_present,
+        dtcm_present: cpu.dtcm_present,
+        vtor_present: cpu.vtor_present,
+        nvic_prio_bits: cpu.nvic_priority_bits,
+        vendor_systick_config: cpu.has_vendor_systick,
+        device_num_interrupts: cpu.device_num_interrupts,
+        sau_num_regions: cpu.sau_num_regions,
+    }
+}
+
+fn expand_peripheral(p: &Peripheral) -> Vec<Cow<'_, PeripheralInfo>> {
+    match p {
+        MaybeArray::Single(info) => vec![Cow::Borrowed(info)],
+        MaybeArray::Array(info, dim) => peripheral::expand(info, dim).map(Cow::Owned).collect(),
+    }
+}
+
+fn peripheral(p: &PeripheralInfo, parent: &RegisterProperties) -> CmsisPeripheral {
+    let properties = p.default_register_properties.inherit(parent);
+    let mut registers = Vec::new();
+    for rc in p.registers.iter().flatten() {
+        flatten(rc, "", 0, &properties, &mut registers);
+    }
+    CmsisPeripheral {
+        name: p.name.clone(),
+        version: p.version.clone(),
+        derived_from: p.derived_from.clone(),
+        description: p.description.clone(),
+        group_name: p.group_name.clone(),
+        prepend_to_name: p.prepend_to_name.clone(),
+        append_to_name: p.append_to_name.clone(),
+        header_struct_name: p.header_struct_name.clone(),
+        disable_condition: p.disable_condition.clone(),
+        base_address: p.base_address,
+        address_block: p.address_block.iter().flatten().next().map(address_block),
+        interrupts: p
+            .interrupt
+            .iter()
+            .map(|i: &Interrupt| CmsisInterrupt {
+                name: i.name.clone(),
+                value: i.value,
+                description: i.description.clone(),
+            })
+            .collect(),
+        registers,
+        size: properties.size,
+        access: properties.access.map(|a| a.as_str()),
+        protection: properties.protection.map(|p| p.as_str()),
+        reset_value: properties.reset_value,
+        reset_mask: properties.reset_mask,
+    }
+}
+
+fn address_block(ab: &AddressBlock) -> CmsisAddressBlock {
+    CmsisAddressBlock {
+        offset: ab.offset,
+        size: ab.size,
+        usage: ab.usage.as_str(),
+    }
+}
+
+/// Add expanded registers of `rc` to `registers`
+fn flatten(
+    rc: &RegisterCluster,
+    prefix: &str,
+    offset: u32,
+    parent: &RegisterProperties,
+    registers: &mut Vec<CmsisRegister>,
+) {
+    match rc {
+        RegisterCluster::Register(r) => {
+            for info in expand_register(r) {
+                registers.push(register(&info, prefix, offset, parent));
+            }
+        }
+        RegisterCluster::Cluster(c) => {
+            for info in expand_cluster(c) {
+                let properties = info.default_register_properties.inherit(parent);
+                let prefix = format!("{}{}_", prefix, info.name);
+                for child in &info.children {
+                    flatten(
+                        child,
+                        &prefix,
+                        offset + info.address_offset,
+                        &properties,
+                        registers,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn expand_register(r: &Register) -> Vec<Cow<'_, RegisterInfo>> {
+    match r {
+        MaybeArray::Single(info) => vec![Cow::Borrowed(info)],
+        MaybeArray::Array(info, dim) => register::expand(info, dim).map(Cow::Owned).collect(),
+    }
+}
+
+fn expand_cluster(c: &Cluster) -> Vec<Cow<'_, super::ClusterInfo>> {
+    match c {
+        MaybeArray::Single(info) => vec![Cow::Borrowed(info)],
+        MaybeArray::Array(info, dim) => cluster::expand(info, dim).map(Cow::Owned).collect(),
+    }
+}
+
+fn register(
+    r: &RegisterInfo,
+    prefix: &str,
+    offset: u32,
+    parent: &RegisterProperties,
+) -> CmsisRegister {
+    let properties = r.properties.inherit(parent);
+    CmsisRegister {
+        name: format!("{}{}", prefix, r.name),
+        derived_from: r.derived_from.clone(),
+        description: r.description.clone(),
+        address_offset: offset + r.address_offset,
+        size: properties.size,
+        access: properties.access.map(|a| a.as_str()),
+        protection: properties.protection.map(|p| p.as_str()),
+        reset_value: properties.reset_value,
+        reset_mask: properties.reset_mask,
+        fields: r
+            .fields
+            .iter()
+            .flatten()
+            .flat_map(expand_field)
+            .map(|f| self::field(&f, properties.access.map(|a| a.as_str())))
+            .collect(),
+        display_name: r.display_name.clone(),
+        alternate_group: r.alternate_group.clone(),
+        modified_write_values: r.modified_write_values.map(|m| m.as_str()),
+        read_action: r.read_action.map(|r| r.as_str()),
+    }
+}
+
+fn expand_field(f: &Field) -> Vec<Cow<'_, FieldInfo>> {
+    match f {
+        MaybeArray::Single(info) => vec![Cow::Borrowed(info)],
+        MaybeArray::Array(info, dim) => field::expand(info, dim).map(Cow::Owned).collect(),
+    }
+}
+
+fn field(f: &FieldInfo, access: Option<&'static str>) -> CmsisField {
+    CmsisField {
+        name: f.name.clone(),
+        derived_from: f.derived_from.clone(),
+        description: f.description.clone(),
+        bit_offset: f.bit_offset(),
+        bit_width: f.bit_width(),
+        access: f.access.map(|a| a.as_str()).or(access),
+        enumerated_values: f
+            .enumerated_values
+            .iter()
+            .flat_map(|evs| &evs.values)
+            .map(|ev: &EnumeratedValue| CmsisEnumeratedValue {
+                name: ev.name.clone(),
+                description: ev.description.clone(),
+                value: ev.value,
+                is_default: ev.is_default,
+            })
+            .collect(),
+        modified_write_values: f.modified_write_values.map(|m| m.as_str()),
+        read_action: f.read_action.map(|r| r.as_str()),
+    }
+}
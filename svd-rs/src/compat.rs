@@ -0,0 +1,48 @@
+//! Superseded APIs kept for compatibility.
+//!
+//! Everything here is deprecated and only forwards to its replacement, named in the
+//! deprecation note. Behavior of these adapters is locked by the `compat` tests and
+//! must not change while they exist.
+//!
+//! Adapters are kept for at least one minor release after the one which deprecated
+//! them and then removed in the next breaking release:
+//!
+//! | Adapter | Replacement | Deprecated | Removal |
+//! |---------|-------------|------------|---------|
+//! | `PeripheralInfo::reg_iter` | [`PeripheralInfo::all_registers`] | 0.12.1 | 0.15.0 |
+//! | `PeripheralInfo::reg_iter_mut` | [`PeripheralInfo::all_registers_mut`] | 0.12.1 | 0.15.0 |
+//! | `ClusterInfo::reg_iter` | [`ClusterInfo::all_registers`] | 0.12.1 | 0.15.0 |
+//! | `ClusterInfo::reg_iter_mut` | [`ClusterInfo::all_registers_mut`] | 0.12.1 | 0.15.0 |
+
+use super::{
+    registercluster::{AllRegistersIter, AllRegistersIterMut},
+    ClusterInfo, PeripheralInfo,
+};
+
+impl PeripheralInfo {
+    /// Returns iterator over all descendant registers
+    #[deprecated(since = "0.12.1", note = "Please use `all_registers` instead")]
+    pub fn reg_iter(&self) -> AllRegistersIter<'_> {
+        self.all_registers()
+    }
+
+    /// Returns mutable iterator over all descendant registers
+    #[deprecated(since = "0.12.1", note = "Please use `all_registers_mut` instead")]
+    pub fn reg_iter_mut(&mut self) -> AllRegistersIterMut<'_> {
+        self.all_registers_mut()
+    }
+}
+
+impl ClusterInfo {
+    /// Returns iterator over all descendant registers
+    #[deprecated(since = "0.12.1", note = "Please use `all_registers` instead")]
+    pub fn reg_iter(&self) -> AllRegistersIter<'_> {
+        self.all_registers()
+    }
+
+    /// Returns mutable iterator over all descendant registers
+    #[deprecated(since = "0.12.1", note = "Please use `all_registers_mut` instead")]
+    pub fn reg_iter_mut(&mut self) -> AllRegistersIterMut<'_> {
+        self.all_registers_mut()
+    }
+}
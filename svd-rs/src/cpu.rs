@@ -1,4 +1,4 @@
-use super::{BuildError, Endian, SvdError, ValidateLevel};
+use super::{BuildError, Endian, SauRegionsConfig, SvdError, ValidateLevel};
 /// CPU describes the processor included in the microcontroller device.
 #[cfg_attr(
     feature = "serde",
@@ -95,7 +95,13 @@ pub struct Cpu {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub sau_num_regions: Option<u32>,
-    // sauRegionsConfig
+
+    /// Initial configuration of the Security Attribution Unit (SAU)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub sau_regions_config: Option<SauRegionsConfig>,
 }
 
 /// Builder for [`Cpu`]
@@ -117,6 +123,7 @@ pub struct CpuBuilder {
     has_vendor_systick: Option<bool>,
     device_num_interrupts: Option<u32>,
     sau_num_regions: Option<u32>,
+    sau_regions_config: Option<SauRegionsConfig>,
 }
 
 impl From<Cpu> for CpuBuilder {
@@ -138,6 +145,7 @@ impl From<Cpu> for CpuBuilder {
             has_vendor_systick: Some(c.has_vendor_systick),
             device_num_interrupts: c.device_num_interrupts,
             sau_num_regions: c.sau_num_regions,
+            sau_regions_config: c.sau_regions_config,
         }
     }
 }
@@ -223,6 +231,11 @@ impl CpuBuilder {
         self.sau_num_regions = value;
         self
     }
+    /// Set the sau_regions_config of the cpu.
+    pub fn sau_regions_config(mut self, value: Option<SauRegionsConfig>) -> Self {
+        self.sau_regions_config = value;
+        self
+    }
     /// Validate and build a [`Cpu`].
     pub fn build(self, lvl: ValidateLevel) -> Result<Cpu, SvdError> {
         let cpu = Cpu {
@@ -256,6 +269,7 @@ impl CpuBuilder {
                 .ok_or_else(|| BuildError::Uninitialized("has_vendor_systick".to_string()))?,
             device_num_interrupts: self.device_num_interrupts,
             sau_num_regions: self.sau_num_regions,
+            sau_regions_config: self.sau_regions_config,
         };
         cpu.validate(lvl)?;
         Ok(cpu)
@@ -317,15 +331,255 @@ impl Cpu {
         if builder.sau_num_regions.is_some() {
             self.sau_num_regions = builder.sau_num_regions;
         }
+        if builder.sau_regions_config.is_some() {
+            self.sau_regions_config = builder.sau_regions_config;
+        }
         self.validate(lvl)
     }
     /// Validate the [`Cpu`]
-    pub fn validate(&self, _lvl: ValidateLevel) -> Result<(), SvdError> {
+    pub fn validate(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
         // TODO
+        if let Some(sau) = &self.sau_regions_config {
+            sau.validate(lvl)?;
+        }
         Ok(())
     }
+    /// Revision of the processor if it is written as `rNpM`, like `r1p2`
+    pub fn cpu_revision(&self) -> Option<CpuRevision> {
+        self.revision.parse().ok()
+    }
     /// Check if the [`Cpu`] is a Cortex-M
     pub fn is_cortex_m(&self) -> bool {
         self.name.starts_with("CM")
     }
+
+    fn core(&self) -> Option<&'static Core> {
+        CORES
+            .iter()
+            .find(|c| c.names.iter().any(|n| n.eq_ignore_ascii_case(&self.name)))
+    }
+
+    /// Architecture of the core. [`CortexArch::Other`] for unknown names,
+    /// `None` if the name is empty
+    pub fn architecture(&self) -> Option<CortexArch> {
+        if self.name.is_empty() {
+            return None;
+        }
+        Some(self.core().map_or(CortexArch::Other, |c| c.arch))
+    }
+
+    /// Returns `true` if the device has FPU.
+    ///
+    /// `fpuPresent` is used even if the core can't have FPU, see
+    /// [`Cpu::capability_conflicts`].
+    pub fn has_fpu(&self) -> bool {
+        self.fpu_present
+    }
+
+    /// Returns `true` if the device has MPU
+    pub fn has_mpu(&self) -> bool {
+        self.mpu_present
+    }
+
+    /// Returns `true` if the core implements security extension (TrustZone)
+    /// or the device has SAU regions
+    pub fn is_trustzone_capable(&self) -> bool {
+        matches!(
+            self.architecture(),
+            Some(
+                CortexArch::ArmV8MBaseline
+                    | CortexArch::ArmV8MMainline
+                    | CortexArch::ArmV81MMainline
+            )
+        ) || self.sau_num_regions.map_or(false, |n| n > 0)
+    }
+
+    /// Number of interrupt priority levels of NVIC
+    pub fn nvic_levels(&self) -> u32 {
+        1u32.checked_shl(self.nvic_priority_bits)
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Returns `true` if vector table can be relocated with VTOR.
+    ///
+    /// If `vtorPresent` is absent, VTOR is assumed present on all cores except
+    /// ones which never have it (Cortex-M0, Cortex-M1 and SC000).
+    pub fn supports_vtor(&self) -> bool {
+        self.vtor_present
+            .unwrap_or_else(|| self.core().map_or(true, |c| c.vtor_default))
+    }
+
+    /// Descriptions of properties which contradict capabilities of the core
+    pub fn capability_conflicts(&self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        let core = match self.core() {
+            Some(core) => core,
+            None => return conflicts,
+        };
+        if self.fpu_present && core.fpu == FpuSupport::Absent {
+            conflicts.push(format!("`fpuPresent` is set, but {} has no FPU", self.name));
+        }
+        if self.fpu_double_precision == Some(true) {
+            if !self.fpu_present {
+                conflicts.push("`fpuDP` is set, but `fpuPresent` is not".into());
+            } else if core.fpu == FpuSupport::Single {
+                conflicts.push(format!(
+                    "`fpuDP` is set, but FPU of {} is single precision only",
+                    self.name
+                ));
+            }
+        }
+        if self.vtor_present == Some(true) && !core.vtor_default {
+            conflicts.push(format!(
+                "`vtorPresent` is set, but {} has no VTOR",
+                self.name
+            ));
+        }
+        if self.sau_num_regions.map_or(false, |n| n > 0)
+            && !matches!(
+                core.arch,
+                CortexArch::ArmV8MBaseline
+                    | CortexArch::ArmV8MMainline
+                    | CortexArch::ArmV81MMainline
+            )
+        {
+            conflicts.push(format!(
+                "`sauNumRegions` is set, but {} has no SAU",
+                self.name
+            ));
+        }
+        conflicts
+    }
+}
+
+/// Errors from parsing [`CpuRevision`]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Revision is not in `rNpM` form
+    #[error("Invalid cpu revision `{0}`, expected `rNpM`")]
+    InvalidRevision(String),
+}
+
+/// Processor revision `rNpM`, see [`Cpu::cpu_revision`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CpuRevision {
+    /// Major revision, `N` in `rNpM`
+    pub major: u32,
+    /// Minor revision (patch), `M` in `rNpM`
+    pub minor: u32,
+}
+
+impl core::str::FromStr for CpuRevision {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidRevision(s.into());
+        let (major, minor) = s
+            .trim()
+            .strip_prefix(['r', 'R'])
+            .and_then(|rest| rest.split_once(['p', 'P']))
+            .ok_or_else(invalid)?;
+        let number = |n: &str| {
+            if n.is_empty() || !n.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            n.parse().map_err(|_| invalid())
+        };
+        Ok(Self {
+            major: number(major)?,
+            minor: number(minor)?,
+        })
+    }
+}
+
+impl core::fmt::Display for CpuRevision {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "r{}p{}", self.major, self.minor)
+    }
+}
+
+/// Architecture of a processor core, see [`Cpu::architecture`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CortexArch {
+    /// Armv6-M: Cortex-M0, Cortex-M0+, Cortex-M1, SC000
+    ArmV6M,
+    /// Armv7-M: Cortex-M3, SC300
+    ArmV7M,
+    /// Armv7E-M: Cortex-M4, Cortex-M7
+    ArmV7EM,
+    /// Armv8-M Baseline: Cortex-M23
+    ArmV8MBaseline,
+    /// Armv8-M Mainline: Cortex-M33, Cortex-M35P
+    ArmV8MMainline,
+    /// Armv8.1-M Mainline: Cortex-M52, Cortex-M55, Cortex-M85
+    ArmV81MMainline,
+    /// Armv7-A: Cortex-A5, Cortex-A7, Cortex-A8, Cortex-A9, Cortex-A15, Cortex-A17
+    ArmV7A,
+    /// Armv8-A: Cortex-A53, Cortex-A57, Cortex-A72
+    ArmV8A,
+    /// Unknown or other core
+    Other,
+}
+
+/// FPU which can be implemented with a core
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FpuSupport {
+    Absent,
+    Single,
+    Double,
+}
+
+/// Known core with its capabilities
+struct Core {
+    /// Values of `name` of `cpu` element
+    names: &'static [&'static str],
+    arch: CortexArch,
+    fpu: FpuSupport,
+    /// `vtorPresent` default
+    vtor_default: bool,
 }
+
+impl Core {
+    const fn new(
+        names: &'static [&'static str],
+        arch: CortexArch,
+        fpu: FpuSupport,
+        vtor_default: bool,
+    ) -> Self {
+        Self {
+            names,
+            arch,
+            fpu,
+            vtor_default,
+        }
+    }
+}
+
+/// Cores listed in the SVD schema
+const CORES: &[Core] = {
+    use CortexArch::*;
+    use FpuSupport::*;
+    &[
+        Core::new(&["CM0", "CM1", "SC000"], ArmV6M, Absent, false),
+        Core::new(&["CM0PLUS", "CM0+"], ArmV6M, Absent, true),
+        Core::new(&["CM3", "SC300"], ArmV7M, Absent, true),
+        Core::new(&["CM4"], ArmV7EM, Single, true),
+        Core::new(&["CM7"], ArmV7EM, Double, true),
+        Core::new(&["CM23", "ARMV8MBL"], ArmV8MBaseline, Absent, true),
+        Core::new(&["CM33", "CM35P", "ARMV8MML"], ArmV8MMainline, Single, true),
+        Core::new(
+            &["CM52", "CM55", "CM85", "ARMV81MML"],
+            ArmV81MMainline,
+            Double,
+            true,
+        ),
+        Core::new(
+            &["CA5", "CA7", "CA8", "CA9", "CA15", "CA17"],
+            ArmV7A,
+            Double,
+            true,
+        ),
+        Core::new(&["CA53", "CA57", "CA72"], ArmV8A, Double, true),
+    ]
+};
@@ -4,7 +4,7 @@
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "snake_case")
 )]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum DataType {
     /// unsigned byte
     U8,
@@ -42,7 +42,17 @@ pub enum DataType {
 
 impl DataType {
     /// Parse a string into an [`DataType`] value, returning [`Option::None`] if the string is not valid.
+    ///
+    /// Like in the schema, any spaces are allowed before the `*` of pointer types.
     pub fn parse_str(s: &str) -> Option<Self> {
+        let normalized;
+        let s = match s.strip_suffix('*') {
+            Some(base) => {
+                normalized = format!("{} *", base.trim_end());
+                &normalized
+            }
+            None => s,
+        };
         match s {
             "uint8_t" => Some(Self::U8),
             "uint16_t" => Some(Self::U16),
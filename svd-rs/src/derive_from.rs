@@ -1,7 +1,7 @@
 //! Implementations of DeriveFrom, setting non-explicit fields.
 use crate::{
-    ClusterInfo, EnumeratedValues, FieldInfo, MaybeArray, PeripheralInfo, RegisterInfo,
-    RegisterProperties,
+    ClusterInfo, EnumeratedValues, FieldInfo, MaybeArray, PeripheralInfo, RegisterCluster,
+    RegisterInfo, RegisterProperties,
 };
 
 /// Fill empty fields of structure with values of other structure
@@ -19,9 +19,7 @@ impl DeriveFrom for ClusterInfo {
         derived.header_struct_name = derived
             .header_struct_name
             .or_else(|| other.header_struct_name.clone());
-        if derived.children.is_empty() {
-            derived.children = other.children.clone();
-        }
+        derived.children = merge_children(&derived.children, &other.children);
         derived
     }
 }
@@ -52,10 +50,16 @@ impl DeriveFrom for PeripheralInfo {
         derived.header_struct_name = derived
             .header_struct_name
             .or_else(|| other.header_struct_name.clone());
+        derived.disable_condition = derived
+            .disable_condition
+            .or_else(|| other.disable_condition.clone());
         derived.default_register_properties = derived
             .default_register_properties
             .derive_from(&other.default_register_properties);
-        derived.registers = derived.registers.or_else(|| other.registers.clone());
+        derived.registers = match (derived.registers.as_ref(), other.registers.as_ref()) {
+            (Some(own), Some(base)) => Some(merge_children(own, base)),
+            (own, base) => own.or(base).cloned(),
+        };
         if derived.interrupt.is_empty() {
             derived.interrupt = other.interrupt.clone();
         }
@@ -63,6 +67,22 @@ impl DeriveFrom for PeripheralInfo {
     }
 }
 
+/// Registers and clusters of `base`, each replaced by the one of `own` with the same name,
+/// followed by the rest of `own`
+fn merge_children(own: &[RegisterCluster], base: &[RegisterCluster]) -> Vec<RegisterCluster> {
+    let mut merged = base.to_vec();
+    for rc in own {
+        match merged
+            .iter_mut()
+            .find(|b| b.effective_name() == rc.effective_name())
+        {
+            Some(b) => *b = rc.clone(),
+            None => merged.push(rc.clone()),
+        }
+    }
+    merged
+}
+
 impl DeriveFrom for RegisterInfo {
     fn derive_from(&self, other: &Self) -> Self {
         let mut derived = self.clone();
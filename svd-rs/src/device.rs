@@ -1,8 +1,11 @@
 #[cfg(feature = "unstable-riscv")]
 use super::Riscv;
+#[cfg(feature = "derive-from")]
+use super::{peripheral, RegisterCluster};
 use super::{
-    BuildError, Cpu, Description, EmptyToNone, Name, Peripheral, RegisterProperties, SvdError,
-    ValidateLevel,
+    BuildError, Cpu, Description, Diagnostic, EffectiveProperties, EmptyToNone, Interrupt, Name,
+    NumberStyles, Peripheral, PeripheralInfo, ProvenanceEntry, ProvenanceRecorder, Register,
+    RegisterProperties, SvdError, ValidateLevel, ValidationConfig,
 };
 
 /// Errors for [`Device::validate`]
@@ -11,6 +14,98 @@ pub enum Error {
     /// Device has no peripherals
     #[error("Device must contain at least one peripheral")]
     EmptyDevice,
+    /// Two different interrupts have the same number
+    #[error("Interrupt {0} is claimed by `{1}` (peripheral `{2}`) and `{3}` (peripheral `{4}`)")]
+    InterruptConflict(u32, String, String, String, String),
+    /// Interrupt number doesn't fit in the interrupt table
+    #[error("Interrupt `{0}` has number {1}, the table holds {2} interrupts")]
+    InterruptOutOfRange(String, u32, u32),
+    /// Register is derived from a register that doesn't exist
+    #[error("Register `{0}` is derived from unknown register `{1}`")]
+    MissingDerivedRegister(String, String),
+    /// Registers are derived from each other in a cycle
+    #[error("Cyclic derivation of `{0}` from `{1}`")]
+    DerivationCycle(String, String),
+}
+
+/// Options for [`Device::interrupt_table`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InterruptTableConfig {
+    /// Merge interrupts of secure/non-secure peripheral aliases into one entry
+    pub collapse_security_aliases: bool,
+    /// Name suffix of secure peripheral aliases
+    pub secure_suffix: String,
+    /// Name suffix of non-secure peripheral aliases
+    pub non_secure_suffix: String,
+    /// Most interrupts the table holds, 496 of the Cortex-M NVIC by default
+    pub max_interrupts: u32,
+}
+
+impl Default for InterruptTableConfig {
+    fn default() -> Self {
+        Self {
+            collapse_security_aliases: false,
+            secure_suffix: "_S".into(),
+            non_secure_suffix: "_NS".into(),
+            max_interrupts: 496,
+        }
+    }
+}
+
+impl InterruptTableConfig {
+    /// Merge interrupts of secure/non-secure peripheral aliases into one entry
+    pub fn collapse_security_aliases(mut self, val: bool) -> Self {
+        self.collapse_security_aliases = val;
+        self
+    }
+    /// Name suffixes of secure and non-secure peripheral aliases
+    pub fn security_suffixes(mut self, secure: String, non_secure: String) -> Self {
+        self.secure_suffix = secure;
+        self.non_secure_suffix = non_secure;
+        self
+    }
+    /// Most interrupts the table holds
+    pub fn max_interrupts(mut self, val: u32) -> Self {
+        self.max_interrupts = val;
+        self
+    }
+
+    fn strip_suffix<'a>(&self, name: &'a str) -> &'a str {
+        // `_NS` ends with `_S`, so try the longest suffix first
+        let (first, second) = if self.secure_suffix.len() > self.non_secure_suffix.len() {
+            (&self.secure_suffix, &self.non_secure_suffix)
+        } else {
+            (&self.non_secure_suffix, &self.secure_suffix)
+        };
+        name.strip_suffix(first.as_str())
+            .or_else(|| name.strip_suffix(second.as_str()))
+            .unwrap_or(name)
+    }
+
+    /// Returns `true` if peripherals are secure and non-secure views of the same hardware
+    pub fn is_security_alias(&self, a: &PeripheralInfo, b: &PeripheralInfo) -> bool {
+        if a.alternate_peripheral.as_deref() == Some(b.name.as_str())
+            || b.alternate_peripheral.as_deref() == Some(a.name.as_str())
+        {
+            return true;
+        }
+        a.name != b.name && self.strip_suffix(&a.name) == self.strip_suffix(&b.name)
+    }
+
+    /// Returns `true` if interrupt names are equal up to the security suffix
+    pub fn is_same_interrupt(&self, a: &str, b: &str) -> bool {
+        self.strip_suffix(a) == self.strip_suffix(b)
+    }
+}
+
+/// Entry of the device vector table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterruptEntry<'a> {
+    /// Interrupt placed at this position
+    pub interrupt: &'a Interrupt,
+    /// Peripherals that list this interrupt
+    pub peripherals: Vec<&'a Peripheral>,
 }
 
 /// The top element in a SVD file. Describes information specific to a device.
@@ -19,7 +114,7 @@ pub enum Error {
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct Device {
     /// Specify the vendor of the device using the full name
@@ -47,12 +142,21 @@ pub struct Device {
     pub series: Option<String>,
 
     /// Define the version of the SVD file
-    pub version: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub version: Option<String>,
 
     /// Describe the main features of the device (for example CPU, clock frequency, peripheral overview)
-    pub description: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub description: Option<String>,
 
-    /// The text will be copied into the header section of the generated device header file and shall contain the legal disclaimer
+    /// The text will be copied into the header section of the generated device header file and shall contain the legal disclaimer.
+    /// Kept as written, use [`Device::license`] to read it with line breaks
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -94,18 +198,33 @@ pub struct Device {
     pub peripherals: Vec<Peripheral>,
 
     /// Specify the underlying XML schema to which the CMSIS-SVD schema is compliant.
-    #[cfg_attr(feature = "serde", serde(skip, default = "default_xmlns_xs"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "default_xmlns_xs",
+            skip_serializing_if = "is_default_xmlns_xs"
+        )
+    )]
     pub xmlns_xs: String,
 
     /// Specify the file path and file name of the CMSIS-SVD Schema
     #[cfg_attr(
         feature = "serde",
-        serde(skip, default = "default_no_namespace_schema_location")
+        serde(
+            default = "default_no_namespace_schema_location",
+            skip_serializing_if = "is_default_no_namespace_schema_location"
+        )
     )]
     pub no_namespace_schema_location: String,
 
     /// Specify the compliant CMSIS-SVD schema version
-    #[cfg_attr(feature = "serde", serde(skip, default = "default_schema_version"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "default_schema_version",
+            skip_serializing_if = "is_default_schema_version"
+        )
+    )]
     pub schema_version: String,
 
     /// Describe the processor included in the device
@@ -115,6 +234,14 @@ pub struct Device {
     )]
     #[cfg(feature = "unstable-riscv")]
     pub riscv: Option<Riscv>,
+
+    /// Changes made by passes, see [`Device::enable_provenance`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub provenance: ProvenanceRecorder,
+
+    /// How `size`, `resetValue` and `resetMask` of the device were written
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub number_styles: NumberStyles,
 }
 
 fn default_xmlns_xs() -> String {
@@ -129,9 +256,21 @@ fn default_no_namespace_schema_location() -> String {
 fn default_schema_version() -> String {
     "1.1".into()
 }
+#[cfg(feature = "serde")]
+fn is_default_xmlns_xs(value: &str) -> bool {
+    value == default_xmlns_xs()
+}
+#[cfg(feature = "serde")]
+fn is_default_no_namespace_schema_location(value: &str) -> bool {
+    value == default_no_namespace_schema_location()
+}
+#[cfg(feature = "serde")]
+fn is_default_schema_version(value: &str) -> bool {
+    value == default_schema_version()
+}
 
 /// Builder for [`Device`]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct DeviceBuilder {
     vendor: Option<String>,
     vendor_id: Option<String>,
@@ -152,6 +291,7 @@ pub struct DeviceBuilder {
     xmlns_xs: Option<String>,
     no_namespace_schema_location: Option<String>,
     schema_version: Option<String>,
+    number_styles: NumberStyles,
 }
 
 impl From<Device> for DeviceBuilder {
@@ -161,8 +301,8 @@ impl From<Device> for DeviceBuilder {
             vendor_id: d.vendor_id,
             name: Some(d.name),
             series: d.series,
-            version: Some(d.version),
-            description: Some(d.description),
+            version: d.version,
+            description: d.description,
             license_text: d.license_text,
             #[cfg(feature = "unstable-riscv")]
             riscv: d.riscv,
@@ -176,6 +316,7 @@ impl From<Device> for DeviceBuilder {
             xmlns_xs: Some(d.xmlns_xs),
             no_namespace_schema_location: Some(d.no_namespace_schema_location),
             schema_version: Some(d.schema_version),
+            number_styles: d.number_styles,
         }
     }
 }
@@ -202,13 +343,13 @@ impl DeviceBuilder {
         self
     }
     /// Set the version of the device.
-    pub fn version(mut self, value: String) -> Self {
-        self.version = Some(value);
+    pub fn version(mut self, value: Option<String>) -> Self {
+        self.version = value;
         self
     }
     /// Set the description of the device.
-    pub fn description(mut self, value: String) -> Self {
-        self.description = Some(value);
+    pub fn description(mut self, value: Option<String>) -> Self {
+        self.description = value;
         self
     }
     /// Set the license_text of the device.
@@ -272,6 +413,11 @@ impl DeviceBuilder {
         self.schema_version = Some(value);
         self
     }
+    /// Set how numbers of the device are written.
+    pub fn number_styles(mut self, value: NumberStyles) -> Self {
+        self.number_styles = value;
+        self
+    }
     /// Validate and build a [`Device`].
     pub fn build(self, lvl: ValidateLevel) -> Result<Device, SvdError> {
         let schema_version = self.schema_version.unwrap_or_else(default_schema_version);
@@ -282,26 +428,8 @@ impl DeviceBuilder {
                 .name
                 .ok_or_else(|| BuildError::Uninitialized("name".to_string()))?,
             series: self.series,
-            version: self
-                .version
-                .or_else(|| {
-                    if !lvl.is_strict() {
-                        Some("1.0".into())
-                    } else {
-                        None
-                    }
-                })
-                .ok_or_else(|| BuildError::Uninitialized("version".to_string()))?,
-            description: self
-                .description
-                .or_else(|| {
-                    if !lvl.is_strict() {
-                        Some("".into())
-                    } else {
-                        None
-                    }
-                })
-                .ok_or_else(|| BuildError::Uninitialized("description".to_string()))?,
+            version: self.version,
+            description: self.description,
             license_text: self.license_text,
             #[cfg(feature = "unstable-riscv")]
             riscv: self.riscv,
@@ -325,6 +453,8 @@ impl DeviceBuilder {
                 .no_namespace_schema_location
                 .unwrap_or_else(default_no_namespace_schema_location),
             schema_version,
+            provenance: ProvenanceRecorder::default(),
+            number_styles: self.number_styles,
         };
         device.validate(lvl)?;
         Ok(device)
@@ -354,11 +484,11 @@ impl Device {
         if builder.series.is_some() {
             self.series = builder.series.empty_to_none();
         }
-        if let Some(version) = builder.version {
-            self.version = version;
+        if builder.version.is_some() {
+            self.version = builder.version.empty_to_none();
         }
-        if let Some(description) = builder.description {
-            self.description = description;
+        if builder.description.is_some() {
+            self.description = builder.description.empty_to_none();
         }
         if builder.license_text.is_some() {
             self.license_text = builder.license_text.empty_to_none();
@@ -421,6 +551,54 @@ impl Device {
         self.validate(lvl)
     }
 
+    /// Returns `true` if `schemaVersion` is `major.minor` or newer.
+    ///
+    /// Malformed versions are kept as written when parsing and are never newer.
+    pub fn is_at_least(&self, major: u32, minor: u32) -> bool {
+        let mut parts = self.schema_version.trim().split('.');
+        let mut next = || parts.next().map(str::parse::<u32>);
+        match (next(), next()) {
+            (Some(Ok(ma)), Some(Ok(mi))) => (ma, mi) >= (major, minor),
+            (Some(Ok(ma)), None) => (ma, 0) >= (major, minor),
+            _ => false,
+        }
+    }
+
+    /// License text with `\n` escapes of the schema replaced with line breaks
+    /// and spaces around every line removed.
+    ///
+    /// [`Device::license_text`] keeps the text as written, so it is encoded unchanged.
+    pub fn license(&self) -> Option<String> {
+        let text = self.license_text.as_deref()?.replace("\\n", "\n");
+        let lines: Vec<&str> = text.lines().map(str::trim).collect();
+        Some(lines.join("\n").trim_matches('\n').into())
+    }
+
+    /// Size of registers which don't declare it and don't inherit it from
+    /// a peripheral or cluster: the device `size` property or the bus `width`
+    pub fn default_register_size(&self) -> u32 {
+        self.default_register_properties.size.unwrap_or(self.width)
+    }
+
+    /// Run checks which need context of the whole device.
+    ///
+    /// Returns all problems found. See [`validation`](crate::validation) for details.
+    pub fn validate_with(&self, config: &ValidationConfig) -> Vec<Diagnostic> {
+        crate::validation::validate(self, config)
+    }
+
+    /// Set size of registers whose fields have bits above it to the smallest
+    /// of 8, 16, 32 or 64 bits which contains all of them.
+    ///
+    /// Returns a diagnostic for every changed register.
+    /// Registers without declared or inherited size are not changed.
+    pub fn widen_registers_to_fields(&mut self) -> Vec<Diagnostic> {
+        let mut provenance = core::mem::take(&mut self.provenance);
+        let diagnostics = crate::validation::widen_registers(self, &mut provenance);
+        self.provenance = provenance;
+        diagnostics
+    }
+
     /// Get peripheral by name
     pub fn get_peripheral(&self, name: &str) -> Option<&Peripheral> {
         self.peripherals.iter().find(|f| f.name == name)
@@ -430,6 +608,258 @@ impl Device {
     pub fn get_mut_peripheral(&mut self, name: &str) -> Option<&mut Peripheral> {
         self.peripherals.iter_mut().find(|f| f.name == name)
     }
+
+    /// Start recording changes made by passes to [`Device::provenance`]
+    pub fn enable_provenance(&mut self) {
+        if !self.provenance.is_enabled() {
+            self.provenance = ProvenanceRecorder::new();
+        }
+    }
+
+    /// Changes of element at dot separated `path`, oldest first
+    pub fn provenance(&self, path: &str) -> &[ProvenanceEntry] {
+        self.provenance.entries(path)
+    }
+
+    /// Human readable history of all changed elements
+    pub fn provenance_report(&self) -> String {
+        self.provenance.report()
+    }
+
+    /// Find register at dot separated `path` like `PERIPHERAL.CLUSTER.REGISTER`.
+    ///
+    /// Registers are looked up like in [`PeripheralInfo::get_register`], by
+    /// effective name or by name.
+    pub fn find(&self, path: &str) -> Option<&Register> {
+        let (peripheral, register) = path.split_once('.')?;
+        self.get_peripheral(peripheral)?.get_register(register)
+    }
+
+    /// Properties of register at dot separated `path` like `PERIPHERAL.CLUSTER.REGISTER`.
+    ///
+    /// Each property is taken from the first of the register, its clusters from the
+    /// innermost, the peripheral and the device which sets it, or from the defaults
+    /// of [`RegisterProperties::effective`]. `derivedFrom` isn't followed,
+    /// resolve it first with `Device::resolve_derived` of the `derive-from` feature.
+    pub fn effective_properties(&self, path: &str) -> Option<EffectiveProperties> {
+        let mut segments: Vec<&str> = path.split('.').collect();
+        let register = segments.pop()?;
+        let (peripheral, clusters) = segments.split_first()?;
+        let peripheral = self.get_peripheral(peripheral)?;
+        let mut properties = peripheral.effective_register_properties(self);
+        let register = match clusters.split_first() {
+            None => peripheral.get_register(register)?,
+            Some((first, rest)) => {
+                let mut cluster = peripheral.get_cluster(first)?;
+                properties = cluster.default_register_properties.inherit(&properties);
+                for name in rest {
+                    cluster = cluster.get_cluster(name)?;
+                    properties = cluster.default_register_properties.inherit(&properties);
+                }
+                cluster.get_register(register)?
+            }
+        };
+        Some(register.properties.inherit(&properties).effective())
+    }
+
+    /// Iterate over interrupts of all peripherals together with the peripheral
+    /// which lists them, in order of peripherals.
+    ///
+    /// Derived peripherals only give the interrupts they list themselves,
+    /// `derivedFrom` doesn't copy interrupts.
+    pub fn interrupts(&self) -> impl Iterator<Item = (&Peripheral, &Interrupt)> {
+        self.peripherals
+            .iter()
+            .flat_map(|p| p.interrupt.iter().map(move |i| (p, i)))
+    }
+
+    /// Place each interrupt of the device at its `value` index.
+    ///
+    /// Interrupts with the same number and name are merged into one entry.
+    /// Different names on one number are reported as conflict unless
+    /// [`collapse_security_aliases`](InterruptTableConfig::collapse_security_aliases) is set
+    /// and the owners are secure/non-secure aliases of each other.
+    /// Numbers from [`max_interrupts`](InterruptTableConfig::max_interrupts) up are rejected.
+    pub fn interrupt_table(
+        &self,
+        config: &InterruptTableConfig,
+    ) -> Result<Vec<Option<InterruptEntry<'_>>>, SvdError> {
+        let mut table: Vec<Option<InterruptEntry>> = Vec::new();
+        for (p, i) in self.interrupts() {
+            if i.value >= config.max_interrupts {
+                return Err(Error::InterruptOutOfRange(
+                    i.name.clone(),
+                    i.value,
+                    config.max_interrupts,
+                )
+                .into());
+            }
+            let idx = i.value as usize;
+            if table.len() <= idx {
+                table.resize(idx + 1, None);
+            }
+            match &mut table[idx] {
+                slot @ None => {
+                    *slot = Some(InterruptEntry {
+                        interrupt: i,
+                        peripherals: vec![p],
+                    });
+                }
+                Some(entry) => {
+                    let owner = entry.peripherals[0];
+                    let aliased = config.collapse_security_aliases
+                        && entry
+                            .peripherals
+                            .iter()
+                            .all(|o| config.is_security_alias(o, p))
+                        && config.is_same_interrupt(&entry.interrupt.name, &i.name);
+                    if entry.interrupt.name != i.name && !aliased {
+                        return Err(Error::InterruptConflict(
+                            i.value,
+                            entry.interrupt.name.clone(),
+                            owner.name.clone(),
+                            i.name.clone(),
+                            p.name.clone(),
+                        )
+                        .into());
+                    }
+                    entry.peripherals.push(p);
+                }
+            }
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(feature = "derive-from")]
+impl Device {
+    /// Apply `derivedFrom` of all peripherals, so that they contain everything
+    /// of the peripheral they are derived from.
+    ///
+    /// Chains are resolved transitively, in any order of peripherals.
+    /// Fails if the base peripheral doesn't exist or derivation is cyclic.
+    pub fn resolve_derived_peripherals(&mut self) -> Result<(), SvdError> {
+        let mut stack = Vec::new();
+        for i in 0..self.peripherals.len() {
+            resolve_derived_peripheral(&mut self.peripherals, i, &mut stack)?;
+        }
+        Ok(())
+    }
+
+    /// Apply `derivedFrom` of all registers, also those inside clusters.
+    ///
+    /// The base register is looked up among the siblings of the derived one first,
+    /// then by dot separated path like in [`Device::find`].
+    /// Fails if the base register doesn't exist or derivation is cyclic.
+    pub fn resolve_derived_registers(&mut self) -> Result<(), SvdError> {
+        use crate::DeriveFrom;
+
+        loop {
+            let mut derived = Vec::new();
+            for (i, p) in self.peripherals.iter().enumerate() {
+                let children = p.registers.as_deref().unwrap_or(&[]);
+                derived_registers(&[i], &p.name, children, &mut derived);
+            }
+            let Some((_, path, base)) = derived.first().cloned() else {
+                return Ok(());
+            };
+            let mut progress = false;
+            // registers are reached by position, as siblings may share a name
+            for (position, path, base) in derived {
+                let (parent, _) = path.rsplit_once('.').unwrap();
+                let sibling = format!("{}.{}", parent, base);
+                let base = match self.find(&sibling).or_else(|| self.find(&base)) {
+                    Some(r) if r.derived_from.is_none() => r.clone(),
+                    Some(_) => continue,
+                    None => return Err(Error::MissingDerivedRegister(path, base).into()),
+                };
+                let (peripheral, position) = position.split_first().unwrap();
+                let children = self.peripherals[*peripheral].registers.as_mut().unwrap();
+                let register = register_at(children, position);
+                *register = register.derive_from(&base);
+                register.derived_from = None;
+                progress = true;
+            }
+            if !progress {
+                return Err(Error::DerivationCycle(path, base).into());
+            }
+        }
+    }
+
+    /// Apply `derivedFrom` of peripherals and then of registers,
+    /// see [`Device::resolve_derived_peripherals`] and [`Device::resolve_derived_registers`].
+    pub fn resolve_derived(&mut self) -> Result<(), SvdError> {
+        self.resolve_derived_peripherals()?;
+        self.resolve_derived_registers()
+    }
+}
+
+/// Collect derived registers in `children` of `parent` at `position`, as their
+/// position, dot separated path and base
+#[cfg(feature = "derive-from")]
+fn derived_registers(
+    position: &[usize],
+    parent: &str,
+    children: &[RegisterCluster],
+    out: &mut Vec<(Vec<usize>, String, String)>,
+) {
+    for (i, rc) in children.iter().enumerate() {
+        let position = [position, &[i]].concat();
+        match rc {
+            RegisterCluster::Register(r) => {
+                if let Some(base) = r.derived_from.as_ref() {
+                    let path = format!("{}.{}", parent, r.effective_name());
+                    out.push((position, path, base.clone()));
+                }
+            }
+            RegisterCluster::Cluster(c) => {
+                let parent = format!("{}.{}", parent, c.name);
+                derived_registers(&position, &parent, &c.children, out)
+            }
+        }
+    }
+}
+
+/// Register at `position` of child indexes, one for each nesting level
+#[cfg(feature = "derive-from")]
+fn register_at<'a>(children: &'a mut [RegisterCluster], position: &[usize]) -> &'a mut Register {
+    match (&mut children[position[0]], &position[1..]) {
+        (RegisterCluster::Register(r), []) => r,
+        (RegisterCluster::Cluster(c), rest) => register_at(&mut c.children, rest),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(feature = "derive-from")]
+fn resolve_derived_peripheral(
+    peripherals: &mut [Peripheral],
+    i: usize,
+    stack: &mut Vec<usize>,
+) -> Result<(), SvdError> {
+    use crate::DeriveFrom;
+
+    let Some(base) = peripherals[i].derived_from.clone() else {
+        return Ok(());
+    };
+    if let Some(start) = stack.iter().position(|&s| s == i) {
+        let mut chain: Vec<&str> = stack[start..]
+            .iter()
+            .map(|&s| peripherals[s].name.as_str())
+            .collect();
+        chain.push(&peripherals[i].name);
+        return Err(peripheral::Error::DeriveCycle(chain.join(" -> ")).into());
+    }
+    let j = peripherals
+        .iter()
+        .position(|p| p.name == base)
+        .ok_or_else(|| peripheral::Error::MissingParent(peripherals[i].name.clone(), base))?;
+    stack.push(i);
+    resolve_derived_peripheral(peripherals, j, stack)?;
+    stack.pop();
+    let mut derived = peripherals[i].derive_from(&peripherals[j]);
+    derived.derived_from = None;
+    peripherals[i] = derived;
+    Ok(())
 }
 
 impl Name for Device {
@@ -440,6 +870,6 @@ impl Name for Device {
 
 impl Description for Device {
     fn description(&self) -> Option<&str> {
-        Some(&self.description)
+        self.description.as_deref()
     }
 }
@@ -2,13 +2,37 @@ use super::{BuildError, EmptyToNone, EnumeratedValue, SvdError, ValidateLevel};
 use std::borrow::Cow;
 use std::ops::RangeInclusive;
 
+/// Errors for [`DimElement::validate`]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// `dimIndex` doesn't give an index for each element
+    #[error("dimIndex must contain {0} indexes, found {1}")]
+    IndexCount(u32, usize),
+    /// `dimIndex` is not a list of indexes and ranges
+    #[error("dimIndex `{0}` is not a list of indexes and ranges")]
+    InvalidIndexes(String),
+    /// Offset of the last element doesn't fit in `u32`
+    #[error("{dim} elements {increment:#x} apart from offset {start:#x} overflow")]
+    OffsetOverflow {
+        /// Offset of the first element
+        start: u32,
+        /// Number of elements
+        dim: u32,
+        /// Distance between elements
+        increment: u32,
+    },
+}
+
+/// Most indexes [`DimElement::parse_indexes`] expands a list into
+pub const MAX_INDEXES: u32 = 0x1_0000;
+
 /// Defines arrays and lists.
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct DimElement {
     /// Defines the number of elements in an array or list
@@ -22,6 +46,7 @@ pub struct DimElement {
     #[cfg_attr(
         feature = "serde",
         serde(
+            default,
             deserialize_with = "ser_de::deserialize_dim_index",
             serialize_with = "ser_de::serialize_dim_index",
             skip_serializing_if = "Option::is_none"
@@ -55,7 +80,7 @@ pub struct DimElement {
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct DimArrayIndex {
     /// Specify the base name of enumerations
     #[cfg_attr(
@@ -140,29 +165,42 @@ impl DimElement {
         DimElementBuilder::default()
     }
 
-    /// Get array of indexes from string
+    /// Get array of indexes from string.
+    ///
+    /// The text is a comma separated list, each entry an index or a range like
+    /// `0-15` or `A-D`. Lists of more than [`MAX_INDEXES`] indexes are rejected.
     pub fn parse_indexes(text: &str) -> Option<Vec<String>> {
-        (if text.contains('-') {
-            let (start, end) = text.split_once('-')?;
-            if let (Ok(start), Ok(end)) = (start.parse::<u32>(), end.parse::<u32>()) {
-                Some((start..=end).map(|i| i.to_string()).collect::<Vec<_>>())
-            } else {
-                let mut start = start.bytes();
-                let mut end = end.bytes();
-                match (start.next(), start.next(), end.next(), end.next()) {
-                    (Some(start), None, Some(end), None)
-                        if (start.is_ascii_lowercase() && end.is_ascii_lowercase())
-                            || (start.is_ascii_uppercase() && end.is_ascii_uppercase()) =>
-                    {
-                        Some((start..=end).map(|c| char::from(c).to_string()).collect())
+        Self::parse_indexes_max(text, MAX_INDEXES).ok()
+    }
+    /// Get array of at most `max` indexes from string.
+    ///
+    /// Ranges are measured before they are expanded, so a list of more than `max`
+    /// indexes fails with [`Error::IndexCount`] without allocating them.
+    pub fn parse_indexes_max(text: &str, max: u32) -> Result<Vec<String>, Error> {
+        let invalid = || Error::InvalidIndexes(text.to_string());
+        let mut indexes = Vec::new();
+        for token in text.split(',').map(str::trim) {
+            // what is left of `max` after the entries before this one
+            let left = max.saturating_sub(indexes.len() as u32);
+            if token.contains('-') {
+                let range = parse_range(token, left).map_err(|e| match e {
+                    Error::IndexCount(_, found) => {
+                        Error::IndexCount(max, indexes.len().saturating_add(found))
                     }
-                    _ => None,
-                }
+                    e => e,
+                })?;
+                indexes.extend(range.ok_or_else(invalid)?);
+            } else if left == 0 {
+                return Err(Error::IndexCount(max, indexes.len() + 1));
+            } else {
+                indexes.push(token.to_string());
             }
+        }
+        if indexes.is_empty() {
+            Err(invalid())
         } else {
-            Some(text.split(',').map(|s| s.to_string()).collect())
-        })
-        .filter(|v| !v.is_empty())
+            Ok(indexes)
+        }
     }
     /// Try to represent [`DimElement`] as range of integer indexes
     pub fn indexes_as_range(&self) -> Option<RangeInclusive<u32>> {
@@ -188,6 +226,28 @@ impl DimElement {
         }
         Some(min..=max)
     }
+    /// Try to represent [`DimElement`] as range of integer indexes, which may be
+    /// zero padded to the same width like `00`, `01`, ..., `15`.
+    ///
+    /// Returns the range and the width of padded indexes, `0` if they are not padded.
+    pub fn indexes_as_padded_range(&self) -> Option<(RangeInclusive<u32>, usize)> {
+        if let Some(range) = self.indexes_as_range() {
+            return Some((range, 0));
+        }
+        let first = self.indexes().next()?;
+        let width = first.len();
+        if width < 2 || !first.starts_with('0') {
+            return None;
+        }
+        let start = first.parse::<u32>().ok()?;
+        let end = start.checked_add(self.dim.checked_sub(1)?)?;
+        let padded = (start..=end).map(|i| format!("{:0width$}", i, width = width));
+        if self.indexes().eq(padded) {
+            Some((start..=end, width))
+        } else {
+            None
+        }
+    }
     /// Modify an existing [`DimElement`] based on a [builder](DimElementBuilder).
     pub fn modify_from(
         &mut self,
@@ -213,15 +273,36 @@ impl DimElement {
     }
     /// Validate the [`DimElement`].
     ///
-    /// # Notes
-    ///
-    /// This doesn't do anything.
-    pub fn validate(&self, _lvl: ValidateLevel) -> Result<(), SvdError> {
-        // TODO
+    /// `dimIndex` must have as many entries as there are elements.
+    pub fn validate(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
+        if !lvl.is_disabled() {
+            if let Some(index) = self.dim_index.as_ref() {
+                if index.len() != self.dim as usize {
+                    return Err(Error::IndexCount(self.dim, index.len()).into());
+                }
+            }
+        }
         Ok(())
     }
+    /// Offset of element `i` of an array starting at `start`, `None` if it overflows
+    pub(crate) fn offset(&self, start: u32, i: u32) -> Option<u32> {
+        i.checked_mul(self.dim_increment)
+            .and_then(|d| start.checked_add(d))
+    }
+    /// Check that offsets of all elements of an array starting at `start` fit in `u32`
+    pub fn check_offsets(&self, start: u32) -> Result<(), Error> {
+        if self.dim == 0 || self.offset(start, self.dim - 1).is_some() {
+            Ok(())
+        } else {
+            Err(Error::OffsetOverflow {
+                start,
+                dim: self.dim,
+                increment: self.dim_increment,
+            })
+        }
+    }
     /// Get the indexes of the array or list.
-    pub fn indexes(&self) -> Indexes {
+    pub fn indexes(&self) -> Indexes<'_> {
         Indexes {
             i: 0,
             dim: self.dim,
@@ -230,6 +311,55 @@ impl DimElement {
     }
 }
 
+/// Indexes of range `start-end` of numbers or of single letters,
+/// `Err` if the range has more than `max` indexes
+fn parse_range(text: &str, max: u32) -> Result<Option<Vec<String>>, Error> {
+    let (start, end) = match text.split_once('-') {
+        Some(range) => range,
+        None => return Ok(None),
+    };
+    // `00-15` keeps zero padding of the start in all indexes
+    let width = if start.len() > 1 && start.starts_with('0') && start.len() == end.len() {
+        start.len()
+    } else {
+        0
+    };
+    let check_len = |start: u64, end: u64| {
+        let len = end
+            .checked_add(1)
+            .map_or(u64::MAX, |e| e.saturating_sub(start));
+        if len > max.into() {
+            Err(Error::IndexCount(
+                max,
+                usize::try_from(len).unwrap_or(usize::MAX),
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    let indexes: Vec<String> =
+        if let (Ok(start), Ok(end)) = (start.parse::<u64>(), end.parse::<u64>()) {
+            check_len(start, end)?;
+            (start..=end)
+                .map(|i| format!("{:0width$}", i, width = width))
+                .collect()
+        } else {
+            let mut start = start.bytes();
+            let mut end = end.bytes();
+            match (start.next(), start.next(), end.next(), end.next()) {
+                (Some(start), None, Some(end), None)
+                    if (start.is_ascii_lowercase() && end.is_ascii_lowercase())
+                        || (start.is_ascii_uppercase() && end.is_ascii_uppercase()) =>
+                {
+                    check_len(start.into(), end.into())?;
+                    (start..=end).map(|c| char::from(c).to_string()).collect()
+                }
+                _ => return Ok(None),
+            }
+        };
+    Ok(Some(indexes).filter(|v| !v.is_empty()))
+}
+
 /// Indexes into a [DimElement]
 pub struct Indexes<'a> {
     i: u32,
@@ -1,8 +1,8 @@
-use super::{BuildError, Description, EmptyToNone, Name, SvdError, ValidateLevel};
+use super::{BuildError, Description, EmptyToNone, Name, NumberStyles, SvdError, ValidateLevel};
 
 /// Describes a single entry in the enumeration.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct EnumeratedValue {
     /// String describing the semantics of the value. Can be displayed instead of the value
@@ -22,12 +22,23 @@ pub struct EnumeratedValue {
     )]
     pub value: Option<u64>,
 
+    /// Mask of don't care bits, written as `x` in the binary form of `value`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub dont_care: Option<u64>,
+
     /// Defines the name and description for all other values that are not listed explicitly
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub is_default: Option<bool>,
+
+    /// How `value` was written
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub number_styles: NumberStyles,
 }
 
 /// Errors for [`EnumeratedValue::validate`]
@@ -42,6 +53,12 @@ pub enum Error {
     /// The value is not in range.
     #[error("Value {0} out of range [{} - {}]", .1.start, .1.end - 1)]
     OutOfRange(u64, core::ops::Range<u64>),
+    /// Don't care mask was specified without value
+    #[error("EnumeratedValue has don't care mask {0:#b} but no `value`")]
+    DontCareWithoutValue(u64),
+    /// Value has bits set in the don't care mask
+    #[error("Value {0:#b} has bits set in don't care mask {1:#b}")]
+    DontCareOverlap(u64, u64),
 }
 
 /// Builder for [`EnumeratedValue`]
@@ -50,7 +67,9 @@ pub struct EnumeratedValueBuilder {
     name: Option<String>,
     description: Option<String>,
     value: Option<u64>,
+    dont_care: Option<u64>,
     is_default: Option<bool>,
+    number_styles: NumberStyles,
 }
 
 impl From<EnumeratedValue> for EnumeratedValueBuilder {
@@ -59,7 +78,9 @@ impl From<EnumeratedValue> for EnumeratedValueBuilder {
             name: Some(e.name),
             description: e.description,
             value: e.value,
+            dont_care: e.dont_care,
             is_default: e.is_default,
+            number_styles: e.number_styles,
         }
     }
 }
@@ -80,12 +101,22 @@ impl EnumeratedValueBuilder {
         self.value = value;
         self
     }
+    /// Set the mask of don't care bits of the enumerated value.
+    pub fn dont_care(mut self, value: Option<u64>) -> Self {
+        self.dont_care = value;
+        self
+    }
     #[allow(clippy::wrong_self_convention)]
     /// Set if the enumerated value is defaulted for non-explicit values.
     pub fn is_default(mut self, value: Option<bool>) -> Self {
         self.is_default = value;
         self
     }
+    /// Set how numbers of the enumerated value are written.
+    pub fn number_styles(mut self, value: NumberStyles) -> Self {
+        self.number_styles = value;
+        self
+    }
     /// Validate and build a [`EnumeratedValue`].
     pub fn build(self, lvl: ValidateLevel) -> Result<EnumeratedValue, SvdError> {
         let ev = EnumeratedValue {
@@ -94,7 +125,9 @@ impl EnumeratedValueBuilder {
                 .ok_or_else(|| BuildError::Uninitialized("name".to_string()))?,
             description: self.description.empty_to_none(),
             value: self.value,
+            dont_care: self.dont_care,
             is_default: self.is_default,
+            number_styles: self.number_styles,
         };
         ev.validate(lvl)?;
         Ok(ev)
@@ -125,6 +158,9 @@ impl EnumeratedValue {
         if builder.value.is_some() {
             self.value = builder.value;
         }
+        if builder.dont_care.is_some() {
+            self.dont_care = builder.dont_care;
+        }
         if builder.is_default.is_some() {
             self.is_default = builder.is_default;
         }
@@ -136,6 +172,13 @@ impl EnumeratedValue {
             if lvl.is_strict() {
                 super::check_name(&self.name, "name")?;
             }
+            match (self.value, self.dont_care) {
+                (None, Some(mask)) => return Err(Error::DontCareWithoutValue(mask).into()),
+                (Some(value), Some(mask)) if value & mask != 0 => {
+                    return Err(Error::DontCareOverlap(value, mask).into())
+                }
+                _ => {}
+            }
             match (self.value.is_some(), self.is_default()) {
                 (false, false) => Err(Error::AbsentValue.into()),
                 (true, true) if lvl.is_strict() => Err(Error::ValueAndDefault(self.value).into()),
@@ -145,10 +188,34 @@ impl EnumeratedValue {
             Ok(())
         }
     }
+    /// Returns `true` if `value` matches this enumerated value, ignoring don't care bits
+    pub fn matches(&self, value: u64) -> bool {
+        match self.value {
+            Some(v) => (v ^ value) & !self.dont_care.unwrap_or(0) == 0,
+            None => false,
+        }
+    }
+    /// Returns `true` if some value matches both enumerated values
+    pub fn overlaps(&self, other: &Self) -> bool {
+        match (self.value, other.value) {
+            (Some(a), Some(b)) => {
+                let mask = self.dont_care.unwrap_or(0) | other.dont_care.unwrap_or(0);
+                (a ^ b) & !mask == 0
+            }
+            _ => self.is_default() && other.is_default(),
+        }
+    }
     pub(crate) fn check_range(&self, range: &core::ops::Range<u64>) -> Result<(), SvdError> {
-        match &self.value {
-            Some(x) if !range.contains(x) => Err(Error::OutOfRange(*x, range.clone()).into()),
-            _ => Ok(()),
+        match self.value {
+            Some(x) => {
+                let x = x | self.dont_care.unwrap_or(0);
+                if range.contains(&x) {
+                    Ok(())
+                } else {
+                    Err(Error::OutOfRange(x, range.clone()).into())
+                }
+            }
+            None => Ok(()),
         }
     }
 }
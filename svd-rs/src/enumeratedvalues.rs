@@ -1,8 +1,12 @@
 use super::{EmptyToNone, EnumeratedValue, SvdError, Usage, ValidateLevel};
+use once_cell::sync::OnceCell;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 
 /// A map describing unsigned integers and their description and name.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct EnumeratedValues {
     /// Identifier for the whole enumeration section
@@ -12,6 +16,13 @@ pub struct EnumeratedValues {
     )]
     pub name: Option<String>,
 
+    /// Name of the enumeration type in the device header file
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub header_enum_name: Option<String>,
+
     /// Usage of the values
     #[cfg_attr(
         feature = "serde",
@@ -28,11 +39,64 @@ pub struct EnumeratedValues {
     pub derived_from: Option<String>,
 
     /// List of variants. The number of required items depends on the bit-width of the associated field.
+    ///
+    /// Lookups by value are cached. Values changed in place here are not seen by
+    /// the cache, change them with [`EnumeratedValues::values_mut`] instead.
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Vec::is_empty")
     )]
     pub values: Vec<EnumeratedValue>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    index: IndexCache,
+}
+
+/// Lazily built positions of values for lookups by value.
+///
+/// The cache is derived from `values`, so all caches compare and hash equal.
+#[derive(Clone, Debug, Default)]
+struct IndexCache(OnceCell<Positions>);
+
+impl PartialEq for IndexCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for IndexCache {}
+
+impl Hash for IndexCache {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+/// Positions of values in document order
+#[derive(Clone, Debug, Default)]
+struct Positions {
+    exact: BTreeMap<u64, usize>,
+    masked: Vec<usize>,
+    default: Option<usize>,
+    len: usize,
+}
+
+impl Positions {
+    fn new(values: &[EnumeratedValue]) -> Self {
+        let mut positions = Self {
+            default: values.iter().position(|e| e.is_default()),
+            len: values.len(),
+            ..Default::default()
+        };
+        for (i, e) in values.iter().enumerate() {
+            match (e.value, e.dont_care) {
+                (Some(_), Some(mask)) if mask != 0 => positions.masked.push(i),
+                (Some(v), _) => {
+                    positions.exact.entry(v).or_insert(i);
+                }
+                _ => {}
+            }
+        }
+        positions
+    }
 }
 
 /// Errors for [`EnumeratedValues::validate`]
@@ -41,12 +105,19 @@ pub enum Error {
     /// Enum is empty
     #[error("EnumeratedValues is empty")]
     Empty,
+    /// Enumerated value with the same name already exists
+    #[error("EnumeratedValue `{0}` already exists")]
+    DuplicateName(String),
+    /// Values matched by two enumerated values overlap
+    #[error("EnumeratedValue `{0}` overlaps with `{1}`")]
+    Overlap(String, String),
 }
 
 /// Builder for [`EnumeratedValues`]
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct EnumeratedValuesBuilder {
     name: Option<String>,
+    header_enum_name: Option<String>,
     usage: Option<Usage>,
     derived_from: Option<String>,
     values: Option<Vec<EnumeratedValue>>,
@@ -56,6 +127,7 @@ impl From<EnumeratedValues> for EnumeratedValuesBuilder {
     fn from(e: EnumeratedValues) -> Self {
         Self {
             name: e.name,
+            header_enum_name: e.header_enum_name,
             usage: e.usage,
             derived_from: e.derived_from,
             values: Some(e.values),
@@ -69,6 +141,11 @@ impl EnumeratedValuesBuilder {
         self.name = value;
         self
     }
+    /// Set the header enum name of the enumerated values
+    pub fn header_enum_name(mut self, value: Option<String>) -> Self {
+        self.header_enum_name = value;
+        self
+    }
     /// Set the usage of the enumerated values
     pub fn usage(mut self, value: Option<Usage>) -> Self {
         self.usage = value;
@@ -88,9 +165,11 @@ impl EnumeratedValuesBuilder {
     pub fn build(self, lvl: ValidateLevel) -> Result<EnumeratedValues, SvdError> {
         let evs = EnumeratedValues {
             name: self.name.empty_to_none(),
+            header_enum_name: self.header_enum_name.empty_to_none(),
             usage: self.usage,
             derived_from: self.derived_from,
             values: self.values.unwrap_or_default(),
+            index: IndexCache::default(),
         };
         evs.validate(lvl)?;
         Ok(evs)
@@ -113,14 +192,19 @@ impl EnumeratedValues {
         builder: EnumeratedValuesBuilder,
         lvl: ValidateLevel,
     ) -> Result<(), SvdError> {
+        self.index = IndexCache::default();
         if builder.derived_from.is_some() {
             self.name = None;
+            self.header_enum_name = None;
             self.usage = None;
             self.values = Vec::new();
         } else {
             if builder.name.is_some() {
                 self.name = builder.name.empty_to_none();
             }
+            if builder.header_enum_name.is_some() {
+                self.header_enum_name = builder.header_enum_name.empty_to_none();
+            }
             if builder.usage.is_some() {
                 self.usage = builder.usage;
             }
@@ -137,6 +221,9 @@ impl EnumeratedValues {
                 if let Some(name) = self.name.as_ref() {
                     super::check_name(name, "name")?;
                 }
+                if let Some(name) = self.header_enum_name.as_ref() {
+                    super::check_name(name, "headerEnumName")?;
+                }
             }
             if let Some(_dname) = self.derived_from.as_ref() {
                 if lvl.is_strict() {
@@ -181,6 +268,96 @@ impl EnumeratedValues {
 
     /// Get mutable `enumeratedValue` by name
     pub fn get_mut_value(&mut self, name: &str) -> Option<&mut EnumeratedValue> {
-        self.values.iter_mut().find(|e| e.name == name)
+        self.values_mut().iter_mut().find(|e| e.name == name)
+    }
+
+    /// Get mutable list of `enumeratedValue`s, dropping the lookup cache
+    pub fn values_mut(&mut self) -> &mut Vec<EnumeratedValue> {
+        self.index = IndexCache::default();
+        &mut self.values
+    }
+
+    /// Get `enumeratedValue` matching `value`, taking don't care bits into account.
+    ///
+    /// Default value is not returned. Lookups use the cached [`EnumeratedValues::index`].
+    pub fn get_by_value(&self, value: u64) -> Option<&EnumeratedValue> {
+        self.index().get(value)
+    }
+
+    /// Returns `enumeratedValue`s ordered by value, then by don't care mask.
+    /// Default value comes last.
+    pub fn values_sorted(&self) -> Vec<&EnumeratedValue> {
+        let mut values: Vec<_> = self.values.iter().collect();
+        values.sort_by_key(|e| (e.value.is_none(), e.value, e.dont_care));
+        values
+    }
+
+    /// Add `enumeratedValue`.
+    ///
+    /// Fails if name is already used or if some value is matched by an existing `enumeratedValue`.
+    pub fn insert(&mut self, value: EnumeratedValue) -> Result<(), SvdError> {
+        if self.get_value(&value.name).is_some() {
+            return Err(Error::DuplicateName(value.name).into());
+        }
+        if let Some(e) = self.values.iter().find(|e| e.overlaps(&value)) {
+            return Err(Error::Overlap(value.name, e.name.clone()).into());
+        }
+        self.values_mut().push(value);
+        Ok(())
+    }
+
+    /// Remove `enumeratedValue` by name
+    pub fn remove(&mut self, name: &str) -> Option<EnumeratedValue> {
+        let i = self.values.iter().position(|e| e.name == name)?;
+        Some(self.values_mut().remove(i))
+    }
+
+    /// Lookup index by value.
+    ///
+    /// The index is built on first use and kept until values are changed through
+    /// `&mut self` methods. It returns the same `enumeratedValue` as a linear search
+    /// would, the first one in document order matching the value.
+    pub fn index(&self) -> ValueIndex<'_> {
+        let positions = self.index.0.get_or_init(|| Positions::new(&self.values));
+        // values added or removed directly in the field
+        let positions = if positions.len == self.values.len() {
+            Cow::Borrowed(positions)
+        } else {
+            Cow::Owned(Positions::new(&self.values))
+        };
+        ValueIndex {
+            values: &self.values,
+            positions,
+        }
+    }
+}
+
+/// Lookup index of [`EnumeratedValues`] by value
+#[derive(Clone, Debug)]
+pub struct ValueIndex<'a> {
+    values: &'a [EnumeratedValue],
+    positions: Cow<'a, Positions>,
+}
+
+impl<'a> ValueIndex<'a> {
+    /// Get `enumeratedValue` matching `value`, taking don't care bits into account
+    pub fn get(&self, value: u64) -> Option<&'a EnumeratedValue> {
+        let exact = self.positions.exact.get(&value).copied();
+        // only values with don't care bits before the exact one can win
+        let before = exact.unwrap_or(usize::MAX);
+        self.positions
+            .masked
+            .iter()
+            .copied()
+            .take_while(|i| *i < before)
+            .find(|i| self.values[*i].matches(value))
+            .or(exact)
+            .map(|i| &self.values[i])
+    }
+
+    /// Get `enumeratedValue` matching `value` or default value if none matches
+    pub fn get_or_default(&self, value: u64) -> Option<&'a EnumeratedValue> {
+        self.get(value)
+            .or_else(|| self.positions.default.map(|i| &self.values[i]))
     }
 }
@@ -12,15 +12,15 @@ pub type Field = MaybeArray<FieldInfo>;
 /// Errors for [`FieldInfo::validate`]
 #[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
 pub enum Error {
-    /// The enumerated value is not recognized by svd-rs.
-    #[error("You can have 0, 1 or 2 enumeratedValues with different usage")]
-    IncompatibleEnumeratedValues,
+    /// Several enumeratedValues of the field have the same usage
+    #[error("Field `{0}` can have 0, 1 or 2 enumeratedValues with different usage")]
+    IncompatibleEnumeratedValues(String),
 }
 
 /// A partition of a [register](crate::RegisterInfo)
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct FieldInfo {
     /// Name string used to identify the field.
@@ -332,7 +332,7 @@ impl FieldInfo {
                     [ev1, ev2]
                         if matches!(ev2.usage(), None | Some(Usage::Read))
                             && matches!(ev1.usage(), None | Some(Usage::Write)) => {}
-                    _ => return Err(Error::IncompatibleEnumeratedValues.into()),
+                    _ => return Err(Error::IncompatibleEnumeratedValues(self.name.clone()).into()),
                 }
             }
 
@@ -401,7 +401,10 @@ impl FieldInfo {
 impl Field {
     /// Validate the [`Field`] recursively
     pub fn validate_all(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
-        if let Self::Array(_, dim) = self {
+        if let Self::Array(info, dim) = self {
+            if !lvl.is_disabled() {
+                super::check_array_name(&info.name, "field")?;
+            }
             dim.validate(lvl)?;
         }
         self.deref().validate_all(lvl)
@@ -0,0 +1,366 @@
+//! Machine-applicable fixes of problems found by validation.
+//!
+//! Some [`Diagnostic`](crate::Diagnostic)s have a [`Fix`] which is the only reasonable
+//! way to correct the problem. It can be applied with [`Device::apply_fix`].
+//!
+//! Elements are addressed by dot separated paths like `PERIPHERAL.CLUSTER.REGISTER.FIELD`
+//! and properties of elements by their SVD tag names like `resetValue`.
+
+use super::{
+    registercluster::child_position, BitRange, Cluster, Device, DimElement, Field, MaybeArray,
+    Peripheral, ProvenanceRecorder, Register, RegisterCluster, RegisterProperties, SvdError,
+};
+
+/// Change of the device which fixes a problem
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fix {
+    /// Set property `element` of element at `path`, like `resetValue`
+    SetText {
+        /// Path of the element
+        path: String,
+        /// Tag name of the property
+        element: String,
+        /// New value as written in SVD
+        new_value: String,
+    },
+    /// Rename element at `path`
+    Rename {
+        /// Path of the element
+        path: String,
+        /// New name
+        new_name: String,
+    },
+    /// Swap most and least significant bits of field at `path`
+    SwapBitRange {
+        /// Path of the field
+        path: String,
+    },
+    /// Remove element at `path`
+    RemoveNode {
+        /// Path of the element
+        path: String,
+    },
+    /// Set property `element` of element at `path` which has no value yet
+    AddMissingElement {
+        /// Path of the element
+        path: String,
+        /// Tag name of the property
+        element: String,
+        /// Value as written in SVD
+        value: String,
+    },
+}
+
+/// Errors from [`Device::apply_fix`]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// No element at path
+    #[error("No element at `{0}`")]
+    PathNotFound(String),
+    /// The element has no such property or it can't be changed
+    #[error("Can't set `{1}` of `{0}`")]
+    UnsupportedElement(String, String),
+    /// Value can't be parsed
+    #[error("Invalid value `{1}` of `{0}`")]
+    InvalidValue(String, String),
+    /// Property already has a value
+    #[error("`{1}` of `{0}` is already set")]
+    AlreadySet(String, String),
+    /// The fix can't be applied to a parsed device
+    #[error("Fix can't be applied to `{0}`: {1}")]
+    Unsupported(String, &'static str),
+}
+
+/// Changeable parts of an element
+struct Properties<'a> {
+    name: &'a mut String,
+    description: &'a mut Option<String>,
+    derived_from: &'a mut Option<String>,
+    dim: Option<&'a mut DimElement>,
+    properties: Option<&'a mut RegisterProperties>,
+    address_offset: Option<&'a mut u32>,
+    base_address: Option<&'a mut u64>,
+    bit_range: Option<&'a mut BitRange>,
+}
+
+fn dim<T>(m: &mut MaybeArray<T>) -> (&mut T, Option<&mut DimElement>) {
+    match m {
+        MaybeArray::Single(info) => (info, None),
+        MaybeArray::Array(info, dim) => (info, Some(dim)),
+    }
+}
+
+impl<'a> Properties<'a> {
+    fn peripheral(p: &'a mut Peripheral) -> Self {
+        let (p, dim) = dim(p);
+        Self {
+            name: &mut p.name,
+            description: &mut p.description,
+            derived_from: &mut p.derived_from,
+            dim,
+            properties: Some(&mut p.default_register_properties),
+            address_offset: None,
+            base_address: Some(&mut p.base_address),
+            bit_range: None,
+        }
+    }
+
+    fn cluster(c: &'a mut Cluster) -> Self {
+        let (c, dim) = dim(c);
+        Self {
+            name: &mut c.name,
+            description: &mut c.description,
+            derived_from: &mut c.derived_from,
+            dim,
+            properties: Some(&mut c.default_register_properties),
+            address_offset: Some(&mut c.address_offset),
+            base_address: None,
+            bit_range: None,
+        }
+    }
+
+    fn register(r: &'a mut Register) -> Self {
+        let (r, dim) = dim(r);
+        Self {
+            name: &mut r.name,
+            description: &mut r.description,
+            derived_from: &mut r.derived_from,
+            dim,
+            properties: Some(&mut r.properties),
+            address_offset: Some(&mut r.address_offset),
+            base_address: None,
+            bit_range: None,
+        }
+    }
+
+    fn field(f: &'a mut Field) -> Self {
+        let (f, dim) = dim(f);
+        Self {
+            name: &mut f.name,
+            description: &mut f.description,
+            derived_from: &mut f.derived_from,
+            dim,
+            properties: None,
+            address_offset: None,
+            base_address: None,
+            bit_range: Some(&mut f.bit_range),
+        }
+    }
+
+    /// Returns `true` if optional property `element` has a value
+    fn is_set(&self, element: &str) -> Option<bool> {
+        let properties = self.properties.as_deref();
+        Some(match element {
+            "description" => self.description.is_some(),
+            "derivedFrom" => self.derived_from.is_some(),
+            "dimIndex" => self.dim.as_deref()?.dim_index.is_some(),
+            "size" => properties?.size.is_some(),
+            "resetValue" => properties?.reset_value.is_some(),
+            "resetMask" => properties?.reset_mask.is_some(),
+            _ => return None,
+        })
+    }
+
+    fn set(&mut self, element: &str, value: &str) -> Result<(), SetError> {
+        use SetError::{Invalid, Unsupported};
+        let number = || parse_number(value).ok_or(Invalid);
+        let small = || u32::try_from(number()?).map_err(|_| Invalid);
+        match element {
+            "name" => *self.name = value.into(),
+            "description" => *self.description = Some(value.into()),
+            "derivedFrom" => *self.derived_from = Some(value.into()),
+            "dim" => self.dim.as_deref_mut().ok_or(Unsupported)?.dim = small()?,
+            "dimIncrement" => self.dim.as_deref_mut().ok_or(Unsupported)?.dim_increment = small()?,
+            "dimIndex" => {
+                self.dim.as_deref_mut().ok_or(Unsupported)?.dim_index =
+                    Some(DimElement::parse_indexes(value).ok_or(Invalid)?)
+            }
+            "size" => self.properties.as_deref_mut().ok_or(Unsupported)?.size = Some(small()?),
+            "resetValue" => {
+                self.properties
+                    .as_deref_mut()
+                    .ok_or(Unsupported)?
+                    .reset_value = Some(number()?)
+            }
+            "resetMask" => {
+                self.properties
+                    .as_deref_mut()
+                    .ok_or(Unsupported)?
+                    .reset_mask = Some(number()?)
+            }
+            "addressOffset" => *self.address_offset.as_deref_mut().ok_or(Unsupported)? = small()?,
+            "baseAddress" => *self.base_address.as_deref_mut().ok_or(Unsupported)? = number()?,
+            "bitOffset" => self.bit_range.as_deref_mut().ok_or(Unsupported)?.offset = small()?,
+            "bitWidth" => self.bit_range.as_deref_mut().ok_or(Unsupported)?.width = small()?,
+            _ => return Err(Unsupported),
+        }
+        Ok(())
+    }
+}
+
+enum SetError {
+    Unsupported,
+    Invalid,
+}
+
+/// Parse decimal or `0x` prefixed hexadecimal number
+fn parse_number(text: &str) -> Option<u64> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Find element at `path` and call `f` with its properties
+fn with_element<R>(
+    device: &mut Device,
+    path: &str,
+    f: impl FnOnce(Properties) -> Result<R, Error>,
+) -> Result<R, Error> {
+    let not_found = || Error::PathNotFound(path.into());
+    let mut segments = path.split('.');
+    let peripheral = device
+        .get_mut_peripheral(segments.next().ok_or_else(not_found)?)
+        .ok_or_else(not_found)?;
+    let mut segment = match segments.next() {
+        Some(segment) => segment,
+        None => return f(Properties::peripheral(peripheral)),
+    };
+    let mut children = peripheral.registers.as_mut().ok_or_else(not_found)?;
+    loop {
+        let i = child_position(children.iter(), segment).ok_or_else(not_found)?;
+        let child = &mut children[i];
+        let next = segments.next();
+        match (child, next) {
+            (RegisterCluster::Cluster(c), None) => return f(Properties::cluster(c)),
+            (RegisterCluster::Cluster(c), Some(next)) => {
+                children = &mut c.children;
+                segment = next;
+            }
+            (RegisterCluster::Register(r), None) => return f(Properties::register(r)),
+            (RegisterCluster::Register(r), Some(field)) => {
+                if segments.next().is_some() {
+                    return Err(not_found());
+                }
+                let field = r.get_mut_field(field).ok_or_else(not_found)?;
+                return f(Properties::field(field));
+            }
+        }
+    }
+}
+
+/// Remove element at `path`
+fn remove(device: &mut Device, path: &str) -> Result<(), Error> {
+    let not_found = || Error::PathNotFound(path.into());
+    let (parent, name) = match path.rsplit_once('.') {
+        Some(split) => split,
+        None => {
+            let len = device.peripherals.len();
+            device.peripherals.retain(|p| p.name != path);
+            return (device.peripherals.len() < len)
+                .then_some(())
+                .ok_or_else(not_found);
+        }
+    };
+    let mut segments = parent.split('.');
+    let peripheral = device
+        .get_mut_peripheral(segments.next().unwrap())
+        .ok_or_else(not_found)?;
+    let mut children = match &mut peripheral.registers {
+        Some(children) => children,
+        None => return Err(not_found()),
+    };
+    for segment in segments {
+        let i = child_position(children.iter(), segment).ok_or_else(not_found)?;
+        match &mut children[i] {
+            RegisterCluster::Cluster(c) => children = &mut c.children,
+            RegisterCluster::Register(r) => {
+                // field of the register
+                let fields = r.fields.as_mut().ok_or_else(not_found)?;
+                let len = fields.len();
+                fields.retain(|f| f.name != name);
+                return (fields.len() < len).then_some(()).ok_or_else(not_found);
+            }
+        }
+    }
+    let i = child_position(children.iter(), name).ok_or_else(not_found)?;
+    children.remove(i);
+    Ok(())
+}
+
+/// Name of the pass in [`ProvenanceRecorder`]
+const PASS: &str = "apply_fix";
+
+impl Device {
+    /// Apply `fix` suggested by validation
+    pub fn apply_fix(&mut self, fix: &Fix) -> Result<(), SvdError> {
+        let mut provenance = core::mem::take(&mut self.provenance);
+        let result = apply(self, fix, &mut provenance);
+        self.provenance = provenance;
+        result
+    }
+}
+
+/// Apply `fix` and record it in `provenance`
+fn apply(
+    device: &mut Device,
+    fix: &Fix,
+    provenance: &mut ProvenanceRecorder,
+) -> Result<(), SvdError> {
+    let (path, detail) = match fix {
+        Fix::SetText {
+            path,
+            element,
+            new_value,
+        } => {
+            set(device, path, element, new_value)?;
+            (path, format!("set `{}` to `{}`", element, new_value))
+        }
+        Fix::AddMissingElement {
+            path,
+            element,
+            value,
+        } => {
+            with_element(device, path, |p| match p.is_set(element) {
+                Some(false) => Ok(()),
+                Some(true) => Err(Error::AlreadySet(path.clone(), element.clone())),
+                None => Err(Error::UnsupportedElement(path.clone(), element.clone())),
+            })?;
+            set(device, path, element, value)?;
+            (path, format!("added `{}` `{}`", element, value))
+        }
+        Fix::Rename { path, new_name } => {
+            set(device, path, "name", new_name)?;
+            let renamed = match path.rsplit_once('.') {
+                Some((parent, _)) => format!("{}.{}", parent, new_name),
+                None => new_name.clone(),
+            };
+            provenance.rekey(path, core::slice::from_ref(&renamed));
+            provenance.record(PASS, || (renamed, format!("renamed from `{}`", path)));
+            return Ok(());
+        }
+        Fix::RemoveNode { path } => {
+            remove(device, path)?;
+            (path, "removed".into())
+        }
+        Fix::SwapBitRange { path } => {
+            return Err(Error::Unsupported(
+                path.clone(),
+                "bit ranges of parsed fields are always ordered",
+            )
+            .into())
+        }
+    };
+    provenance.record(PASS, || (path.clone(), detail));
+    Ok(())
+}
+
+fn set(device: &mut Device, path: &str, element: &str, value: &str) -> Result<(), SvdError> {
+    Ok(with_element(device, path, |mut p| {
+        p.set(element, value).map_err(|e| match e {
+            SetError::Invalid => Error::InvalidValue(element.into(), value.into()),
+            SetError::Unsupported => Error::UnsupportedElement(path.into(), element.into()),
+        })
+    })?)
+}
@@ -0,0 +1,226 @@
+//! Register layouts described directly in Rust.
+//!
+//! [`RegisterDef`] and [`FieldDef`] are `const` constructible, so small register
+//! descriptions can be placed in `static` tables without parsing any XML:
+//!
+//! ```
+//! use svd_rs::layout::{FieldDef, RegisterDef};
+//!
+//! static CTRL: RegisterDef = RegisterDef::new(
+//!     "CTRL",
+//!     0x0,
+//!     16,
+//!     &[FieldDef::new("EN", 0, 1), FieldDef::new("MODE", 4, 4)],
+//! );
+//!
+//! let values = CTRL.decode(0x51).unwrap();
+//! assert_eq!(values, [("EN", 1), ("MODE", 5)]);
+//! ```
+//!
+//! Use [`RegisterInfo::from`] to get the full model, for example to run
+//! [`packing`](RegisterInfo::packing) statistics.
+
+use super::{BitRange, FieldInfo, RegisterInfo, ValidateLevel};
+
+/// Errors of register layouts
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Field has bits above the register size
+    #[error("Field `{0}` ends at bit {1} which is outside of {2}-bit register")]
+    FieldOutsideRegister(String, u32, u32),
+    /// Fields share bits
+    #[error("Fields `{0}` and `{1}` overlap")]
+    Overlap(String, String),
+    /// Field has no bits
+    #[error("Field `{0}` has zero width")]
+    ZeroWidth(String),
+}
+
+/// Field of a [`RegisterDef`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldDef {
+    /// Field name
+    pub name: &'static str,
+    /// Bits of the field
+    pub bits: BitRange,
+}
+
+impl FieldDef {
+    /// Field of `width` bits starting at bit `offset`
+    pub const fn new(name: &'static str, offset: u32, width: u32) -> Self {
+        Self {
+            name,
+            bits: BitRange::from_offset_width(offset, width),
+        }
+    }
+}
+
+/// Register with a fixed list of fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterDef {
+    /// Register name
+    pub name: &'static str,
+    /// Offset in bytes from the peripheral base address
+    pub address_offset: u32,
+    /// Size in bits
+    pub size: u32,
+    /// Fields
+    pub fields: &'static [FieldDef],
+}
+
+impl RegisterDef {
+    /// Register of `size` bits at `address_offset`
+    pub const fn new(
+        name: &'static str,
+        address_offset: u32,
+        size: u32,
+        fields: &'static [FieldDef],
+    ) -> Self {
+        Self {
+            name,
+            address_offset,
+            size,
+            fields,
+        }
+    }
+
+    /// Check that fields fit in the register and don't overlap
+    pub fn validate(&self) -> Result<(), Error> {
+        for (i, f) in self.fields.iter().enumerate() {
+            check_fits(f.name, f.bits, self.size)?;
+            if let Some(other) = self.fields[..i].iter().find(|o| overlap(o.bits, f.bits)) {
+                return Err(Error::Overlap(other.name.into(), f.name.into()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract values of all fields from register `value`
+    pub fn decode(&self, value: u64) -> Result<Vec<(&'static str, u64)>, Error> {
+        self.fields
+            .iter()
+            .map(|f| Ok((f.name, extract(value, f.bits, self.size, f.name)?)))
+            .collect()
+    }
+
+    /// Combine field values into register value. Missing fields are 0.
+    ///
+    /// Values wider than their fields are truncated.
+    pub fn encode(&self, values: &[(&str, u64)]) -> Result<u64, Error> {
+        let mut value = 0;
+        for f in self.fields {
+            check_fits(f.name, f.bits, self.size)?;
+            if let Some((_, v)) = values.iter().find(|(name, _)| *name == f.name) {
+                value |= (v & mask(f.bits.width)) << f.bits.offset;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Ranges of bits not covered by fields, from the lowest
+    pub fn reserved(&self) -> Vec<BitRange> {
+        let mut reserved = Vec::new();
+        let mut start = None;
+        for bit in 0..=self.size {
+            let free = bit < self.size && !self.fields.iter().any(|f| covers(f.bits, bit));
+            match (free, start) {
+                (true, None) => start = Some(bit),
+                (false, Some(offset)) => {
+                    reserved.push(BitRange::from_offset_width(offset, bit - offset));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        reserved
+    }
+
+    /// Render fields and reserved ranges from the most significant bit, one per line:
+    ///
+    /// ```text
+    /// [15:8]  -
+    /// [7:4]   MODE
+    /// [3:1]   -
+    /// [0:0]   EN
+    /// ```
+    pub fn render(&self) -> String {
+        let mut rows: Vec<(BitRange, &str)> = self
+            .fields
+            .iter()
+            .map(|f| (f.bits, f.name))
+            .chain(self.reserved().into_iter().map(|r| (r, "-")))
+            .collect();
+        rows.sort_by_key(|(bits, _)| core::cmp::Reverse(bits.offset));
+        let width = rows
+            .iter()
+            .map(|(bits, _)| bits.bit_range().len())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        for (bits, name) in rows {
+            out.push_str(&format!(
+                "{:width$}  {}\n",
+                bits.bit_range(),
+                name,
+                width = width
+            ));
+        }
+        out
+    }
+}
+
+impl From<&RegisterDef> for RegisterInfo {
+    fn from(def: &RegisterDef) -> Self {
+        let fields = def
+            .fields
+            .iter()
+            .map(|f| {
+                FieldInfo::builder()
+                    .name(f.name.into())
+                    .bit_range(f.bits)
+                    .build(ValidateLevel::Disabled)
+                    .expect("name and bit range are set")
+                    .single()
+            })
+            .collect();
+        RegisterInfo::builder()
+            .name(def.name.into())
+            .address_offset(def.address_offset)
+            .size(Some(def.size))
+            .fields(Some(fields))
+            .build(ValidateLevel::Disabled)
+            .expect("name and address offset are set")
+    }
+}
+
+/// Extract value of field `name` with `bits` from `value` of `size`-bit register
+pub fn extract(value: u64, bits: BitRange, size: u32, name: &str) -> Result<u64, Error> {
+    check_fits(name, bits, size)?;
+    Ok((value >> bits.offset) & mask(bits.width))
+}
+
+fn check_fits(name: &str, bits: BitRange, size: u32) -> Result<(), Error> {
+    if bits.width == 0 {
+        return Err(Error::ZeroWidth(name.into()));
+    }
+    if bits.msb() >= size.min(64) {
+        return Err(Error::FieldOutsideRegister(name.into(), bits.msb(), size));
+    }
+    Ok(())
+}
+
+fn mask(width: u32) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+fn covers(bits: BitRange, bit: u32) -> bool {
+    bits.width > 0 && bits.lsb() <= bit && bit <= bits.msb()
+}
+
+fn overlap(a: BitRange, b: BitRange) -> bool {
+    a.lsb() <= b.msb() && b.lsb() <= a.msb()
+}
@@ -12,7 +12,11 @@ pub use self::endian::Endian;
 
 /// Cpu objects
 pub mod cpu;
-pub use self::cpu::{Cpu, CpuBuilder};
+pub use self::cpu::{CortexArch, Cpu, CpuBuilder, CpuRevision};
+
+/// Security Attribution Unit objects
+pub mod sauregion;
+pub use self::sauregion::{SauAccess, SauRegion, SauRegionBuilder, SauRegionsConfig};
 
 /// Interrupt objects
 pub mod interrupt;
@@ -46,9 +50,13 @@ pub use self::enumeratedvalues::{EnumeratedValues, EnumeratedValuesBuilder};
 pub mod field;
 pub use self::field::{Field, FieldInfo, FieldInfoBuilder};
 
+/// Textual form of numbers
+pub mod numberstyle;
+pub use self::numberstyle::{NumberStyle, NumberStyles, Radix};
+
 /// Register Properties objects
 pub mod registerproperties;
-pub use self::registerproperties::RegisterProperties;
+pub use self::registerproperties::{EffectiveProperties, RegisterProperties};
 
 /// Address Block objects
 pub mod addressblock;
@@ -60,7 +68,7 @@ pub use self::cluster::{Cluster, ClusterInfo, ClusterInfoBuilder};
 
 /// Register objects
 pub mod register;
-pub use self::register::{Register, RegisterInfo, RegisterInfoBuilder};
+pub use self::register::{Register, RegisterInfo, RegisterInfoBuilder, SizeAdjustment};
 
 /// Register Cluster objects
 pub mod registercluster;
@@ -76,7 +84,66 @@ pub use self::peripheral::{Peripheral, PeripheralInfo, PeripheralInfoBuilder};
 
 /// Device objects
 pub mod device;
-pub use self::device::{Device, DeviceBuilder};
+pub use self::device::{Device, DeviceBuilder, InterruptEntry, InterruptTableConfig};
+
+/// Device-wide validation
+pub mod validation;
+pub use self::validation::{
+    BuilderConfig, BuilderProfile, Diagnostic, RuleSeverity, Severity, Suppression,
+    ValidationConfig,
+};
+
+/// Registers accessible with several widths
+pub mod widthalias;
+pub use self::widthalias::{WidthAliasGroup, WidthAliasOptions};
+
+/// Register offsets relative to clusters
+pub mod clusteroffset;
+pub use self::clusteroffset::Suspicion;
+
+/// History of changes made by passes
+pub mod provenance;
+pub use self::provenance::{ProvenanceEntry, ProvenanceRecorder};
+
+/// Machine-applicable fixes of validation problems
+pub mod fix;
+pub use self::fix::Fix;
+
+/// Names with `%s` placeholder
+pub mod template;
+pub use self::template::{ArrayStyle, NameTemplate};
+
+/// Naming of identifiers in generated code
+pub mod naming;
+pub use self::naming::{NameMangler, NamingContext};
+
+/// Guessing operating modes of peripherals
+pub mod modes;
+pub use self::modes::{ModeCandidate, ModeHeuristics};
+
+/// Field packing statistics
+pub mod packing;
+pub use self::packing::{PackingInfo, PackingSummary};
+
+/// Lookup by path
+pub mod path;
+pub use self::path::PathElement;
+
+/// Absolute addresses of registers
+pub mod addressmap;
+pub use self::addressmap::{AddressEntry, RegisterMatch};
+
+/// Layout of the Python `cmsis-svd` package
+#[cfg(feature = "serde")]
+pub mod cmsis_svd;
+#[cfg(feature = "serde")]
+pub use self::cmsis_svd::CmsisDevice;
+
+/// Superseded APIs kept for compatibility
+pub mod compat;
+
+/// Register layouts described in Rust
+pub mod layout;
 
 /// Modified Write Values objects
 pub mod modifiedwritevalues;
@@ -101,22 +168,21 @@ pub mod riscv;
 pub use self::riscv::Riscv;
 
 /// Level of validation
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///
+/// `build` of builders runs the `validate` method of the built node with this level,
+/// which only looks at the node itself. To run rules of [`Device::validate_with`]
+/// on registers and clusters as they are built, see [`BuilderProfile`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ValidateLevel {
     /// No validation.
     Disabled,
     /// Weak validation.
+    #[default]
     Weak,
     /// Strict validation.
     Strict,
 }
 
-impl Default for ValidateLevel {
-    fn default() -> Self {
-        ValidateLevel::Weak
-    }
-}
-
 impl ValidateLevel {
     /// Returns true if validation is disabled.
     pub fn is_disabled(self) -> bool {
@@ -179,6 +245,21 @@ pub enum SvdError {
     /// WriteConstraint error
     #[error("`WriteConstraint error: {0}")]
     WriteConstraint(#[from] writeconstraint::Error),
+    /// DimElement error
+    #[error("`DimElement error: {0}")]
+    DimElement(#[from] dimelement::Error),
+    /// Register layout error
+    #[error("`Layout error: {0}")]
+    Layout(#[from] layout::Error),
+    /// Name template error
+    #[error("`Name template error: {0}")]
+    Template(#[from] template::Error),
+    /// Fix application error
+    #[error("`Fix error: {0}")]
+    Fix(#[from] fix::Error),
+    /// Validation error of a builder
+    #[error("`Validation error: {0}")]
+    Validation(#[from] validation::Error),
 }
 
 /// Errors from a builder
@@ -195,6 +276,9 @@ pub enum NameError {
     /// Name is invalid
     #[error("Name `{0}` contains unexpected symbol")]
     Invalid(String, String),
+    /// Name of an array has no `%s` placeholder
+    #[error("Name `{0}` in tag `{1}` is missing a %s placeholder")]
+    MissingPlaceholder(String, String),
 }
 
 pub(crate) fn check_name(name: &str, tag: &str) -> Result<(), NameError> {
@@ -217,6 +301,17 @@ pub(crate) fn check_dimable_name(name: &str, tag: &str) -> Result<(), NameError>
     }
 }
 
+pub(crate) fn check_array_name(name: &str, tag: &str) -> Result<(), NameError> {
+    if template::has_placeholder(name) {
+        Ok(())
+    } else {
+        Err(NameError::MissingPlaceholder(
+            name.to_string(),
+            tag.to_string(),
+        ))
+    }
+}
+
 pub(crate) fn check_derived_name(name: &str, tag: &str) -> Result<(), NameError> {
     for x in name.split('.') {
         check_dimable_name(x, tag)?
@@ -0,0 +1,188 @@
+//! Guessing operating modes of peripherals.
+//!
+//! SVD has no notion of modes, but they often can be recognized: a field selects
+//! the mode with enumerated values named like the `alternateGroup`s of registers
+//! which change meaning in this mode, or descriptions of other registers mention
+//! enumerated values of a `MODE`-like field. The analysis is a heuristic and
+//! every candidate has a confidence score.
+
+use super::{PeripheralInfo, Register};
+
+/// Rules of [`PeripheralInfo::mode_candidates`].
+///
+/// Default rules only match enumerated value names with `alternateGroup`s exactly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ModeHeuristics {
+    /// Match enumerated value names with `alternateGroup`s ignoring case
+    pub ignore_case: bool,
+    /// Look for enumerated value names in descriptions of other registers
+    pub match_descriptions: bool,
+    /// Parts of names of fields checked with `match_descriptions`
+    pub name_patterns: Vec<String>,
+    /// Minimal number of enumerated values matched to report a candidate
+    pub min_values: usize,
+}
+
+impl Default for ModeHeuristics {
+    fn default() -> Self {
+        Self {
+            ignore_case: false,
+            match_descriptions: false,
+            name_patterns: vec!["MODE".into(), "SEL".into(), "CFG".into()],
+            min_values: 2,
+        }
+    }
+}
+
+impl ModeHeuristics {
+    /// Match enumerated value names with `alternateGroup`s ignoring case
+    pub fn ignore_case(mut self, val: bool) -> Self {
+        self.ignore_case = val;
+        self
+    }
+
+    /// Look for enumerated value names in descriptions of other registers
+    pub fn match_descriptions(mut self, val: bool) -> Self {
+        self.match_descriptions = val;
+        self
+    }
+
+    /// Set parts of names of fields checked with `match_descriptions`
+    pub fn name_patterns(mut self, val: Vec<String>) -> Self {
+        self.name_patterns = val;
+        self
+    }
+
+    /// Set minimal number of enumerated values matched to report a candidate
+    pub fn min_values(mut self, val: usize) -> Self {
+        self.min_values = val;
+        self
+    }
+}
+
+/// Field which probably selects operating mode of a peripheral
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ModeCandidate {
+    /// Dot separated path of the field, like `PERIPHERAL.REGISTER.FIELD`
+    pub field: String,
+    /// Enumerated value names with the `alternateGroup` they select,
+    /// or the register which mentions them in description
+    pub mapping: Vec<(String, String)>,
+    /// From 0 to 1, part of enumerated values matched lowered for weaker rules
+    pub confidence: f32,
+}
+
+impl PeripheralInfo {
+    /// Find fields which probably select operating mode of the peripheral,
+    /// most confident first.
+    pub fn mode_candidates(&self, heuristics: &ModeHeuristics) -> Vec<ModeCandidate> {
+        let mut registers = Vec::new();
+        crate::validation::for_each_register(
+            self.registers.as_deref().unwrap_or(&[]),
+            &self.name,
+            0,
+            None,
+            &mut Vec::new(),
+            &mut |path, r, _, _, _| registers.push((format!("{}.{}", path, r.name), r)),
+        );
+        let groups: Vec<&str> = registers
+            .iter()
+            .filter_map(|(_, r)| r.alternate_group.as_deref())
+            .collect();
+
+        let mut candidates = Vec::new();
+        for (path, r) in &registers {
+            for f in r.fields() {
+                let names: Vec<&str> = f
+                    .enumerated_values
+                    .iter()
+                    .flat_map(|ev| &ev.values)
+                    .filter(|v| v.is_default != Some(true))
+                    .map(|v| v.name.as_str())
+                    .collect();
+                let field = format!("{}.{}", path, f.name);
+                if let Some(candidate) = by_groups(&field, &names, &groups, heuristics)
+                    .or_else(|| by_descriptions(&field, &f.name, r, &names, &registers, heuristics))
+                {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        candidates
+    }
+}
+
+fn by_groups(
+    field: &str,
+    names: &[&str],
+    groups: &[&str],
+    heuristics: &ModeHeuristics,
+) -> Option<ModeCandidate> {
+    let mut mapping = Vec::new();
+    let mut exact = true;
+    for name in names {
+        if let Some(group) = groups.iter().find(|g| *g == name) {
+            mapping.push((name.to_string(), group.to_string()));
+        } else if let Some(group) = groups
+            .iter()
+            .find(|g| heuristics.ignore_case && g.eq_ignore_ascii_case(name))
+        {
+            exact = false;
+            mapping.push((name.to_string(), group.to_string()));
+        }
+    }
+    let weight = if exact { 1. } else { 0.8 };
+    candidate(field, names, mapping, weight, heuristics)
+}
+
+fn by_descriptions(
+    field: &str,
+    field_name: &str,
+    owner: &Register,
+    names: &[&str],
+    registers: &[(String, &Register)],
+    heuristics: &ModeHeuristics,
+) -> Option<ModeCandidate> {
+    if !heuristics.match_descriptions
+        || !heuristics
+            .name_patterns
+            .iter()
+            .any(|p| field_name.contains(p.as_str()))
+    {
+        return None;
+    }
+    let mut mapping = Vec::new();
+    for name in names {
+        let mentioned = registers.iter().find(|(_, r)| {
+            r.name != owner.name
+                && r.description.as_deref().map_or(false, |d| {
+                    d.split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                        .any(|word| word == *name)
+                })
+        });
+        if let Some((_, r)) = mentioned {
+            mapping.push((name.to_string(), r.name.clone()));
+        }
+    }
+    candidate(field, names, mapping, 0.5, heuristics)
+}
+
+fn candidate(
+    field: &str,
+    names: &[&str],
+    mapping: Vec<(String, String)>,
+    weight: f32,
+    heuristics: &ModeHeuristics,
+) -> Option<ModeCandidate> {
+    if mapping.is_empty() || mapping.len() < heuristics.min_values {
+        return None;
+    }
+    Some(ModeCandidate {
+        field: field.into(),
+        confidence: weight * mapping.len() as f32 / names.len() as f32,
+        mapping,
+    })
+}
@@ -0,0 +1,280 @@
+//! Naming of identifiers in generated code.
+//!
+//! Different ecosystems use different conventions for generated names, so code
+//! generators take a [`NameMangler`]. Three presets are provided:
+//!
+//! | Preset | Register | Field mask | Field position |
+//! |--------|----------|------------|----------------|
+//! | [`CmsisClassic`] | `TIMER_CR` | `TIMER_CR_EN_Msk` | `TIMER_CR_EN_Pos` |
+//! | [`RustConst`] | `TIMER_CR` | `TIMER_CR_EN_MASK` | `TIMER_CR_EN_OFFSET` |
+//! | [`LowerSnake`] | `timer_cr` | `timer_cr_en_mask` | `timer_cr_en_pos` |
+
+use std::collections::BTreeMap;
+
+/// Element of the path being named, which can be an element of an array
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scope {
+    /// The peripheral
+    Peripheral,
+    /// Enclosing cluster at this depth, outermost is `0`
+    Cluster(usize),
+    /// The register
+    Register,
+    /// The field
+    Field,
+}
+
+/// Everything known about the element being named
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NamingContext {
+    /// Sanitized peripheral name
+    pub peripheral: String,
+    /// Sanitized names of enclosing clusters, outermost first
+    pub clusters: Vec<String>,
+    /// Sanitized register name
+    pub register: Option<String>,
+    /// Sanitized field name
+    pub field: Option<String>,
+    /// Sanitized enumerated value name
+    pub enum_value: Option<String>,
+    /// Indexes of array elements by scope, already substituted in sanitized names.
+    /// Outer scopes come first
+    pub indexes: BTreeMap<Scope, String>,
+    /// `prependToName` of the peripheral
+    pub prepend_to_name: Option<String>,
+    /// `appendToName` of the peripheral
+    pub append_to_name: Option<String>,
+    /// `headerDefinitionsPrefix` of the device
+    pub header_definitions_prefix: Option<String>,
+    /// Index for the next named element
+    next_index: Option<String>,
+}
+
+impl NamingContext {
+    /// Context of peripheral `name`, with `index` if it is an element of peripheral array
+    pub fn new(peripheral: &str, index: Option<&str>) -> Self {
+        let mut ctx = Self {
+            next_index: index.map(String::from),
+            ..Default::default()
+        };
+        ctx.peripheral = ctx.sanitize(Scope::Peripheral, peripheral);
+        ctx
+    }
+
+    /// Set index of array element. Set it before the name of the array,
+    /// it applies to that element only
+    pub fn index(mut self, index: impl Into<String>) -> Self {
+        self.next_index = Some(index.into());
+        self
+    }
+
+    /// Index of element in `scope`, if it is an element of an array
+    pub fn index_of(&self, scope: Scope) -> Option<&str> {
+        self.indexes.get(&scope).map(String::as_str)
+    }
+
+    /// Sanitize `name` of element in `scope` with the index set for it
+    fn sanitize(&mut self, scope: Scope, name: &str) -> String {
+        let index = self.next_index.take();
+        let name = sanitize(name, index.as_deref());
+        if let Some(index) = index {
+            self.indexes.insert(scope, index);
+        }
+        name
+    }
+
+    /// Add enclosing cluster
+    pub fn cluster(mut self, name: &str) -> Self {
+        let name = self.sanitize(Scope::Cluster(self.clusters.len()), name);
+        self.clusters.push(name);
+        self
+    }
+
+    /// Set register name
+    pub fn register(mut self, name: &str) -> Self {
+        self.register = Some(self.sanitize(Scope::Register, name));
+        self
+    }
+
+    /// Set register name from `register`.
+    ///
+    /// The [effective name](crate::RegisterInfo::effective_name) is used, so registers
+    /// of different alternate groups sharing a name get different identifiers.
+    pub fn register_info(self, register: &crate::RegisterInfo) -> Self {
+        self.register(&register.effective_name())
+    }
+
+    /// Set field name
+    pub fn field(mut self, name: &str) -> Self {
+        self.field = Some(self.sanitize(Scope::Field, name));
+        self
+    }
+
+    /// Set enumerated value name
+    pub fn enum_value(mut self, name: &str) -> Self {
+        self.enum_value = Some(sanitize(name, None));
+        self
+    }
+
+    /// Set `prependToName` and `appendToName` of the peripheral
+    pub fn affixes(mut self, prepend: Option<&str>, append: Option<&str>) -> Self {
+        self.prepend_to_name = prepend.map(String::from);
+        self.append_to_name = append.map(String::from);
+        self
+    }
+
+    /// Set `headerDefinitionsPrefix` of the device
+    pub fn header_definitions_prefix(mut self, prefix: Option<&str>) -> Self {
+        self.header_definitions_prefix = prefix.map(String::from);
+        self
+    }
+
+    /// Peripheral, clusters and register (with prepended and appended parts) joined by `_`
+    pub fn register_path(&self) -> String {
+        let mut parts = vec![self.peripheral.clone()];
+        parts.extend(self.clusters.iter().cloned());
+        if let Some(register) = &self.register {
+            parts.push(format!(
+                "{}{}{}",
+                self.prepend_to_name.as_deref().unwrap_or_default(),
+                register,
+                self.append_to_name.as_deref().unwrap_or_default()
+            ));
+        }
+        parts.join("_")
+    }
+
+    /// [`register_path`](Self::register_path) with field
+    pub fn field_path(&self) -> String {
+        match &self.field {
+            Some(field) => format!("{}_{}", self.register_path(), field),
+            None => self.register_path(),
+        }
+    }
+}
+
+/// Make a valid identifier from SVD name.
+///
+/// `%s` is replaced with `index` or removed, other characters which are not
+/// alphanumeric are replaced with `_`. Names starting with a digit get a `_` prefix.
+pub fn sanitize(name: &str, index: Option<&str>) -> String {
+    let name = crate::template::substitute(name, index.unwrap_or_default());
+    let prefix = name
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then_some('_');
+    prefix
+        .into_iter()
+        .chain(
+            name.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }),
+        )
+        .collect()
+}
+
+/// Naming convention of generated identifiers
+pub trait NameMangler {
+    /// Name of peripheral instance
+    fn peripheral(&self, ctx: &NamingContext) -> String;
+    /// Name of register
+    fn register(&self, ctx: &NamingContext) -> String;
+    /// Name of field mask
+    fn field_mask(&self, ctx: &NamingContext) -> String;
+    /// Name of field bit position
+    fn field_pos(&self, ctx: &NamingContext) -> String;
+    /// Name of enumerated value of field
+    fn enum_value(&self, ctx: &NamingContext) -> String;
+}
+
+/// Names of CMSIS device headers, like `TIMER_CR_EN_Msk`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CmsisClassic;
+
+impl CmsisClassic {
+    fn prefixed(ctx: &NamingContext, name: String) -> String {
+        format!(
+            "{}{}",
+            ctx.header_definitions_prefix.as_deref().unwrap_or_default(),
+            name
+        )
+    }
+}
+
+impl NameMangler for CmsisClassic {
+    fn peripheral(&self, ctx: &NamingContext) -> String {
+        Self::prefixed(ctx, ctx.peripheral.clone())
+    }
+    fn register(&self, ctx: &NamingContext) -> String {
+        Self::prefixed(ctx, ctx.register_path())
+    }
+    fn field_mask(&self, ctx: &NamingContext) -> String {
+        Self::prefixed(ctx, format!("{}_Msk", ctx.field_path()))
+    }
+    fn field_pos(&self, ctx: &NamingContext) -> String {
+        Self::prefixed(ctx, format!("{}_Pos", ctx.field_path()))
+    }
+    fn enum_value(&self, ctx: &NamingContext) -> String {
+        let value = ctx.enum_value.as_deref().unwrap_or_default();
+        Self::prefixed(ctx, format!("{}_{}", ctx.field_path(), value))
+    }
+}
+
+/// Names of Rust constants, like `TIMER_CR_EN_MASK`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RustConst;
+
+impl NameMangler for RustConst {
+    fn peripheral(&self, ctx: &NamingContext) -> String {
+        constant(&ctx.peripheral)
+    }
+    fn register(&self, ctx: &NamingContext) -> String {
+        constant(&ctx.register_path())
+    }
+    fn field_mask(&self, ctx: &NamingContext) -> String {
+        constant(&format!("{}_MASK", ctx.field_path()))
+    }
+    fn field_pos(&self, ctx: &NamingContext) -> String {
+        constant(&format!("{}_OFFSET", ctx.field_path()))
+    }
+    fn enum_value(&self, ctx: &NamingContext) -> String {
+        let value = ctx.enum_value.as_deref().unwrap_or_default();
+        constant(&format!("{}_{}", ctx.field_path(), value))
+    }
+}
+
+/// Lowercase names, like `timer_cr_en_mask`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LowerSnake;
+
+impl NameMangler for LowerSnake {
+    fn peripheral(&self, ctx: &NamingContext) -> String {
+        ctx.peripheral.to_lowercase()
+    }
+    fn register(&self, ctx: &NamingContext) -> String {
+        ctx.register_path().to_lowercase()
+    }
+    fn field_mask(&self, ctx: &NamingContext) -> String {
+        format!("{}_mask", ctx.field_path()).to_lowercase()
+    }
+    fn field_pos(&self, ctx: &NamingContext) -> String {
+        format!("{}_pos", ctx.field_path()).to_lowercase()
+    }
+    fn enum_value(&self, ctx: &NamingContext) -> String {
+        let value = ctx.enum_value.as_deref().unwrap_or_default();
+        format!("{}_{}", ctx.field_path(), value).to_lowercase()
+    }
+}
+
+/// Upper case with `_` between words of camel case parts, so `DataReady` becomes `DATA_READY`
+fn constant(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_ascii_uppercase() && prev_lower {
+            out.push('_');
+        }
+        prev_lower = c.is_ascii_lowercase();
+        out.push(c.to_ascii_uppercase());
+    }
+    out
+}
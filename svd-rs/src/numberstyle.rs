@@ -0,0 +1,114 @@
+//! Textual form of numbers as written in SVD files.
+//!
+//! Parsers can record how numeric elements were written, so that encoders write them
+//! back the same way and changed files keep readable diffs against the original.
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+/// Base of a number with its prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Radix {
+    /// Decimal, `123`
+    Dec,
+    /// Hexadecimal with `0x` prefix, `0x7B`
+    Hex,
+    /// Binary with `0b` prefix, `0b1111011`
+    Bin,
+    /// Binary with `#` prefix, `#1111011`
+    Hash,
+}
+
+/// How a number is written
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberStyle {
+    /// Base and prefix
+    pub radix: Radix,
+    /// Minimal number of digits, padded with zeros
+    pub digits: u8,
+    /// Use uppercase hexadecimal digits
+    pub uppercase: bool,
+}
+
+impl NumberStyle {
+    /// Detect style of number `text`.
+    ///
+    /// Returns [`None`] for forms which can't be reproduced from the value, like
+    /// scaled (`4k`) or negative numbers and don't care bits (`#1x`).
+    pub fn detect(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (radix, digits) =
+            if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+                (Radix::Hex, hex)
+            } else if let Some(bin) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+                (Radix::Bin, bin)
+            } else if let Some(bin) = text.strip_prefix('#') {
+                (Radix::Hash, bin)
+            } else {
+                (Radix::Dec, text)
+            };
+        let valid = match radix {
+            Radix::Dec => digits.bytes().all(|b| b.is_ascii_digit()),
+            Radix::Hex => digits.bytes().all(|b| b.is_ascii_hexdigit()),
+            Radix::Bin | Radix::Hash => digits.bytes().all(|b| matches!(b, b'0' | b'1')),
+        };
+        if digits.is_empty() || !valid {
+            return None;
+        }
+        Some(Self {
+            radix,
+            digits: u8::try_from(digits.len()).ok()?,
+            uppercase: !digits.bytes().any(|b| b.is_ascii_lowercase()),
+        })
+    }
+
+    /// Write `value` in this style
+    pub fn format(&self, value: u64) -> String {
+        let w = self.digits as usize;
+        match self.radix {
+            Radix::Dec => format!("{:0w$}", value),
+            Radix::Hex if self.uppercase => format!("0x{:0w$X}", value),
+            Radix::Hex => format!("0x{:0w$x}", value),
+            Radix::Bin => format!("0b{:0w$b}", value),
+            Radix::Hash => format!("#{:0w$b}", value),
+        }
+    }
+}
+
+/// Styles of numeric elements of an element, keyed by tag name like `resetValue`.
+///
+/// Empty unless recorded by the parser.
+///
+/// Styles are only formatting, so all styles compare and hash equal and elements
+/// which only differ in how their numbers are written are equal.
+#[derive(Clone, Debug, Default)]
+pub struct NumberStyles(BTreeMap<&'static str, NumberStyle>);
+
+impl PartialEq for NumberStyles {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for NumberStyles {}
+
+impl Hash for NumberStyles {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl NumberStyles {
+    /// Style of element `tag`
+    pub fn get(&self, tag: &str) -> Option<&NumberStyle> {
+        self.0.get(tag)
+    }
+
+    /// Set style of element `tag`
+    pub fn insert(&mut self, tag: &'static str, style: NumberStyle) {
+        self.0.insert(tag, style);
+    }
+
+    /// Returns `true` if no styles are recorded
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
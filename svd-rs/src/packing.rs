@@ -0,0 +1,166 @@
+//! How densely registers are filled with fields.
+
+use super::{field, BitRange, Device, Field, PeripheralInfo, RegisterInfo};
+
+/// Field packing statistics of a register, see [`RegisterInfo::packing`]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct PackingInfo {
+    /// Number of bits covered by fields
+    pub defined_bits: u32,
+    /// Part of the register covered by fields, from 0 to 1
+    pub utilization: f32,
+    /// Largest contiguous range of bits not covered by fields.
+    /// Lowest one if there are several of the same width
+    pub largest_gap: Option<BitRange>,
+    /// Names of fields which have bits in several bytes
+    pub byte_crossing_fields: Vec<String>,
+    /// Some fields overlap
+    pub overlapping: bool,
+}
+
+/// Field packing statistics of a group of registers
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct PackingSummary {
+    /// Number of registers
+    pub registers: usize,
+    /// Average utilization of registers
+    pub utilization: f32,
+    /// Register paths with their utilization, from the emptiest
+    pub emptiest: Vec<(String, f32)>,
+}
+
+impl PackingSummary {
+    fn new(mut registers: Vec<(String, f32)>) -> Self {
+        let utilization = if registers.is_empty() {
+            0.
+        } else {
+            registers.iter().map(|(_, u)| u).sum::<f32>() / registers.len() as f32
+        };
+        registers.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Self {
+            registers: registers.len(),
+            utilization,
+            emptiest: registers,
+        }
+    }
+}
+
+impl RegisterInfo {
+    /// Compute field packing statistics for register of `effective_size` bits.
+    ///
+    /// Each element of field arrays is counted. Bits above `effective_size` are ignored.
+    pub fn packing(&self, effective_size: u32) -> PackingInfo {
+        let size = effective_size.min(64);
+        let size_mask = if size == 64 {
+            u64::MAX
+        } else {
+            (1 << size) - 1
+        };
+        let mut used = 0u64;
+        let mut overlapping = false;
+        let mut byte_crossing_fields = Vec::new();
+        for f in self.fields() {
+            let elements: Vec<_> = match f {
+                Field::Single(info) => vec![info.clone()],
+                Field::Array(info, dim) => field::expand(info, dim).collect(),
+            };
+            for info in elements {
+                let (lsb, msb) = (info.lsb(), info.msb());
+                let mask = bit_mask(lsb, msb) & size_mask;
+                overlapping |= used & mask != 0;
+                used |= mask;
+                if lsb / 8 != msb / 8 {
+                    byte_crossing_fields.push(info.name);
+                }
+            }
+        }
+
+        let defined_bits = used.count_ones();
+        let mut largest_gap: Option<BitRange> = None;
+        let mut start = None;
+        for bit in 0..=size {
+            let free = bit < size && used & (1 << bit) == 0;
+            match (free, start) {
+                (true, None) => start = Some(bit),
+                (false, Some(offset)) => {
+                    let width = bit - offset;
+                    if largest_gap.map_or(true, |g| width > g.width) {
+                        largest_gap = Some(BitRange::from_offset_width(offset, width));
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        PackingInfo {
+            defined_bits,
+            utilization: if size == 0 {
+                0.
+            } else {
+                defined_bits as f32 / size as f32
+            },
+            largest_gap,
+            byte_crossing_fields,
+            overlapping,
+        }
+    }
+}
+
+impl PeripheralInfo {
+    /// Summarize field packing of all registers of the peripheral.
+    ///
+    /// Register size is inherited from clusters and the peripheral.
+    /// `default_size` is used if none of them specifies it.
+    /// Registers without fields are skipped.
+    pub fn packing_summary(&self, default_size: u32) -> PackingSummary {
+        PackingSummary::new(self.register_packing(default_size))
+    }
+
+    fn register_packing(&self, default_size: u32) -> Vec<(String, f32)> {
+        let mut registers = Vec::new();
+        crate::validation::for_each_register(
+            self.registers.as_deref().unwrap_or(&[]),
+            &self.name,
+            0,
+            self.default_register_properties.size,
+            &mut Vec::new(),
+            &mut |path, r, _, size, _| {
+                if r.fields.is_some() {
+                    let size = r.properties.size.or(size).unwrap_or(default_size);
+                    registers.push((format!("{}.{}", path, r.name), r.packing(size).utilization));
+                }
+            },
+        );
+        registers
+    }
+}
+
+impl Device {
+    /// Summarize field packing of all registers of the device.
+    ///
+    /// Registers without inherited size are considered 32-bit.
+    pub fn packing_summary(&self) -> PackingSummary {
+        let default_size = self.default_register_properties.size.unwrap_or(32);
+        PackingSummary::new(
+            self.peripherals
+                .iter()
+                .flat_map(|p| p.register_packing(default_size))
+                .collect(),
+        )
+    }
+}
+
+fn bit_mask(lsb: u32, msb: u32) -> u64 {
+    if lsb > msb || lsb >= 64 {
+        return 0;
+    }
+    let width = (msb.min(63) - lsb) + 1;
+    if width == 64 {
+        u64::MAX
+    } else {
+        ((1 << width) - 1) << lsb
+    }
+}
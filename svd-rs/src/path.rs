@@ -0,0 +1,157 @@
+//! Lookup of elements by dot separated path.
+
+use super::{
+    template, Cluster, Device, DimElement, Field, MaybeArray, Name, Peripheral, Register,
+    RegisterCluster,
+};
+
+/// Element found by [`Device::get_path`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathElement<'a> {
+    /// Peripheral
+    Peripheral(&'a Peripheral),
+    /// Cluster
+    Cluster(&'a Cluster),
+    /// Register
+    Register(&'a Register),
+    /// Field
+    Field(&'a Field),
+}
+
+impl Device {
+    /// Get peripheral, cluster, register or field at dot separated `path`
+    /// like `TIM1.CR1.CEN` or `DMA.CH%s.CTRL`.
+    ///
+    /// Each segment matches an element by name. Arrays match both by their
+    /// name with `%s` and by the names of their elements, like `CCR1` for `CCR%s`,
+    /// and the whole array is returned for an element. Registers of alternate
+    /// groups are found by [effective name](crate::RegisterInfo::effective_name)
+    /// first, like with [`PeripheralInfo::get_register`](crate::PeripheralInfo::get_register).
+    pub fn get_path(&self, path: &str) -> Option<PathElement<'_>> {
+        lookup(self, path, false)
+    }
+
+    /// Get element at `path` like [`Device::get_path`], comparing names
+    /// case-insensitively
+    pub fn get_path_ignore_case(&self, path: &str) -> Option<PathElement<'_>> {
+        lookup(self, path, true)
+    }
+}
+
+fn lookup<'a>(device: &'a Device, path: &str, ignore_case: bool) -> Option<PathElement<'a>> {
+    let mut segments = path.split('.');
+    let first = segments.next()?;
+    let peripheral = device
+        .peripherals
+        .iter()
+        .find(|p| matches(p, first, ignore_case))?;
+    let mut element = PathElement::Peripheral(peripheral);
+    for segment in segments {
+        element = match element {
+            PathElement::Peripheral(p) => {
+                child(p.registers.as_deref().unwrap_or(&[]), segment, ignore_case)?
+            }
+            PathElement::Cluster(c) => child(&c.children, segment, ignore_case)?,
+            PathElement::Register(r) => {
+                PathElement::Field(r.fields().find(|f| matches(f, segment, ignore_case))?)
+            }
+            PathElement::Field(_) => return None,
+        };
+    }
+    Some(element)
+}
+
+fn child<'a>(
+    children: &'a [RegisterCluster],
+    name: &str,
+    ignore_case: bool,
+) -> Option<PathElement<'a>> {
+    // like `get_register`, registers of alternate groups are found by effective name first
+    let effective = children.iter().find_map(|rc| match rc {
+        RegisterCluster::Register(r)
+            if r.alternate_group.is_some() && eq(&r.effective_name(), name, ignore_case) =>
+        {
+            Some(PathElement::Register(r))
+        }
+        _ => None,
+    });
+    effective.or_else(|| {
+        children.iter().find_map(|rc| match rc {
+            RegisterCluster::Register(r) if matches(r, name, ignore_case) => {
+                Some(PathElement::Register(r))
+            }
+            RegisterCluster::Cluster(c) if matches(c, name, ignore_case) => {
+                Some(PathElement::Cluster(c))
+            }
+            _ => None,
+        })
+    })
+}
+
+fn eq(a: &str, b: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+/// `element` is named `name` or is an array with an element named `name`
+fn matches<T: Name>(element: &MaybeArray<T>, name: &str, ignore_case: bool) -> bool {
+    if eq(element.name(), name, ignore_case) {
+        return true;
+    }
+    let dim = match element {
+        MaybeArray::Single(_) => return false,
+        MaybeArray::Array(_, dim) => dim,
+    };
+    // elements named by `dimArrayIndex` like in `array::names`
+    let renamed = |index: &str| {
+        dim.dim_array_index.as_ref().and_then(|dai| {
+            dai.values
+                .iter()
+                .find(|v| v.value.map(|v| v.to_string()).as_deref() == Some(index))
+        })
+    };
+    if let Some(dai) = &dim.dim_array_index {
+        let by_name = dai.values.iter().any(|v| {
+            let index = match v.value {
+                Some(value) if eq(&v.name, name, ignore_case) => value.to_string(),
+                _ => return false,
+            };
+            is_index(dim, &index, false) && renamed(&index).map_or(false, |r| std::ptr::eq(r, v))
+        });
+        if by_name {
+            return true;
+        }
+    }
+    match instance_index(element.name(), name, ignore_case) {
+        Some(index) => is_index(dim, index, ignore_case) && renamed(index).is_none(),
+        None => false,
+    }
+}
+
+/// Part of `name` in place of the placeholder of array name `template`
+fn instance_index<'n>(template: &str, name: &'n str, ignore_case: bool) -> Option<&'n str> {
+    let (prefix, suffix) = template
+        .split_once(template::ARRAY_PLACEHOLDER)
+        .or_else(|| template.split_once(template::PLACEHOLDER))?;
+    let end = name.len().checked_sub(suffix.len())?;
+    let index = name.get(prefix.len()..end).filter(|i| !i.is_empty())?;
+    (eq(name.get(..prefix.len())?, prefix, ignore_case)
+        && eq(name.get(end..)?, suffix, ignore_case))
+    .then_some(index)
+}
+
+/// `index` is one of the indexes of `dim`
+fn is_index(dim: &DimElement, index: &str, ignore_case: bool) -> bool {
+    match &dim.dim_index {
+        Some(indexes) => indexes.iter().any(|i| eq(i, index, ignore_case)),
+        // written like `u32::to_string` does
+        None => {
+            index.bytes().all(|b| b.is_ascii_digit())
+                && (index == "0" || !index.starts_with('0'))
+                && index.parse::<u32>().map_or(false, |i| i < dim.dim)
+        }
+    }
+}
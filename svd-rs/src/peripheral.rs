@@ -1,12 +1,15 @@
 use super::{
     array::{descriptions, names},
     registercluster::{
-        AllRegistersIter, AllRegistersIterMut, ClusterIter, ClusterIterMut, RegisterIter,
-        RegisterIterMut,
+        register_position, AllRegistersIter, AllRegistersIterMut, ClusterIter, ClusterIterMut,
+        RegisterIter, RegisterIterMut,
     },
-    AddressBlock, BuildError, Cluster, Description, DimElement, EmptyToNone, Interrupt, MaybeArray,
-    Name, Register, RegisterCluster, RegisterProperties, SvdError, ValidateLevel,
+    template, AddressBlock, BuildError, Cluster, Description, Device, DimElement, EmptyToNone,
+    Interrupt, MaybeArray, Name, NumberStyles, Register, RegisterCluster, RegisterInfo,
+    RegisterProperties, SvdError, ValidateLevel,
 };
+use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
 use std::ops::Deref;
 
 /// A single peripheral or array of peripherals
@@ -18,6 +21,12 @@ pub enum Error {
     /// The peripheral has no registers, but specified a `<registers>` tag.
     #[error("Peripheral have `registers` tag, but it is empty")]
     EmptyRegisters,
+    /// The peripheral is derived from peripheral missing in the device.
+    #[error("Peripheral `{0}` is derived from missing peripheral `{1}`")]
+    MissingParent(String, String),
+    /// The peripheral is derived from itself through the chain.
+    #[error("Peripheral derivation cycle: {0}")]
+    DeriveCycle(String),
 }
 
 /// A description of a peripheral in the [device](crate::Device), describing, for example, the [memory mappings](crate::RegisterInfo).
@@ -26,7 +35,7 @@ pub enum Error {
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct PeripheralInfo {
     /// The string identifies the peripheral. Peripheral names are required to be unique for a device
@@ -88,6 +97,13 @@ pub struct PeripheralInfo {
     )]
     pub header_struct_name: Option<String>,
 
+    /// C expression which is `true` when the peripheral is disabled, for example by clock gating
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub disable_condition: Option<String>,
+
     /// Lowest address reserved or used by the peripheral
     pub base_address: u64,
 
@@ -123,14 +139,20 @@ pub struct PeripheralInfo {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub derived_from: Option<String>,
+
+    /// How `baseAddress` and register properties of the peripheral were written
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub number_styles: NumberStyles,
 }
 
-/// Return iterator over base addresses of each peripheral in array
+/// Return iterator over base addresses of each peripheral in array.
+///
+/// Stops at the first address which doesn't fit in `u64`
 pub fn base_addresses<'a>(
     info: &'a PeripheralInfo,
     dim: &'a DimElement,
 ) -> impl Iterator<Item = u64> + 'a {
-    (0..dim.dim as u64).map(|i| info.base_address + i * dim.dim_increment as u64)
+    (0..dim.dim as u64).map_while(|i| info.base_address.checked_add(i * dim.dim_increment as u64))
 }
 
 /// Extract `PeripheralInfo` items from array
@@ -147,15 +169,13 @@ pub fn expand<'a>(
             info.name = name;
             info.description = description;
             info.base_address = base_address;
-            info.display_name = info
-                .display_name
-                .map(|d| d.replace("[%s]", &idx).replace("%s", &idx));
+            info.display_name = info.display_name.map(|d| template::substitute(&d, &idx));
             info
         })
 }
 
 /// Builder for [`Peripheral`]
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct PeripheralInfoBuilder {
     name: Option<String>,
     display_name: Option<String>,
@@ -166,12 +186,14 @@ pub struct PeripheralInfoBuilder {
     prepend_to_name: Option<String>,
     append_to_name: Option<String>,
     header_struct_name: Option<String>,
+    disable_condition: Option<String>,
     base_address: Option<u64>,
     default_register_properties: RegisterProperties,
     address_block: Option<Vec<AddressBlock>>,
     interrupt: Option<Vec<Interrupt>>,
     registers: Option<Vec<RegisterCluster>>,
     derived_from: Option<String>,
+    number_styles: NumberStyles,
 }
 
 impl From<PeripheralInfo> for PeripheralInfoBuilder {
@@ -186,12 +208,14 @@ impl From<PeripheralInfo> for PeripheralInfoBuilder {
             prepend_to_name: p.prepend_to_name,
             append_to_name: p.append_to_name,
             header_struct_name: p.header_struct_name,
+            disable_condition: p.disable_condition,
             base_address: Some(p.base_address),
             default_register_properties: p.default_register_properties,
             address_block: p.address_block,
             interrupt: Some(p.interrupt),
             registers: p.registers,
             derived_from: p.derived_from,
+            number_styles: p.number_styles,
         }
     }
 }
@@ -242,6 +266,11 @@ impl PeripheralInfoBuilder {
         self.header_struct_name = value;
         self
     }
+    /// Set the disable condition of the peripheral
+    pub fn disable_condition(mut self, value: Option<String>) -> Self {
+        self.disable_condition = value;
+        self
+    }
     /// Set the base address of the peripheral
     pub fn base_address(mut self, value: u64) -> Self {
         self.base_address = Some(value);
@@ -272,6 +301,11 @@ impl PeripheralInfoBuilder {
         self.derived_from = value;
         self
     }
+    /// Set how numbers of the peripheral are written.
+    pub fn number_styles(mut self, value: NumberStyles) -> Self {
+        self.number_styles = value;
+        self
+    }
     /// Validate and build a [`PeripheralInfo`].
     pub fn build(self, lvl: ValidateLevel) -> Result<PeripheralInfo, SvdError> {
         let per = PeripheralInfo {
@@ -286,6 +320,7 @@ impl PeripheralInfoBuilder {
             prepend_to_name: self.prepend_to_name.empty_to_none(),
             append_to_name: self.append_to_name.empty_to_none(),
             header_struct_name: self.header_struct_name.empty_to_none(),
+            disable_condition: self.disable_condition.empty_to_none(),
             base_address: self
                 .base_address
                 .ok_or_else(|| BuildError::Uninitialized("base_address".to_string()))?,
@@ -294,6 +329,7 @@ impl PeripheralInfoBuilder {
             interrupt: self.interrupt.unwrap_or_default(),
             registers: self.registers,
             derived_from: self.derived_from,
+            number_styles: self.number_styles,
         };
         per.validate(lvl)?;
         Ok(per)
@@ -354,6 +390,9 @@ impl PeripheralInfo {
         if builder.header_struct_name.is_some() {
             self.header_struct_name = builder.header_struct_name.empty_to_none();
         }
+        if builder.disable_condition.is_some() {
+            self.disable_condition = builder.disable_condition.empty_to_none();
+        }
         if let Some(base_address) = builder.base_address {
             self.base_address = base_address;
         }
@@ -418,7 +457,7 @@ impl PeripheralInfo {
     }
 
     /// Returns iterator over child registers
-    pub fn registers(&self) -> RegisterIter {
+    pub fn registers(&self) -> RegisterIter<'_> {
         RegisterIter {
             all: match &self.registers {
                 Some(regs) => regs.iter(),
@@ -428,7 +467,7 @@ impl PeripheralInfo {
     }
 
     /// Returns mutable iterator over child registers
-    pub fn registers_mut(&mut self) -> RegisterIterMut {
+    pub fn registers_mut(&mut self) -> RegisterIterMut<'_> {
         RegisterIterMut {
             all: match &mut self.registers {
                 Some(regs) => regs.iter_mut(),
@@ -438,7 +477,7 @@ impl PeripheralInfo {
     }
 
     /// Returns iterator over child clusters
-    pub fn clusters(&self) -> ClusterIter {
+    pub fn clusters(&self) -> ClusterIter<'_> {
         ClusterIter {
             all: match &self.registers {
                 Some(regs) => regs.iter(),
@@ -448,7 +487,7 @@ impl PeripheralInfo {
     }
 
     /// Returns mutable iterator over child clusters
-    pub fn clusters_mut(&mut self) -> ClusterIterMut {
+    pub fn clusters_mut(&mut self) -> ClusterIterMut<'_> {
         ClusterIterMut {
             all: match &mut self.registers {
                 Some(regs) => regs.iter_mut(),
@@ -458,13 +497,7 @@ impl PeripheralInfo {
     }
 
     /// Returns iterator over all descendant registers
-    #[deprecated(since = "0.12.1", note = "Please use `all_registers` instead")]
-    pub fn reg_iter(&self) -> AllRegistersIter {
-        self.all_registers()
-    }
-
-    /// Returns iterator over all descendant registers
-    pub fn all_registers(&self) -> AllRegistersIter {
+    pub fn all_registers(&self) -> AllRegistersIter<'_> {
         AllRegistersIter {
             rem: match &self.registers {
                 Some(regs) => regs.iter().rev().collect(),
@@ -474,13 +507,7 @@ impl PeripheralInfo {
     }
 
     /// Returns mutable iterator over all descendant registers
-    #[deprecated(since = "0.12.1", note = "Please use `all_registers_mut` instead")]
-    pub fn reg_iter_mut(&mut self) -> AllRegistersIterMut {
-        self.all_registers_mut()
-    }
-
-    /// Returns mutable iterator over all descendant registers
-    pub fn all_registers_mut(&mut self) -> AllRegistersIterMut {
+    pub fn all_registers_mut(&mut self) -> AllRegistersIterMut<'_> {
         AllRegistersIterMut {
             rem: match &mut self.registers {
                 Some(regs) => regs.iter_mut().rev().collect(),
@@ -489,14 +516,124 @@ impl PeripheralInfo {
         }
     }
 
-    /// Get register by name
+    /// Name of `register` in generated headers: its
+    /// [effective name](crate::RegisterInfo::effective_name) with `prependToName`
+    /// and `appendToName` of the peripheral around it
+    pub fn register_display_name(&self, register: &RegisterInfo) -> String {
+        format!(
+            "{}{}{}",
+            self.prepend_to_name.as_deref().unwrap_or_default(),
+            register.effective_name(),
+            self.append_to_name.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Get register by [effective name](crate::RegisterInfo::effective_name) or by name.
+    ///
+    /// Registers in clusters are found by dot separated path like `CLUSTER.REGISTER`.
     pub fn get_register(&self, name: &str) -> Option<&Register> {
-        self.registers().find(|f| f.name == name)
+        match name.rsplit_once('.') {
+            None => {
+                let i = register_position(self.registers(), name)?;
+                self.registers().nth(i)
+            }
+            Some((clusters, name)) => {
+                let mut clusters = clusters.split('.');
+                let mut cluster = self.get_cluster(clusters.next()?)?;
+                for c in clusters {
+                    cluster = cluster.get_cluster(c)?;
+                }
+                cluster.get_register(name)
+            }
+        }
     }
 
-    /// Get mutable register by name
+    /// Get mutable register like [`PeripheralInfo::get_register`]
     pub fn get_mut_register(&mut self, name: &str) -> Option<&mut Register> {
-        self.registers_mut().find(|f| f.name == name)
+        match name.rsplit_once('.') {
+            None => {
+                let i = register_position(self.registers(), name)?;
+                self.registers_mut().nth(i)
+            }
+            Some((clusters, name)) => {
+                let mut clusters = clusters.split('.');
+                let mut cluster = self.get_mut_cluster(clusters.next()?)?;
+                for c in clusters {
+                    cluster = cluster.get_mut_cluster(c)?;
+                }
+                cluster.get_mut_register(name)
+            }
+        }
+    }
+
+    /// Returns this peripheral and the peripherals of `device` up its `derivedFrom` chain,
+    /// the closest first.
+    fn registers_chain<'a>(
+        &'a self,
+        device: &'a Device,
+    ) -> Result<Vec<&'a PeripheralInfo>, SvdError> {
+        let mut chain = vec![self];
+        let mut p = self;
+        while let Some(parent) = &p.derived_from {
+            if chain.iter().any(|c| &c.name == parent) {
+                let mut names: Vec<_> = chain.iter().map(|c| c.name.as_str()).collect();
+                names.push(parent);
+                return Err(Error::DeriveCycle(names.join(" -> ")).into());
+            }
+            p = device
+                .get_peripheral(parent)
+                .ok_or_else(|| Error::MissingParent(p.name.clone(), parent.clone()))?;
+            chain.push(p);
+        }
+        Ok(chain)
+    }
+
+    /// Default register properties of the peripheral with those of `device` filling
+    /// the ones it doesn't set.
+    ///
+    /// Registers and clusters layer their own properties on top of these.
+    pub fn effective_register_properties(&self, device: &Device) -> RegisterProperties {
+        self.default_register_properties
+            .inherit(&device.default_register_properties)
+    }
+
+    /// Iterate over registers placed directly in the peripheral after derivation
+    /// from parents in `device`, without copying them.
+    ///
+    /// Registers are taken the same way `derivedFrom` is resolved when the device is
+    /// expanded: registers of the parents, each replaced by the register of the same
+    /// name of a closer peripheral, followed by the registers only the closer ones have.
+    pub fn effective_registers<'a>(
+        &'a self,
+        device: &'a Device,
+    ) -> Result<impl Iterator<Item = &'a Register> + 'a, SvdError> {
+        let mut registers: Vec<&Register> = Vec::new();
+        // position of each effective name in `registers`
+        let mut positions: HashMap<Cow<str>, usize> = HashMap::new();
+        for p in self.registers_chain(device)?.into_iter().rev() {
+            for r in p.registers() {
+                match positions.entry(r.effective_name()) {
+                    Entry::Occupied(e) => registers[*e.get()] = r,
+                    Entry::Vacant(e) => {
+                        e.insert(registers.len());
+                        registers.push(r);
+                    }
+                }
+            }
+        }
+        Ok(registers.into_iter())
+    }
+
+    /// Get register by name after derivation, see [`PeripheralInfo::effective_registers`]
+    pub fn effective_register<'a>(
+        &'a self,
+        device: &'a Device,
+        name: &str,
+    ) -> Option<&'a Register> {
+        let registers = self.effective_registers(device).ok()?;
+        let registers: Vec<&Register> = registers.collect();
+        let i = register_position(registers.iter().copied(), name)?;
+        Some(registers[i])
     }
 
     /// Get cluster by name
@@ -523,7 +660,10 @@ impl PeripheralInfo {
 impl Peripheral {
     /// Validate the [`Peripheral`] recursively
     pub fn validate_all(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
-        if let Self::Array(_, dim) = self {
+        if let Self::Array(info, dim) = self {
+            if !lvl.is_disabled() {
+                super::check_array_name(&info.name, "peripheral")?;
+            }
             dim.validate(lvl)?;
         }
         self.deref().validate_all(lvl)
@@ -0,0 +1,125 @@
+//! Record of passes which changed elements of a device.
+//!
+//! Transformations like [`Device::apply_fix`](crate::Device::apply_fix) or
+//! [`Device::widen_registers_to_fields`](crate::Device::widen_registers_to_fields)
+//! record what they did to [`Device::provenance`](crate::Device::provenance) once it
+//! is enabled with [`Device::enable_provenance`](crate::Device::enable_provenance).
+//! Elements are addressed by dot separated paths like `PERIPHERAL.CLUSTER.REGISTER.FIELD`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Change of an element made by a pass
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvenanceEntry {
+    /// Name of the pass, like `apply_fix`
+    pub pass: &'static str,
+    /// What the pass did
+    pub detail: String,
+    /// Number of the entry in the recorder, increasing in order of recording
+    pub timestamp_counter: u64,
+}
+
+/// History of changes of elements.
+///
+/// Default recorder is disabled and records nothing. History is not part of the
+/// device description, so all recorders compare equal.
+#[derive(Clone, Debug, Default)]
+pub struct ProvenanceRecorder {
+    enabled: bool,
+    counter: u64,
+    history: BTreeMap<String, Vec<ProvenanceEntry>>,
+}
+
+impl PartialEq for ProvenanceRecorder {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ProvenanceRecorder {}
+
+impl ProvenanceRecorder {
+    /// Create enabled recorder
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns `true` if the recorder records changes
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record that `pass` changed an element.
+    ///
+    /// `f` returns path of the element and details and is only called when the
+    /// recorder is enabled, so disabled recording costs no allocation.
+    pub fn record(&mut self, pass: &'static str, f: impl FnOnce() -> (String, String)) {
+        if !self.enabled {
+            return;
+        }
+        let (path, detail) = f();
+        self.counter += 1;
+        self.history.entry(path).or_default().push(ProvenanceEntry {
+            pass,
+            detail,
+            timestamp_counter: self.counter,
+        });
+    }
+
+    /// Changes of element at `path`, oldest first
+    pub fn entries(&self, path: &str) -> &[ProvenanceEntry] {
+        self.history
+            .get(path)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Paths of all changed elements
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.history.keys().map(String::as_str)
+    }
+
+    /// Move history of element at `from` and of its children to every path of `to`,
+    /// for example after an array is replaced with its elements.
+    pub fn rekey(&mut self, from: &str, to: &[String]) {
+        if !self.enabled || to.iter().all(|t| t == from) {
+            return;
+        }
+        let prefix = format!("{}.", from);
+        let moved: Vec<String> = self
+            .history
+            .keys()
+            .filter(|k| *k == from || k.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for key in moved {
+            let entries = self.history.remove(&key).unwrap();
+            let rest = &key[from.len()..];
+            for t in to {
+                let target = self.history.entry(format!("{}{}", t, rest)).or_default();
+                target.extend(entries.iter().cloned());
+                target.sort_by_key(|e| e.timestamp_counter);
+            }
+        }
+    }
+
+    /// Human readable history of all elements, one entry per line
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for (path, entries) in &self.history {
+            for e in entries {
+                writeln!(
+                    report,
+                    "{}: #{} {}: {}",
+                    path, e.timestamp_counter, e.pass, e.detail
+                )
+                .unwrap();
+            }
+        }
+        report
+    }
+}
@@ -4,7 +4,7 @@
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum ReadAction {
     /// The register/field is cleared (set to zero) following a read operation
     Clear,
@@ -13,18 +13,13 @@ pub enum ReadAction {
     Set,
 
     /// The register/field is modified in some way after a read operation
+    #[default]
     Modify,
 
     /// One or more dependent resources other than the current register/field are immediately affected by a read operation
     ModifyExternal,
 }
 
-impl Default for ReadAction {
-    fn default() -> Self {
-        Self::Modify
-    }
-}
-
 impl ReadAction {
     /// Parse a string into an [`ReadAction`] value, returning [`Option::None`] if the string is not valid.
     pub fn parse_str(s: &str) -> Option<Self> {
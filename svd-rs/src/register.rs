@@ -1,9 +1,11 @@
 use super::{
     array::{descriptions, names},
-    Access, BuildError, DataType, Description, DimElement, EmptyToNone, Field, MaybeArray,
-    ModifiedWriteValues, Name, ReadAction, RegisterProperties, SvdError, ValidateLevel,
+    field, layout, template, validation, Access, BuildError, BuilderConfig, ClusterInfo, DataType,
+    Description, DimElement, EmptyToNone, Field, FieldInfo, MaybeArray, ModifiedWriteValues, Name,
+    NumberStyles, ReadAction, RegisterCluster, RegisterProperties, SvdError, ValidateLevel,
     WriteConstraint,
 };
+use std::borrow::Cow;
 use std::ops::Deref;
 
 /// A single register or array of registers. A register is a named, programmable resource that belongs to a [peripheral](crate::Peripheral).
@@ -17,13 +19,26 @@ pub enum Error {
     EmptyFields,
 }
 
+/// Register size needed to contain all fields, see
+/// [`RegisterInfo::effective_size_considering_fields`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SizeAdjustment {
+    /// Declared or inherited size
+    pub declared: u32,
+    /// Smallest of 8, 16, 32 or 64 bits which contains all fields
+    pub required: u32,
+    /// Fields which don't fit in the declared size
+    pub fields: Vec<String>,
+}
+
 /// A register is a named, programmable resource that belongs to a [peripheral](crate::Peripheral).
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct RegisterInfo {
     /// String to identify the register.
@@ -109,14 +124,20 @@ pub struct RegisterInfo {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub derived_from: Option<String>,
+
+    /// How `addressOffset` and properties of the register were written
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub number_styles: NumberStyles,
 }
 
-/// Return iterator over address offsets of each register in array
+/// Return iterator over address offsets of each register in array.
+///
+/// Stops at the first offset which doesn't fit in `u32`
 pub fn address_offsets<'a>(
     info: &'a RegisterInfo,
     dim: &'a DimElement,
 ) -> impl Iterator<Item = u32> + 'a {
-    (0..dim.dim).map(move |i| info.address_offset + i * dim.dim_increment)
+    (0..dim.dim).map_while(move |i| dim.offset(info.address_offset, i))
 }
 
 /// Extract `RegisterInfo` items from array
@@ -133,9 +154,7 @@ pub fn expand<'a>(
             info.name = name;
             info.description = description;
             info.address_offset = address_offset;
-            info.display_name = info
-                .display_name
-                .map(|d| d.replace("[%s]", &idx).replace("%s", &idx));
+            info.display_name = info.display_name.map(|d| template::substitute(&d, &idx));
             info
         })
 }
@@ -156,6 +175,7 @@ pub struct RegisterInfoBuilder {
     read_action: Option<ReadAction>,
     fields: Option<Vec<Field>>,
     derived_from: Option<String>,
+    number_styles: NumberStyles,
 }
 
 impl From<RegisterInfo> for RegisterInfoBuilder {
@@ -174,6 +194,7 @@ impl From<RegisterInfo> for RegisterInfoBuilder {
             read_action: r.read_action,
             fields: r.fields,
             derived_from: r.derived_from,
+            number_styles: r.number_styles,
         }
     }
 }
@@ -264,6 +285,11 @@ impl RegisterInfoBuilder {
         self.derived_from = value;
         self
     }
+    /// Set how numbers of the register are written.
+    pub fn number_styles(mut self, value: NumberStyles) -> Self {
+        self.number_styles = value;
+        self
+    }
     /// Validate and build a [`RegisterInfo`].
     pub fn build(self, lvl: ValidateLevel) -> Result<RegisterInfo, SvdError> {
         let reg = RegisterInfo {
@@ -284,10 +310,27 @@ impl RegisterInfoBuilder {
             read_action: self.read_action,
             fields: self.fields,
             derived_from: self.derived_from,
+            number_styles: self.number_styles,
         };
         reg.validate(lvl)?;
         Ok(reg)
     }
+    /// Build a [`Register`], an array if `dim` is given, with checks of builder `config`.
+    ///
+    /// See [`BuilderProfile`](crate::BuilderProfile) for what each profile checks.
+    pub fn build_with(
+        self,
+        dim: Option<DimElement>,
+        config: &BuilderConfig,
+    ) -> Result<Register, SvdError> {
+        let register = self.build(ValidateLevel::Disabled)?.maybe_array(dim);
+        if let Register::Array(info, _) = &register {
+            super::check_array_name(&info.name, "register")?;
+        }
+        let node = RegisterCluster::Register(register);
+        validation::check_built(&node, config)?;
+        Ok(Register::try_from(node).unwrap())
+    }
 }
 
 impl RegisterInfo {
@@ -396,7 +439,7 @@ impl RegisterInfo {
     }
 
     /// Returns iterator over child fields
-    pub fn fields(&self) -> std::slice::Iter<Field> {
+    pub fn fields(&self) -> std::slice::Iter<'_, Field> {
         match &self.fields {
             Some(fields) => fields.iter(),
             None => [].iter(),
@@ -404,7 +447,7 @@ impl RegisterInfo {
     }
 
     /// Returns mutable iterator over child fields
-    pub fn fields_mut(&mut self) -> std::slice::IterMut<Field> {
+    pub fn fields_mut(&mut self) -> std::slice::IterMut<'_, Field> {
         match &mut self.fields {
             Some(fields) => fields.iter_mut(),
             None => [].iter_mut(),
@@ -420,16 +463,139 @@ impl RegisterInfo {
     pub fn get_mut_field(&mut self, name: &str) -> Option<&mut Field> {
         self.fields_mut().find(|f| f.name == name)
     }
+
+    /// Name identifying the register among its siblings.
+    ///
+    /// Registers of different alternate groups may share `name`, so a register with
+    /// `alternateGroup` is identified by `name_alternateGroup`. `name` itself is kept
+    /// as written in SVD. [`Device::validate_with`](crate::Device::validate_with)
+    /// reports effective names which collide with names of other registers.
+    pub fn effective_name(&self) -> Cow<'_, str> {
+        match &self.alternate_group {
+            Some(group) => Cow::Owned(format!("{}_{}", self.name, group)),
+            None => Cow::Borrowed(&self.name),
+        }
+    }
+
+    /// Address offset relative to the enclosing cluster, or to the peripheral
+    /// if the register is placed directly in it
+    pub fn offset_in_parent(&self) -> u32 {
+        self.address_offset
+    }
+
+    /// Address offset relative to the peripheral for the register placed in
+    /// `cluster_chain`, outermost cluster first.
+    ///
+    /// Offsets of first elements of cluster arrays are used.
+    pub fn offset_in_peripheral(&self, cluster_chain: &[&ClusterInfo]) -> u64 {
+        cluster_chain
+            .iter()
+            .map(|c| c.address_offset as u64)
+            .sum::<u64>()
+            + self.address_offset as u64
+    }
+
+    fn field_elements(&self) -> impl Iterator<Item = Cow<'_, FieldInfo>> {
+        self.fields().flat_map(|f| -> Box<dyn Iterator<Item = _>> {
+            match f {
+                Field::Single(info) => Box::new(core::iter::once(Cow::Borrowed(info))),
+                Field::Array(info, dim) => Box::new(field::expand(info, dim).map(Cow::Owned)),
+            }
+        })
+    }
+
+    /// Returns register size with `inherited` as default and the adjustment
+    /// needed if some fields have bits above it.
+    ///
+    /// The size is never widened here, the adjustment is advisory.
+    /// Use [`RegisterInfo::widen_to_fields`] to apply it.
+    pub fn effective_size_considering_fields(
+        &self,
+        inherited: u32,
+    ) -> (u32, Option<SizeAdjustment>) {
+        let declared = self.properties.size.unwrap_or(inherited);
+        let mut msb = 0;
+        let mut fields = Vec::new();
+        for f in self.field_elements() {
+            if f.msb() >= declared {
+                msb = msb.max(f.msb());
+                fields.push(f.name.clone());
+            }
+        }
+        if fields.is_empty() {
+            return (declared, None);
+        }
+        let required = [8, 16, 32, 64]
+            .into_iter()
+            .find(|s| msb < *s)
+            .unwrap_or(msb + 1);
+        (
+            declared,
+            Some(SizeAdjustment {
+                declared,
+                required,
+                fields,
+            }),
+        )
+    }
+
+    /// Set register size big enough to contain all fields.
+    ///
+    /// Returns the applied adjustment or `None` if fields already fit.
+    pub fn widen_to_fields(&mut self, inherited: u32) -> Option<SizeAdjustment> {
+        let (_, adjustment) = self.effective_size_considering_fields(inherited);
+        if let Some(adjustment) = adjustment.as_ref() {
+            self.properties.size = Some(adjustment.required);
+        }
+        adjustment
+    }
+
+    /// Extract values of all fields from register `value`.
+    ///
+    /// Elements of field arrays are returned separately.
+    /// Fails if a field doesn't fit in the register size (with `inherited` as default),
+    /// see [`layout::extract`].
+    pub fn decode(&self, value: u64, inherited: u32) -> Result<Vec<(String, u64)>, SvdError> {
+        let size = self.properties.size.unwrap_or(inherited);
+        self.field_elements()
+            .map(|f| {
+                Ok((
+                    f.name.clone(),
+                    layout::extract(value, f.bit_range, size, &f.name)?,
+                ))
+            })
+            .collect()
+    }
 }
 
 impl Register {
     /// Validate the [`Register`] recursively
     pub fn validate_all(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
-        if let Self::Array(_, dim) = self {
+        if let Self::Array(info, dim) = self {
+            if !lvl.is_disabled() {
+                super::check_array_name(&info.name, "register")?;
+            }
             dim.validate(lvl)?;
         }
         self.deref().validate_all(lvl)
     }
+
+    /// Registers of the array with `%s` substituted by the indexes and address offsets
+    /// moved by `dimIncrement`, or the register itself if it isn't an array.
+    ///
+    /// Fails if the name has no placeholder, `dimIndex` has fewer or more indexes than `dim`
+    /// or address offsets overflow.
+    pub fn expand(&self) -> Result<Vec<RegisterInfo>, SvdError> {
+        match self {
+            Self::Single(info) => Ok(vec![info.clone()]),
+            Self::Array(info, dim) => {
+                super::check_array_name(&info.name, "register")?;
+                dim.validate(ValidateLevel::Weak)?;
+                dim.check_offsets(info.address_offset)?;
+                Ok(expand(info, dim).collect())
+            }
+        }
+    }
 }
 
 impl Name for RegisterInfo {
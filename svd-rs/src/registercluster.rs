@@ -6,7 +6,7 @@ use super::{Cluster, Register};
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "lowercase")
 )]
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(clippy::large_enum_variant)]
 pub enum RegisterCluster {
     /// Register
@@ -27,6 +27,30 @@ impl From<Cluster> for RegisterCluster {
     }
 }
 
+impl TryFrom<RegisterCluster> for Register {
+    type Error = RegisterCluster;
+
+    /// Unwrap a register, returning the cluster unchanged otherwise
+    fn try_from(rc: RegisterCluster) -> Result<Self, Self::Error> {
+        match rc {
+            RegisterCluster::Register(r) => Ok(r),
+            rc => Err(rc),
+        }
+    }
+}
+
+impl TryFrom<RegisterCluster> for Cluster {
+    type Error = RegisterCluster;
+
+    /// Unwrap a cluster, returning the register unchanged otherwise
+    fn try_from(rc: RegisterCluster) -> Result<Self, Self::Error> {
+        match rc {
+            RegisterCluster::Cluster(c) => Ok(c),
+            rc => Err(rc),
+        }
+    }
+}
+
 impl RegisterCluster {
     /// Name of register or cluster
     pub fn name(&self) -> &String {
@@ -35,6 +59,14 @@ impl RegisterCluster {
             Self::Cluster(c) => &c.name,
         }
     }
+    /// Name identifying register or cluster among its siblings,
+    /// see [`RegisterInfo::effective_name`](crate::RegisterInfo::effective_name)
+    pub fn effective_name(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            Self::Register(r) => r.effective_name(),
+            Self::Cluster(c) => c.name.as_str().into(),
+        }
+    }
     /// Description of register or cluster
     pub fn description(&self) -> &Option<String> {
         match self {
@@ -58,6 +90,51 @@ impl RegisterCluster {
     }
 }
 
+/// Position of register `name` in `registers`. Effective names are looked up first,
+/// so a register of an alternate group is found by its own name only if no
+/// register has that effective name
+pub(crate) fn register_position<'a>(
+    registers: impl Iterator<Item = &'a Register>,
+    name: &str,
+) -> Option<usize> {
+    position(
+        registers,
+        |r| r.effective_name() == name,
+        |r| r.name == name,
+    )
+}
+
+/// Position of register or cluster `name` in `children`, looked up like in
+/// [`register_position`]
+pub(crate) fn child_position<'a>(
+    children: impl Iterator<Item = &'a RegisterCluster>,
+    name: &str,
+) -> Option<usize> {
+    position(
+        children,
+        |rc| rc.effective_name() == name,
+        |rc| rc.name() == name,
+    )
+}
+
+/// Position of the first item matching `effective`, or else of the first matching `plain`
+fn position<T>(
+    items: impl Iterator<Item = T>,
+    effective: impl Fn(&T) -> bool,
+    plain: impl Fn(&T) -> bool,
+) -> Option<usize> {
+    let mut first_plain = None;
+    for (i, item) in items.enumerate() {
+        if effective(&item) {
+            return Some(i);
+        }
+        if first_plain.is_none() && plain(&item) {
+            first_plain = Some(i);
+        }
+    }
+    first_plain
+}
+
 /// Register iterator
 pub struct RegisterIter<'a> {
     pub(crate) all: std::slice::Iter<'a, RegisterCluster>,
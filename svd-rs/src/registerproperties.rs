@@ -20,7 +20,7 @@ pub enum Error {
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "camelCase")
 )]
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub struct RegisterProperties {
     /// Bit-width of register
@@ -59,11 +59,52 @@ pub struct RegisterProperties {
     pub reset_mask: Option<u64>,
 }
 
+/// Register properties with all values known, see [`RegisterProperties::effective`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EffectiveProperties {
+    /// Bit-width of register
+    pub size: u32,
+    /// Access rights for register
+    pub access: Access,
+    /// Security privilege, SVD has no default for it
+    pub protection: Option<Protection>,
+    /// Register value at RESET
+    pub reset_value: u64,
+    /// Register bits which have a defined reset value
+    pub reset_mask: u64,
+}
+
 impl RegisterProperties {
     /// Create a new [`RegisterProperties`].
     pub fn new() -> Self {
         Self::default()
     }
+    /// Properties with those absent taken from `parent`, like a register
+    /// takes them from its cluster, peripheral or device.
+    pub fn inherit(&self, parent: &RegisterProperties) -> RegisterProperties {
+        RegisterProperties {
+            size: self.size.or(parent.size),
+            access: self.access.or(parent.access),
+            protection: self.protection.or(parent.protection),
+            reset_value: self.reset_value.or(parent.reset_value),
+            reset_mask: self.reset_mask.or(parent.reset_mask),
+        }
+    }
+    /// Properties with absent values replaced by the defaults:
+    /// size 32, read-write access, reset value 0 and a reset mask of all bits of size.
+    pub fn effective(&self) -> EffectiveProperties {
+        let size = self.size.unwrap_or(32);
+        EffectiveProperties {
+            size,
+            access: self.access.unwrap_or(Access::ReadWrite),
+            protection: self.protection,
+            reset_value: self.reset_value.unwrap_or(0),
+            reset_mask: self
+                .reset_mask
+                .unwrap_or_else(|| u64::MAX >> (64 - size.clamp(1, 64))),
+        }
+    }
     /// Modify an existing [`RegisterProperties`] based on another.
     pub fn modify_from(
         &mut self,
@@ -133,7 +174,7 @@ pub(crate) fn check_reset_value(
     mask: Option<u64>,
     lvl: ValidateLevel,
 ) -> Result<(), Error> {
-    const MAX_BITS: u32 = core::u64::MAX.count_ones();
+    const MAX_BITS: u32 = u64::MAX.count_ones();
 
     if let (Some(size), Some(value)) = (size, value) {
         if MAX_BITS - value.leading_zeros() > size {
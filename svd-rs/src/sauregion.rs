@@ -0,0 +1,228 @@
+use super::{BuildError, Protection, SvdError, ValidateLevel};
+
+/// Initial configuration of the Security Attribution Unit (SAU) of an ARMv8-M [`Cpu`](crate::Cpu)
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "camelCase")
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SauRegionsConfig {
+    /// Specify whether the SAU is enabled. The default is `true`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub enabled: Option<bool>,
+
+    /// Security privilege of the whole address space when the SAU is disabled
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub protection_when_disabled: Option<Protection>,
+
+    /// Preconfigured regions of the SAU
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub regions: Vec<SauRegion>,
+}
+
+impl SauRegionsConfig {
+    /// Create a new [`SauRegionsConfig`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set the enabled of the SAU configuration.
+    pub fn enabled(mut self, value: Option<bool>) -> Self {
+        self.enabled = value;
+        self
+    }
+    /// Set the protection_when_disabled of the SAU configuration.
+    pub fn protection_when_disabled(mut self, value: Option<Protection>) -> Self {
+        self.protection_when_disabled = value;
+        self
+    }
+    /// Set the regions of the SAU configuration.
+    pub fn regions(mut self, value: Vec<SauRegion>) -> Self {
+        self.regions = value;
+        self
+    }
+    /// Returns `true` if the SAU is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+    /// Validate the [`SauRegionsConfig`].
+    pub fn validate(&self, lvl: ValidateLevel) -> Result<(), SvdError> {
+        for region in &self.regions {
+            region.validate(lvl)?;
+        }
+        Ok(())
+    }
+}
+
+/// Region of the address space preconfigured in the SAU
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SauRegion {
+    /// Specify whether the region is enabled
+    pub enabled: bool,
+
+    /// Identifier of the region
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub name: Option<String>,
+
+    /// Start address of the region
+    pub base: u32,
+
+    /// End address of the region
+    pub limit: u32,
+
+    /// Security attribute of the region
+    pub access: SauAccess,
+}
+
+/// Security attribute of a [`SauRegion`]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SauAccess {
+    /// Non-secure
+    #[cfg_attr(feature = "serde", serde(rename = "n"))]
+    NonSecure,
+
+    /// Secure, callable from non-secure code
+    #[cfg_attr(feature = "serde", serde(rename = "c"))]
+    NonSecureCallable,
+}
+
+impl SauAccess {
+    /// Parse a string into an [`SauAccess`] value, returning [`Option::None`] if the string is not valid.
+    pub fn parse_str(s: &str) -> Option<Self> {
+        match s {
+            "n" => Some(Self::NonSecure),
+            "c" => Some(Self::NonSecureCallable),
+            _ => None,
+        }
+    }
+
+    /// Convert this [`SauAccess`] into a static string.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NonSecure => "n",
+            Self::NonSecureCallable => "c",
+        }
+    }
+}
+
+/// Builder for [`SauRegion`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SauRegionBuilder {
+    enabled: Option<bool>,
+    name: Option<String>,
+    base: Option<u32>,
+    limit: Option<u32>,
+    access: Option<SauAccess>,
+}
+
+impl From<SauRegion> for SauRegionBuilder {
+    fn from(r: SauRegion) -> Self {
+        Self {
+            enabled: Some(r.enabled),
+            name: r.name,
+            base: Some(r.base),
+            limit: Some(r.limit),
+            access: Some(r.access),
+        }
+    }
+}
+
+impl SauRegionBuilder {
+    /// Set the enabled of the region. The default is `true`
+    pub fn enabled(mut self, value: bool) -> Self {
+        self.enabled = Some(value);
+        self
+    }
+    /// Set the name of the region
+    pub fn name(mut self, value: Option<String>) -> Self {
+        self.name = value;
+        self
+    }
+    /// Set the base address of the region
+    pub fn base(mut self, value: u32) -> Self {
+        self.base = Some(value);
+        self
+    }
+    /// Set the limit address of the region
+    pub fn limit(mut self, value: u32) -> Self {
+        self.limit = Some(value);
+        self
+    }
+    /// Set the access of the region
+    pub fn access(mut self, value: SauAccess) -> Self {
+        self.access = Some(value);
+        self
+    }
+    /// Validate and build a [`SauRegion`].
+    pub fn build(self, lvl: ValidateLevel) -> Result<SauRegion, SvdError> {
+        let region = SauRegion {
+            enabled: self.enabled.unwrap_or(true),
+            name: self.name,
+            base: self
+                .base
+                .ok_or_else(|| BuildError::Uninitialized("base".to_string()))?,
+            limit: self
+                .limit
+                .ok_or_else(|| BuildError::Uninitialized("limit".to_string()))?,
+            access: self
+                .access
+                .ok_or_else(|| BuildError::Uninitialized("access".to_string()))?,
+        };
+        region.validate(lvl)?;
+        Ok(region)
+    }
+}
+
+impl SauRegion {
+    /// Make a builder for [`SauRegion`]
+    pub fn builder() -> SauRegionBuilder {
+        SauRegionBuilder::default()
+    }
+    /// Modify an existing [`SauRegion`] based on a [builder](SauRegionBuilder).
+    pub fn modify_from(
+        &mut self,
+        builder: SauRegionBuilder,
+        lvl: ValidateLevel,
+    ) -> Result<(), SvdError> {
+        if let Some(enabled) = builder.enabled {
+            self.enabled = enabled;
+        }
+        if builder.name.is_some() {
+            self.name = builder.name;
+        }
+        if let Some(base) = builder.base {
+            self.base = base;
+        }
+        if let Some(limit) = builder.limit {
+            self.limit = limit;
+        }
+        if let Some(access) = builder.access {
+            self.access = access;
+        }
+        self.validate(lvl)
+    }
+    /// Validate the [`SauRegion`].
+    ///
+    /// # Notes
+    ///
+    /// This doesn't do anything.
+    pub fn validate(&self, _lvl: ValidateLevel) -> Result<(), SvdError> {
+        Ok(())
+    }
+}
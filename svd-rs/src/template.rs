@@ -0,0 +1,119 @@
+//! Names with `%s` placeholder of arrays.
+//!
+//! Names of arrays contain `%s` which is replaced with the index of each element.
+//! `NAME[%s]` describes an array and `NAME%s` or `NA%sME` a list of elements.
+//! Functions of this module are used everywhere the placeholder is substituted
+//! or recognized, so they can't diverge.
+
+/// Placeholder of array index
+pub const PLACEHOLDER: &str = "%s";
+/// Placeholder of array index in names of arrays
+pub const ARRAY_PLACEHOLDER: &str = "[%s]";
+
+/// Errors of [`NameTemplate::parse`]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Name has more than one placeholder
+    #[error("Name `{0}` has more than one %s placeholder")]
+    MultiplePlaceholders(String),
+}
+
+/// Returns `true` if `text` has a placeholder
+pub fn has_placeholder(text: &str) -> bool {
+    text.contains(PLACEHOLDER)
+}
+
+/// Replace all placeholders in `text` with `index`. `[%s]` is replaced entirely.
+///
+/// Unlike [`NameTemplate::substitute`] it accepts any number of placeholders,
+/// so it is suitable for descriptions.
+pub fn substitute(text: &str, index: &str) -> String {
+    text.replace(ARRAY_PLACEHOLDER, index)
+        .replace(PLACEHOLDER, index)
+}
+
+/// How array elements are named
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArrayStyle {
+    /// `NAME[%s]`, an array
+    Array,
+    /// `NAME%s`, a list with index at the end
+    Suffix,
+    /// `NA%sME`, `NA[%s]ME` or `%sNAME`, a list with index inside of the name
+    Infix,
+}
+
+/// Name with a single `%s` placeholder
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NameTemplate {
+    name: String,
+    prefix: String,
+    suffix: String,
+    style: ArrayStyle,
+}
+
+impl NameTemplate {
+    /// Parse `name`. Returns `None` if it has no placeholder.
+    ///
+    /// Fails if there are several placeholders, which the specification doesn't allow
+    /// and which make matching instance names ambiguous.
+    pub fn parse(name: &str) -> Result<Option<Self>, Error> {
+        if !has_placeholder(name) {
+            return Ok(None);
+        }
+        if name.matches(PLACEHOLDER).count() > 1 {
+            return Err(Error::MultiplePlaceholders(name.into()));
+        }
+        // `[%s]` is replaced entirely wherever it is, like in `substitute`
+        let (prefix, suffix) = name
+            .split_once(ARRAY_PLACEHOLDER)
+            .or_else(|| name.split_once(PLACEHOLDER))
+            .unwrap();
+        let style = if name.ends_with(ARRAY_PLACEHOLDER) {
+            ArrayStyle::Array
+        } else if suffix.is_empty() {
+            ArrayStyle::Suffix
+        } else {
+            ArrayStyle::Infix
+        };
+        Ok(Some(Self {
+            name: name.into(),
+            prefix: prefix.into(),
+            suffix: suffix.into(),
+            style,
+        }))
+    }
+
+    /// Name of element with `index`. `[%s]` is replaced entirely
+    pub fn substitute(&self, index: &str) -> String {
+        format!("{}{}{}", self.prefix, index, self.suffix)
+    }
+
+    /// Index of element named `name`, if it is an element of this template.
+    ///
+    /// The template has only one placeholder, so the index is everything between
+    /// the parts before and after it, and is never empty. For example `CH%s_CTRL`
+    /// matches `CH10_CTRL` with index `10`, but not `CH1_CTRLX`.
+    pub fn match_instance(&self, name: &str) -> Option<String> {
+        let index = name
+            .strip_prefix(self.prefix.as_str())?
+            .strip_suffix(self.suffix.as_str())?;
+        (!index.is_empty()).then(|| index.into())
+    }
+
+    /// How array elements are named
+    pub fn style(&self) -> ArrayStyle {
+        self.style
+    }
+
+    /// Name without placeholder, and brackets around it
+    pub fn strip(&self) -> String {
+        format!("{}{}", self.prefix, self.suffix)
+    }
+}
+
+impl core::fmt::Display for NameTemplate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
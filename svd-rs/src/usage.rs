@@ -5,22 +5,17 @@
     derive(serde::Deserialize, serde::Serialize),
     serde(rename_all = "kebab-case")
 )]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Default)]
 pub enum Usage {
     /// Read
     Read,
     /// Write
     Write,
     /// Read & Write
+    #[default]
     ReadWrite,
 }
 
-impl Default for Usage {
-    fn default() -> Self {
-        Self::ReadWrite
-    }
-}
-
 impl Usage {
     /// Parse a string into an [`Usage`] value, returning [`Option::None`] if the string is not valid.
     pub fn parse_str(s: &str) -> Option<Self> {
@@ -0,0 +1,1042 @@
+//! Device-wide checks that need context of the whole device.
+//!
+//! Unlike `validate` methods of the SVD objects, these checks don't fail fast
+//! and report every problem found as a [`Diagnostic`].
+//!
+//! Checks which depend on register sizes run here rather than while parsing:
+//! registers without `size` inherit it from enclosing clusters, the peripheral
+//! or the device, which is only known once the whole device is parsed.
+
+use super::{
+    peripheral, register, Cluster, Device, Fix, InterruptTableConfig, MaybeArray, Peripheral,
+    PeripheralInfo, ProvenanceRecorder, Register, RegisterCluster, RegisterInfo, WidthAliasOptions,
+};
+use core::fmt;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// Severity of a [`Diagnostic`]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth knowing, nothing to fix
+    Info,
+    /// Suspicious, but can be intended
+    Warning,
+    /// Description is wrong
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Problem found by [`Device::validate_with`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Diagnostic {
+    /// Code of the rule which reported the problem, like `alignment.register`
+    pub code: &'static str,
+    /// Severity
+    pub severity: Severity,
+    /// Dot separated path of the element, like `PERIPHERAL.CLUSTER.REGISTER`
+    pub path: String,
+    /// Human readable description of the problem
+    pub message: String,
+    /// Change which fixes the problem, if it is unambiguous.
+    /// Apply it with [`Device::apply_fix`]
+    pub suggested_fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    /// Create new diagnostic
+    pub fn new(
+        code: &'static str,
+        severity: Severity,
+        path: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            code,
+            severity,
+            path: path.into(),
+            message: message.into(),
+            suggested_fix: None,
+        }
+    }
+
+    /// Set suggested fix
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.suggested_fix = Some(fix);
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            self.severity, self.code, self.path, self.message
+        )
+    }
+}
+
+/// Severity a rule reports with, set in [`ValidationConfig::severity_overrides`]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RuleSeverity {
+    /// Don't report
+    Off,
+    /// Report as [`Severity::Info`]
+    Info,
+    /// Report as [`Severity::Warning`]
+    Warning,
+    /// Report as [`Severity::Error`]
+    Error,
+}
+
+/// Code of diagnostic with number of suppressed diagnostics
+pub const SUPPRESSED_CODE: &str = "validation.suppressed";
+/// Code of diagnostic about suppression which matched nothing
+pub const UNUSED_SUPPRESSION_CODE: &str = "validation.unused_suppression";
+
+/// Hides diagnostics of a rule under some elements
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Suppression {
+    /// Rule code, `*` and `?` wildcards are allowed
+    pub code: String,
+    /// Element path pattern like `TIM*` or `UART?.CTRL`. It also hides diagnostics
+    /// of children of matched elements
+    pub path: String,
+}
+
+impl Suppression {
+    /// Create new suppression
+    pub fn new(code: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            path: path.into(),
+        }
+    }
+
+    /// Returns `true` if `diagnostic` is hidden by the suppression
+    pub fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        wildcard_match(&self.code, diagnostic.code)
+            && (wildcard_match(&self.path, &diagnostic.path)
+                || diagnostic
+                    .path
+                    .match_indices('.')
+                    .any(|(i, _)| wildcard_match(&self.path, &diagnostic.path[..i])))
+    }
+}
+
+/// Match `text` with `pattern` where `*` is any sequence of characters and `?` any character
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) =
+        (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    // position of the last `*` in pattern and of text matched by it
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Options of [`Device::validate_with`].
+///
+/// With the `serde` feature it can be read from a configuration file, missing
+/// options keep default values.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default)
+)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValidationConfig {
+    /// Report registers not aligned to their size as errors instead of warnings
+    pub misaligned_as_error: bool,
+    /// Peripherals with registers at any byte offset, not checked for alignment
+    pub packed_peripherals: Vec<String>,
+    /// Peripherals which legitimately share addresses with other peripherals
+    pub aliased_peripherals: Vec<String>,
+    /// Name suffix of secure peripheral aliases, `_S` by default
+    pub secure_suffix: String,
+    /// Name suffix of non-secure peripheral aliases, `_NS` by default
+    pub non_secure_suffix: String,
+    /// Detection of registers accessible with several widths, which are allowed to overlap
+    pub width_aliases: WidthAliasOptions,
+    /// Severities of rules by code, replacing severities rules report with
+    pub severity_overrides: BTreeMap<String, RuleSeverity>,
+    /// Diagnostics to hide. Number of hidden diagnostics and suppressions which
+    /// matched nothing are reported as [`Severity::Info`]
+    pub suppressions: Vec<Suppression>,
+    /// Hide diagnostics less severe than this. Doesn't hide reports about suppressions
+    pub min_severity: Option<Severity>,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        let security = InterruptTableConfig::default();
+        Self {
+            misaligned_as_error: false,
+            packed_peripherals: Vec::new(),
+            aliased_peripherals: Vec::new(),
+            secure_suffix: security.secure_suffix,
+            non_secure_suffix: security.non_secure_suffix,
+            width_aliases: WidthAliasOptions::default(),
+            severity_overrides: BTreeMap::new(),
+            suppressions: Vec::new(),
+            min_severity: None,
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Report registers not aligned to their size as errors instead of warnings
+    pub fn misaligned_as_error(mut self, val: bool) -> Self {
+        self.misaligned_as_error = val;
+        self
+    }
+
+    /// Set peripherals with registers at any byte offset
+    pub fn packed_peripherals(mut self, val: Vec<String>) -> Self {
+        self.packed_peripherals = val;
+        self
+    }
+
+    /// Set peripherals which legitimately share addresses with other peripherals
+    pub fn aliased_peripherals(mut self, val: Vec<String>) -> Self {
+        self.aliased_peripherals = val;
+        self
+    }
+
+    /// Name suffixes of secure and non-secure peripheral aliases, which are allowed
+    /// to share addresses
+    pub fn security_suffixes(mut self, secure: String, non_secure: String) -> Self {
+        self.secure_suffix = secure;
+        self.non_secure_suffix = non_secure;
+        self
+    }
+
+    /// Set detection options of registers accessible with several widths
+    pub fn width_aliases(mut self, val: WidthAliasOptions) -> Self {
+        self.width_aliases = val;
+        self
+    }
+
+    /// Override severity of rule `code`
+    pub fn severity(mut self, code: impl Into<String>, val: RuleSeverity) -> Self {
+        self.severity_overrides.insert(code.into(), val);
+        self
+    }
+
+    /// Hide diagnostics of rule `code` under elements matching `path`
+    pub fn suppress(mut self, code: impl Into<String>, path: impl Into<String>) -> Self {
+        self.suppressions.push(Suppression::new(code, path));
+        self
+    }
+
+    /// Hide diagnostics less severe than `val`
+    pub fn min_severity(mut self, val: Severity) -> Self {
+        self.min_severity = Some(val);
+        self
+    }
+}
+
+/// Checks builders run in `build_with`, like [`RegisterInfoBuilder::build_with`](super::RegisterInfoBuilder::build_with)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BuilderProfile {
+    /// Validate on build: run [`CONTEXT_FREE_RULES`] of [`Device::validate_with`]
+    /// on the built node and fail with all their diagnostics
+    Strict,
+    /// Construct, then validate: only check that required values are set and
+    /// that names of arrays have a `%s` placeholder. The rest is left to a later
+    /// [`Device::validate_with`] call
+    #[default]
+    Deferred,
+}
+
+/// Options of builders
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BuilderConfig {
+    /// Checks of `build_with`
+    pub profile: BuilderProfile,
+}
+
+impl BuilderConfig {
+    /// Checks of `build_with`
+    pub fn profile(mut self, val: BuilderProfile) -> Self {
+        self.profile = val;
+        self
+    }
+}
+
+/// Errors of building with [`BuilderProfile::Strict`]
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// Rules reported problems in the built node
+    #[error("{}", list(.0))]
+    Diagnostics(Vec<Diagnostic>),
+}
+
+fn list(diagnostics: &[Diagnostic]) -> String {
+    let list: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+    list.join("; ")
+}
+
+/// Returns `true` if `offset` in bytes is a multiple of register `size` in bits.
+///
+/// Sizes which are not a power of 2 multiple of 8 have no natural alignment and are always aligned.
+pub fn is_aligned(offset: u64, size: u32) -> bool {
+    if size < 8 || size % 8 != 0 || !size.is_power_of_two() {
+        return true;
+    }
+    offset % (size / 8) as u64 == 0
+}
+
+pub(crate) fn validate(device: &Device, config: &ValidationConfig) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for p in &device.peripherals {
+        if !config.packed_peripherals.contains(&p.name) {
+            check_alignment(device, p, config, &mut diagnostics);
+        }
+        check_dim(p, &p.name, &mut diagnostics);
+        let size = p
+            .default_register_properties
+            .size
+            .or(Some(device.default_register_size()));
+        check_tree(
+            p.registers.as_deref().unwrap_or(&[]),
+            &p.name,
+            size,
+            &mut diagnostics,
+        );
+        check_register_overlap(device, p, config, &mut diagnostics);
+    }
+    check_peripheral_overlap(device, config, &mut diagnostics);
+    check_derived_from(device, &mut diagnostics);
+    if let Some(cpu) = &device.cpu {
+        for conflict in cpu.capability_conflicts() {
+            diagnostics.push(Diagnostic::new(
+                "cpu.capability",
+                Severity::Warning,
+                &device.name,
+                conflict,
+            ));
+        }
+    }
+    filter(&device.name, diagnostics, config)
+}
+
+/// Codes of rules which only look at the checked registers and clusters.
+///
+/// [`BuilderProfile::Strict`] runs exactly these rules on built nodes. Rules which
+/// need the surrounding device are skipped: `alignment.register` (offset in the
+/// peripheral), `overlap.register`, `overlap.peripheral`, `base_address.duplicate`,
+/// `reference.derived_from` and `cpu.capability`.
+pub const CONTEXT_FREE_RULES: &[&str] = &[
+    "size.register",
+    "dim.increment",
+    "reset.mask",
+    "dim.index",
+    "name.duplicate",
+];
+
+/// Run [`CONTEXT_FREE_RULES`] on `children` of element at `path`,
+/// registers without `size` inherit `size`
+fn check_tree(
+    children: &[RegisterCluster],
+    path: &str,
+    size: Option<u32>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    check_field_bits(children, path, size, diagnostics);
+    check_increments(children, path, size, diagnostics);
+    check_reset_mask(children, path, diagnostics);
+    check_dim_index(children, path, diagnostics);
+    check_duplicate_names(children, path, diagnostics);
+}
+
+/// Check `node` built with `config`, see [`BuilderProfile`]
+pub(crate) fn check_built(node: &RegisterCluster, config: &BuilderConfig) -> Result<(), Error> {
+    match config.profile {
+        BuilderProfile::Deferred => Ok(()),
+        BuilderProfile::Strict => {
+            let mut diagnostics = Vec::new();
+            check_tree(std::slice::from_ref(node), "", None, &mut diagnostics);
+            if diagnostics.is_empty() {
+                return Ok(());
+            }
+            // paths start at the node itself
+            for d in &mut diagnostics {
+                d.path = d.path.trim_start_matches('.').into();
+            }
+            Err(Error::Diagnostics(diagnostics))
+        }
+    }
+}
+
+/// Apply severity overrides, suppressions and minimal severity of `config`
+fn filter(
+    device: &str,
+    diagnostics: Vec<Diagnostic>,
+    config: &ValidationConfig,
+) -> Vec<Diagnostic> {
+    let mut used = vec![false; config.suppressions.len()];
+    let mut suppressed = 0;
+    let mut result = Vec::with_capacity(diagnostics.len());
+    for mut d in diagnostics {
+        match config.severity_overrides.get(d.code) {
+            Some(RuleSeverity::Off) => continue,
+            Some(RuleSeverity::Info) => d.severity = Severity::Info,
+            Some(RuleSeverity::Warning) => d.severity = Severity::Warning,
+            Some(RuleSeverity::Error) => d.severity = Severity::Error,
+            None => {}
+        }
+        if let Some(i) = config.suppressions.iter().position(|s| s.matches(&d)) {
+            used[i] = true;
+            suppressed += 1;
+            continue;
+        }
+        if config.min_severity.map_or(true, |min| d.severity >= min) {
+            result.push(d);
+        }
+    }
+    if suppressed > 0 {
+        result.push(Diagnostic::new(
+            SUPPRESSED_CODE,
+            Severity::Info,
+            device,
+            format!("{} diagnostics suppressed", suppressed),
+        ));
+    }
+    for (s, _) in config
+        .suppressions
+        .iter()
+        .zip(used)
+        .filter(|(_, used)| !used)
+    {
+        result.push(Diagnostic::new(
+            UNUSED_SUPPRESSION_CODE,
+            Severity::Info,
+            &s.path,
+            format!("Suppression of `{}` matched nothing", s.code),
+        ));
+    }
+    result
+}
+
+/// Check that peripherals don't share addresses.
+///
+/// Address range of a peripheral ends with its last address block (of the parent if the
+/// peripheral is derived and has none). Secure and non-secure views of the same peripheral
+/// and peripherals naming each other in `alternatePeripheral` are allowed to overlap.
+fn check_peripheral_overlap(
+    device: &Device,
+    config: &ValidationConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let instances: Vec<Cow<PeripheralInfo>> = device
+        .peripherals
+        .iter()
+        .filter(|p| !config.aliased_peripherals.contains(&p.name))
+        .flat_map(|p| match p {
+            Peripheral::Single(info) => vec![Cow::Borrowed(info)],
+            Peripheral::Array(info, dim) => peripheral::expand(info, dim).map(Cow::Owned).collect(),
+        })
+        .collect();
+    let size = |p: &PeripheralInfo| {
+        let blocks = p.address_block.as_ref().or_else(|| {
+            p.derived_from
+                .as_ref()
+                .and_then(|d| device.get_peripheral(d))
+                .and_then(|d| d.address_block.as_ref())
+        });
+        blocks
+            .into_iter()
+            .flatten()
+            .map(|b| b.offset as u64 + b.size as u64)
+            .max()
+            .unwrap_or(0)
+    };
+    let security = InterruptTableConfig::default().security_suffixes(
+        config.secure_suffix.clone(),
+        config.non_secure_suffix.clone(),
+    );
+    for (i, b) in instances.iter().enumerate() {
+        for a in &instances[..i] {
+            if security.is_security_alias(a, b) {
+                continue;
+            }
+            let derived = |x: &PeripheralInfo, y: &PeripheralInfo| {
+                x.derived_from.as_deref() == Some(y.name.as_str())
+            };
+            if a.base_address == b.base_address {
+                let message = if derived(b, a) || derived(a, b) {
+                    let (parent, child) = if derived(b, a) { (a, b) } else { (b, a) };
+                    format!(
+                        "`{}` is derived from `{}` and has the same base address {:#x}",
+                        child.name, parent.name, child.base_address
+                    )
+                } else {
+                    format!(
+                        "`{}` and `{}` have the same base address {:#x}",
+                        a.name, b.name, b.base_address
+                    )
+                };
+                diagnostics.push(Diagnostic::new(
+                    "base_address.duplicate",
+                    Severity::Error,
+                    &b.name,
+                    message,
+                ));
+            } else if a.base_address < b.base_address.saturating_add(size(b))
+                && b.base_address < a.base_address.saturating_add(size(a))
+            {
+                let note = if derived(b, a) || derived(a, b) {
+                    ", one is derived from the other"
+                } else {
+                    ""
+                };
+                diagnostics.push(Diagnostic::new(
+                    "overlap.peripheral",
+                    Severity::Warning,
+                    &b.name,
+                    format!(
+                        "`{}` at {:#x} overlaps `{}` at {:#x}{}",
+                        b.name, b.base_address, a.name, a.base_address, note
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn check_alignment(
+    device: &Device,
+    p: &Peripheral,
+    config: &ValidationConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let size = p
+        .default_register_properties
+        .size
+        .or(Some(device.default_register_size()));
+    let severity = if config.misaligned_as_error {
+        Severity::Error
+    } else {
+        Severity::Warning
+    };
+    let mut check = |path: &str, r: &Register, offset: u64, size: Option<u32>, strides: &[u32]| {
+        let size = match r.properties.size.or(size) {
+            Some(size) => size,
+            None => return,
+        };
+        let path = format!("{}.{}", path, r.name);
+        if !is_aligned(offset, size) {
+            diagnostics.push(Diagnostic::new(
+                "alignment.register",
+                severity,
+                &path,
+                format!(
+                    "{}-bit register at offset {:#x} is not aligned to its size",
+                    size, offset
+                ),
+            ));
+        }
+        let own_stride = match r {
+            Register::Array(_, dim) => Some(dim.dim_increment),
+            Register::Single(_) => None,
+        };
+        if let Some(stride) = strides
+            .iter()
+            .copied()
+            .chain(own_stride)
+            .find(|s| !is_aligned(*s as u64, size))
+        {
+            diagnostics.push(Diagnostic::new(
+                "alignment.register",
+                severity,
+                &path,
+                format!(
+                    "array increment {:#x} is not a multiple of {}-bit register size",
+                    stride, size
+                ),
+            ));
+        }
+    };
+    let mut strides = Vec::new();
+    for_each_register(
+        p.registers.as_deref().unwrap_or(&[]),
+        &p.name,
+        0,
+        size,
+        &mut strides,
+        &mut check,
+    );
+}
+
+/// Check that registers placed directly in the peripheral don't share addresses.
+///
+/// Alternate registers and registers accessible with several widths
+/// (see [`PeripheralInfo::access_width_aliases`]) are allowed to overlap.
+fn check_register_overlap(
+    device: &Device,
+    p: &Peripheral,
+    config: &ValidationConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let default_size = p
+        .default_register_properties
+        .size
+        .unwrap_or_else(|| device.default_register_size());
+    let groups = p.access_width_aliases(&config.width_aliases);
+    let registers: Vec<Cow<RegisterInfo>> = p
+        .registers()
+        .flat_map(|r| match r {
+            Register::Single(info) => vec![Cow::Borrowed(info)],
+            Register::Array(info, dim) => register::expand(info, dim).map(Cow::Owned).collect(),
+        })
+        .collect();
+    let range = |r: &RegisterInfo| {
+        let start = r.address_offset as u64;
+        let bytes = (r.properties.size.unwrap_or(default_size) as u64 + 7) / 8;
+        start..start + bytes
+    };
+    let alternate =
+        |r: &RegisterInfo| r.alternate_group.is_some() || r.alternate_register.is_some();
+    for (i, b) in registers.iter().enumerate() {
+        for a in &registers[..i] {
+            let (ra, rb) = (range(a), range(b));
+            if ra.start < rb.end
+                && rb.start < ra.end
+                && !alternate(a)
+                && !alternate(b)
+                && !groups.iter().any(|g| g.contains(&a.name, &b.name))
+            {
+                diagnostics.push(Diagnostic::new(
+                    "overlap.register",
+                    Severity::Warning,
+                    format!("{}.{}", p.name, b.name),
+                    format!(
+                        "`{}` at offset {:#x} overlaps `{}` at offset {:#x}",
+                        b.name, b.address_offset, a.name, a.address_offset
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Check that register reset values have no bits outside of their reset masks
+fn check_reset_mask(children: &[RegisterCluster], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for_each_register(
+        children,
+        path,
+        0,
+        None,
+        &mut Vec::new(),
+        &mut |path, r, _, _, _| {
+            if let (Some(value), Some(mask)) = (r.properties.reset_value, r.properties.reset_mask) {
+                if value & !mask != 0 {
+                    let path = format!("{}.{}", path, r.name);
+                    diagnostics.push(
+                        Diagnostic::new(
+                            "reset.mask",
+                            Severity::Warning,
+                            &path,
+                            format!(
+                                "reset value {:#x} has bits outside of reset mask {:#x}",
+                                value, mask
+                            ),
+                        )
+                        .with_fix(Fix::SetText {
+                            path,
+                            element: "resetValue".into(),
+                            new_value: format!("{:#x}", value & mask),
+                        }),
+                    );
+                }
+            }
+        },
+    );
+}
+
+/// Check that array `m` at `path` has as many `dimIndex` entries as elements
+fn check_dim<T>(m: &MaybeArray<T>, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let dim = match m {
+        MaybeArray::Array(_, dim) => dim,
+        MaybeArray::Single(_) => return,
+    };
+    let indexes = match &dim.dim_index {
+        Some(indexes) if indexes.len() != dim.dim as usize => indexes,
+        _ => return,
+    };
+    let diagnostic = Diagnostic::new(
+        "dim.index",
+        Severity::Error,
+        path,
+        format!(
+            "`dimIndex` has {} entries, but `dim` is {}",
+            indexes.len(),
+            dim.dim
+        ),
+    );
+    // extra entries can be dropped, missing ones can't be guessed
+    diagnostics.push(if indexes.len() > dim.dim as usize {
+        diagnostic.with_fix(Fix::SetText {
+            path: path.into(),
+            element: "dimIndex".into(),
+            new_value: indexes[..dim.dim as usize].join(","),
+        })
+    } else {
+        diagnostic
+    });
+}
+
+/// Check that arrays in `children` have as many `dimIndex` entries as elements
+fn check_dim_index(children: &[RegisterCluster], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for rc in children {
+        let path = format!("{}.{}", path, rc.name());
+        match rc {
+            RegisterCluster::Register(r) => {
+                check_dim(r, &path, diagnostics);
+                for f in r.fields() {
+                    check_dim(f, &format!("{}.{}", path, f.name), diagnostics);
+                }
+            }
+            RegisterCluster::Cluster(c) => {
+                check_dim(c, &path, diagnostics);
+                check_dim_index(&c.children, &path, diagnostics);
+            }
+        }
+    }
+}
+
+/// Check that peripherals are derived from existing peripherals
+fn check_derived_from(device: &Device, diagnostics: &mut Vec<Diagnostic>) {
+    for p in &device.peripherals {
+        let parent = match &p.derived_from {
+            Some(parent) if device.get_peripheral(parent).is_none() => parent,
+            _ => continue,
+        };
+        let diagnostic = Diagnostic::new(
+            "reference.derived_from",
+            Severity::Error,
+            &p.name,
+            format!("derived from missing peripheral `{}`", parent),
+        );
+        let mut candidates = device
+            .peripherals
+            .iter()
+            .filter(|c| c.name != p.name && is_one_edit_apart(&c.name, parent));
+        diagnostics.push(match (candidates.next(), candidates.next()) {
+            (Some(candidate), None) => diagnostic.with_fix(Fix::SetText {
+                path: p.name.clone(),
+                element: "derivedFrom".into(),
+                new_value: candidate.name.clone(),
+            }),
+            _ => diagnostic,
+        });
+    }
+}
+
+/// Returns `true` if Levenshtein distance between `a` and `b` is 1
+fn is_one_edit_apart(a: &str, b: &str) -> bool {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (short, long) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+    let prefix = short
+        .iter()
+        .zip(long.iter())
+        .take_while(|(x, y)| x == y)
+        .count();
+    if short.len() == long.len() {
+        // substitution
+        prefix < short.len() && short[prefix + 1..] == long[prefix + 1..]
+    } else {
+        // insertion
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
+/// Check that fields fit in their registers.
+/// Registers without declared or inherited size are skipped.
+fn check_field_bits(
+    children: &[RegisterCluster],
+    path: &str,
+    size: Option<u32>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for_each_register(
+        children,
+        path,
+        0,
+        size,
+        &mut Vec::new(),
+        &mut |path, r, _, size, _| {
+            let size = match r.properties.size.or(size) {
+                Some(size) => size,
+                None => return,
+            };
+            if let (_, Some(adjustment)) = r.effective_size_considering_fields(size) {
+                diagnostics.push(Diagnostic::new(
+                    "size.register",
+                    Severity::Warning,
+                    format!("{}.{}", path, r.name),
+                    format!(
+                        "fields {} don't fit in {}-bit register, {} bits needed",
+                        adjustment.fields.join(", "),
+                        adjustment.declared,
+                        adjustment.required
+                    ),
+                ));
+            }
+        },
+    );
+}
+
+/// Check that elements of register and cluster arrays don't overlap each other
+fn check_increments(
+    children: &[RegisterCluster],
+    path: &str,
+    size: Option<u32>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => {
+                let (dim, size) = match (r, r.properties.size.or(size)) {
+                    (Register::Array(_, dim), Some(size)) if dim.dim > 1 => (dim, size),
+                    _ => continue,
+                };
+                if (dim.dim_increment as u64) < (size as u64 + 7) / 8 {
+                    diagnostics.push(Diagnostic::new(
+                        "dim.increment",
+                        Severity::Warning,
+                        format!("{}.{}", path, r.name),
+                        format!(
+                            "array increment {:#x} is smaller than {}-bit register",
+                            dim.dim_increment, size
+                        ),
+                    ));
+                }
+            }
+            RegisterCluster::Cluster(c) => {
+                let size = c.default_register_properties.size.or(size);
+                let path = format!("{}.{}", path, c.name);
+                if let Cluster::Array(_, dim) = c {
+                    match extent(&c.children, size) {
+                        Some(extent) if dim.dim > 1 && extent > dim.dim_increment as u64 => {
+                            diagnostics.push(Diagnostic::new(
+                                "dim.increment",
+                                Severity::Warning,
+                                &path,
+                                format!(
+                                    "array increment {:#x} is smaller than {:#x} bytes of cluster registers",
+                                    dim.dim_increment, extent
+                                ),
+                            ));
+                        }
+                        _ => {}
+                    }
+                }
+                check_increments(&c.children, &path, size, diagnostics);
+            }
+        }
+    }
+}
+
+/// Bytes from the start of a cluster to the end of its last register.
+/// `None` if size of a register is unknown
+fn extent(children: &[RegisterCluster], size: Option<u32>) -> Option<u64> {
+    // offset of the last element of an array
+    fn last<T>(m: &MaybeArray<T>) -> u64 {
+        match m {
+            MaybeArray::Array(_, d) => d.dim.saturating_sub(1) as u64 * d.dim_increment as u64,
+            MaybeArray::Single(_) => 0,
+        }
+    }
+    children.iter().try_fold(0, |max, rc| {
+        let end = match rc {
+            RegisterCluster::Register(r) => {
+                r.address_offset as u64 + last(r) + (r.properties.size.or(size)? as u64 + 7) / 8
+            }
+            RegisterCluster::Cluster(c) => {
+                let size = c.default_register_properties.size.or(size);
+                c.address_offset as u64 + last(c) + extent(&c.children, size)?
+            }
+        };
+        Some(max.max(end))
+    })
+}
+
+/// Check that registers and clusters of `children` have unique names.
+///
+/// Registers are compared by [effective name](RegisterInfo::effective_name), so
+/// registers of different alternate groups may share their name, but a register
+/// `A` in group `B` collides with a register named `A_B`.
+fn check_duplicate_names(
+    children: &[RegisterCluster],
+    path: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    // name and group of a register in an alternate group
+    fn alternate(rc: &RegisterCluster) -> Option<(&str, &str)> {
+        match rc {
+            RegisterCluster::Register(r) => r.alternate_group.as_deref().map(|g| (&*r.name, g)),
+            RegisterCluster::Cluster(_) => None,
+        }
+    }
+    let mut names: Vec<(Cow<str>, &RegisterCluster)> = Vec::with_capacity(children.len());
+    for rc in children {
+        let name = rc.effective_name();
+        if let Some((_, other)) = names.iter().find(|(n, _)| *n == name) {
+            let message = match alternate(rc).or_else(|| alternate(other)) {
+                Some((register, group)) if alternate(rc) != alternate(other) => format!(
+                    "`{}` of register `{}` in alternate group `{}` collides with another name",
+                    name, register, group
+                ),
+                _ => format!("`{}` is defined more than once", name),
+            };
+            diagnostics.push(Diagnostic::new(
+                "name.duplicate",
+                Severity::Error,
+                format!("{}.{}", path, name),
+                message,
+            ));
+        }
+        if let RegisterCluster::Cluster(c) = rc {
+            check_duplicate_names(&c.children, &format!("{}.{}", path, name), diagnostics);
+        }
+        names.push((name, rc));
+    }
+}
+
+/// Widen registers whose fields don't fit in their size, see [`Device::widen_registers_to_fields`]
+pub(crate) fn widen_registers(
+    device: &mut Device,
+    provenance: &mut ProvenanceRecorder,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let device_size = Some(device.default_register_size());
+    for p in &mut device.peripherals {
+        let size = p.default_register_properties.size.or(device_size);
+        let path = p.name.clone();
+        if let Some(registers) = p.registers.as_mut() {
+            for_each_register_mut(registers, &path, size, &mut |path, r, size| {
+                let size = match r.properties.size.or(size) {
+                    Some(size) => size,
+                    None => return,
+                };
+                if let Some(adjustment) = r.widen_to_fields(size) {
+                    let message = format!(
+                        "size changed from {} to {} bits to contain fields {}",
+                        adjustment.declared,
+                        adjustment.required,
+                        adjustment.fields.join(", ")
+                    );
+                    let path = format!("{}.{}", path, r.name);
+                    provenance.record("widen_registers_to_fields", || {
+                        (path.clone(), message.clone())
+                    });
+                    diagnostics.push(Diagnostic::new(
+                        "size.register",
+                        Severity::Info,
+                        path,
+                        message,
+                    ));
+                }
+            });
+        }
+    }
+    diagnostics
+}
+
+fn for_each_register_mut(
+    children: &mut [RegisterCluster],
+    path: &str,
+    size: Option<u32>,
+    f: &mut impl FnMut(&str, &mut Register, Option<u32>),
+) {
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => f(path, r, size),
+            RegisterCluster::Cluster(c) => {
+                let size = c.default_register_properties.size.or(size);
+                let path = format!("{}.{}", path, c.name);
+                for_each_register_mut(&mut c.children, &path, size, f);
+            }
+        }
+    }
+}
+
+/// Call `f` for every register with its path, offset in peripheral,
+/// inherited size and increments of enclosing cluster arrays.
+/// Only first element of cluster arrays is visited.
+pub(crate) fn for_each_register<'a>(
+    children: &'a [RegisterCluster],
+    path: &str,
+    offset: u64,
+    size: Option<u32>,
+    strides: &mut Vec<u32>,
+    f: &mut impl FnMut(&str, &'a Register, u64, Option<u32>, &[u32]),
+) {
+    for rc in children {
+        match rc {
+            RegisterCluster::Register(r) => {
+                f(path, r, offset + r.address_offset as u64, size, strides)
+            }
+            RegisterCluster::Cluster(c) => {
+                let size = c.default_register_properties.size.or(size);
+                let offset = offset + c.address_offset as u64;
+                let path = format!("{}.{}", path, c.name);
+                if let Cluster::Array(_, dim) = c {
+                    strides.push(dim.dim_increment);
+                    for_each_register(&c.children, &path, offset, size, strides, f);
+                    strides.pop();
+                } else {
+                    for_each_register(&c.children, &path, offset, size, strides, f);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Registers accessible with several widths.
+//!
+//! Some devices map the same register at the same address several times with
+//! different sizes, for example 32-bit `DATA`, 16-bit `DATAH` and 8-bit `DATAB`,
+//! so it can be accessed by words, halfwords or bytes. Fields of such registers
+//! describe the same bits, so they are aliases rather than conflicting registers.
+
+use super::{Field, PeripheralInfo, Register, RegisterInfo};
+
+/// Options of [`PeripheralInfo::access_width_aliases`]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default)
+)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WidthAliasOptions {
+    /// Only group registers whose names are the name of the widest one with a
+    /// width suffix like `B`, `H`, `_BYTE` or `_HALFWORD`, optionally followed by index
+    pub require_name_hint: bool,
+}
+
+impl WidthAliasOptions {
+    /// Only group registers with width suffixes in names
+    pub fn require_name_hint(mut self, val: bool) -> Self {
+        self.require_name_hint = val;
+        self
+    }
+}
+
+/// Registers which describe the same bits with different access widths
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WidthAliasGroup {
+    /// Name of the widest register
+    pub primary: String,
+    /// Names of narrower registers inside of the primary one
+    pub aliases: Vec<String>,
+}
+
+impl WidthAliasGroup {
+    /// Returns `true` if both registers belong to the group
+    pub fn contains(&self, a: &str, b: &str) -> bool {
+        let has = |name: &str| self.primary == name || self.aliases.iter().any(|n| n == name);
+        has(a) && has(b)
+    }
+}
+
+const NAME_HINTS: &[&str] = &["B", "H", "W", "_B", "_H", "_BYTE", "_HALF", "_HALFWORD"];
+
+impl PeripheralInfo {
+    /// Find registers of this peripheral accessible with several widths.
+    ///
+    /// Registers are grouped when a narrower register is placed fully inside of a
+    /// wider one, both are 8, 16, 32 or 64 bits and fields of the narrower register
+    /// have the same bits as fields of the wider one in the overlapping part
+    /// (little endian byte order). Registers without fields are never grouped.
+    /// The widest register of a group is the primary one.
+    ///
+    /// Only single registers placed directly in the peripheral are checked.
+    /// Registers without size are considered 32-bit.
+    pub fn access_width_aliases(&self, options: &WidthAliasOptions) -> Vec<WidthAliasGroup> {
+        let default_size = self.default_register_properties.size.unwrap_or(32);
+        let mut registers: Vec<(&RegisterInfo, u32)> = self
+            .registers()
+            .filter_map(|r| match r {
+                Register::Single(info) => {
+                    Some((info, info.properties.size.unwrap_or(default_size)))
+                }
+                Register::Array(..) => None,
+            })
+            .filter(|(_, size)| matches!(size, 8 | 16 | 32 | 64))
+            .collect();
+        // stable sort keeps document order for registers of the same size
+        registers.sort_by_key(|(_, size)| core::cmp::Reverse(*size));
+
+        let mut grouped = vec![false; registers.len()];
+        let mut groups = Vec::new();
+        for (i, &(primary, size)) in registers.iter().enumerate() {
+            if grouped[i] {
+                continue;
+            }
+            let mut aliases = Vec::new();
+            for (j, &(alias, alias_size)) in registers.iter().enumerate().skip(i + 1) {
+                if !grouped[j]
+                    && alias_size < size
+                    && is_alias(primary, size, alias, alias_size)
+                    && (!options.require_name_hint || has_name_hint(&primary.name, &alias.name))
+                {
+                    grouped[j] = true;
+                    aliases.push(alias.name.clone());
+                }
+            }
+            if !aliases.is_empty() {
+                grouped[i] = true;
+                groups.push(WidthAliasGroup {
+                    primary: primary.name.clone(),
+                    aliases,
+                });
+            }
+        }
+        groups
+    }
+}
+
+fn is_alias(primary: &RegisterInfo, size: u32, alias: &RegisterInfo, alias_size: u32) -> bool {
+    let start = primary.address_offset as u64;
+    let end = start + (size / 8) as u64;
+    let offset = alias.address_offset as u64;
+    if offset < start || offset + (alias_size / 8) as u64 > end {
+        return false;
+    }
+    let shift = ((offset - start) * 8) as u32;
+    let (first, last) = (shift, shift + alias_size - 1);
+    // bits of fields inside of the window, including parts of fields covering it
+    let bits = |r: &RegisterInfo, shift: u32| -> Vec<(u32, u32)> {
+        let mut bits: Vec<_> = r
+            .fields()
+            .filter_map(|f| match f {
+                Field::Single(f) => Some((f.lsb() + shift, f.msb() + shift)),
+                Field::Array(..) => None,
+            })
+            .filter(|&(lsb, msb)| lsb <= last && msb >= first)
+            .map(|(lsb, msb)| (lsb.max(first), msb.min(last)))
+            .collect();
+        bits.sort_unstable();
+        bits
+    };
+    let alias_bits = bits(alias, shift);
+    !alias_bits.is_empty() && bits(primary, 0) == alias_bits
+}
+
+fn has_name_hint(primary: &str, alias: &str) -> bool {
+    alias
+        .strip_prefix(primary)
+        .map(|suffix| suffix.trim_end_matches(|c: char| c.is_ascii_digit()))
+        .map_or(false, |suffix| NAME_HINTS.contains(&suffix))
+}
@@ -33,3 +33,23 @@ fn decode_encode() {
 
     run_test::<Access>(&tests[..], None, None);
 }
+
+#[test]
+fn from_str_display() {
+    // access, readable, writable
+    let all = [
+        (Access::ReadOnly, true, false),
+        (Access::ReadWrite, true, true),
+        (Access::ReadWriteOnce, true, true),
+        (Access::WriteOnly, false, true),
+        (Access::WriteOnce, false, true),
+    ];
+    for (access, readable, writable) in all {
+        let text = access.to_string();
+        assert_eq!(text.parse::<Access>(), Ok(access));
+        assert_eq!(access.can_read(), readable, "{}", text);
+        assert_eq!(access.can_write(), writable, "{}", text);
+    }
+    let err = "read-writeonce".parse::<Access>().unwrap_err();
+    assert_eq!(err.to_string(), "Unknown access `read-writeonce`");
+}
@@ -1,5 +1,7 @@
 use super::run_test;
 use crate::svd::{AddressBlock, AddressBlockUsage, ValidateLevel};
+use roxmltree::Document;
+use svd_parser::Parse;
 
 #[test]
 fn decode_encode() {
@@ -30,3 +32,68 @@ fn decode_encode() {
 
     run_test::<AddressBlock>(&tests[..], Some(parse_config), Some(encode_config));
 }
+
+#[test]
+fn usage() {
+    let config = svd_parser::Config::default();
+    for (text, usage) in [
+        ("registers", AddressBlockUsage::Registers),
+        ("buffer", AddressBlockUsage::Buffer),
+        ("reserved", AddressBlockUsage::Reserved),
+    ] {
+        let xml = format!("<usage>{}</usage>", text);
+        let tree = Document::parse(&xml).unwrap();
+        assert_eq!(
+            AddressBlockUsage::parse(&tree.root_element(), &config).unwrap(),
+            usage
+        );
+    }
+
+    let xml = "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>UART</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <addressBlock>
+                        <offset>0</offset>
+                        <size>0x100</size>
+                        <usage>register</usage>
+                    </addressBlock>
+                </peripheral>
+            </peripherals>
+        </device>";
+    let error = format!("{:#}", svd_parser::parse(xml).unwrap_err());
+    assert!(error.contains("peripheral `UART`"), "{}", error);
+    assert!(
+        error.contains("Unknown addressBlock usage `register`"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn scaled_size() {
+    let size = |text: &str| {
+        let xml = format!(
+            "<addressBlock>
+                <offset>0</offset>
+                <size>{}</size>
+                <usage>registers</usage>
+            </addressBlock>",
+            text
+        );
+        let tree = Document::parse(&xml).unwrap();
+        AddressBlock::parse(&tree.root_element(), &Default::default()).map(|b| b.size)
+    };
+
+    assert_eq!(size("0x400").unwrap(), 0x400);
+    assert_eq!(size("1k").unwrap(), 0x400);
+    assert_eq!(size("64K").unwrap(), 0x1_0000);
+    assert_eq!(size("1M").unwrap(), 0x10_0000);
+    assert_eq!(size("2g").unwrap(), 0x8000_0000);
+
+    let err = size("8G").unwrap_err().to_string();
+    assert_eq!(err, "Number `8G` does not fit in 32 bits");
+}
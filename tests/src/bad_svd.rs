@@ -14,3 +14,24 @@ fn arm_sample_faulty() {
         panic!()
     }
 }
+
+#[test]
+fn error_size() {
+    // errors refer to the failed node by id instead of copying it
+    assert!(std::mem::size_of::<svd::SVDErrorAt>() <= 64);
+}
+
+#[test]
+fn unexpected_tag() {
+    let xml = "<enumeratedValues><enumeratedValue><name>A</name><value>0</value></enumeratedValue><field/></enumeratedValues>";
+    let tree = roxmltree::Document::parse(xml).unwrap();
+    let err = <svd::svd::EnumeratedValues as svd::Parse>::parse(
+        &tree.root_element(),
+        &Default::default(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected a <enumeratedValue> tag, found <field>"
+    );
+}
@@ -1,21 +1,23 @@
-use crate::parse::Parse;
-use crate::svd::{BitRange, BitRangeType};
-use xmltree::Element;
+use super::run_test;
+use crate::svd::{BitRange, BitRangeType, Field, FieldInfo, ValidateLevel};
+use roxmltree::Document;
+use svd_parser::Parse;
+
+fn parse(xml: &str) -> Result<BitRange, svd_parser::SVDErrorAt> {
+    let tree = Document::parse(xml).unwrap();
+    BitRange::parse(&tree.root_element(), &Default::default())
+}
 
 #[test]
-fn decode_encode() {
-    let types = vec![
+fn decode() {
+    let types = [
         (
             BitRange {
                 offset: 16,
                 width: 4,
                 range_type: BitRangeType::BitRange,
             },
-            String::from(
-                "
-            <fake><bitRange>[19:16]</bitRange></fake>
-        ",
-            ),
+            "<fake><bitRange>[19:16]</bitRange></fake>",
         ),
         (
             BitRange {
@@ -23,11 +25,7 @@ fn decode_encode() {
                 width: 4,
                 range_type: BitRangeType::OffsetWidth,
             },
-            String::from(
-                "
-            <fake><bitOffset>16</bitOffset><bitWidth>4</bitWidth></fake>
-        ",
-            ),
+            "<fake><bitOffset>16</bitOffset><bitWidth>4</bitWidth></fake>",
         ),
         (
             BitRange {
@@ -35,20 +33,89 @@ fn decode_encode() {
                 width: 4,
                 range_type: BitRangeType::MsbLsb,
             },
-            String::from(
-                "
-            <fake><lsb>16</lsb><msb>19</msb></fake>
-        ",
-            ),
+            "<fake><lsb>16</lsb><msb>19</msb></fake>",
+        ),
+    ];
+
+    for (expected, xml) in types {
+        assert_eq!(parse(xml).unwrap(), expected, "{}", xml);
+    }
+}
+
+#[test]
+fn bit_range_syntax() {
+    let expected = BitRange {
+        offset: 4,
+        width: 4,
+        range_type: BitRangeType::BitRange,
+    };
+    for text in ["[7:4]", " [7 : 4] ", "[ 7:4 ]"] {
+        let xml = format!("<fake><bitRange>{}</bitRange></fake>", text);
+        assert_eq!(parse(&xml).unwrap(), expected, "{}", text);
+        assert_eq!(BitRange::from_bit_range(text), Some(expected), "{}", text);
+    }
+    for text in ["[4:7]", "7:4", "[7:4", "[7]", "[7:4:0]", "[a:4]"] {
+        let xml = format!("<fake><bitRange>{}</bitRange></fake>", text);
+        parse(&xml).expect_err(text);
+        assert_eq!(BitRange::from_bit_range(text), None, "{}", text);
+    }
+}
+
+#[test]
+fn encode_keeps_syntax() {
+    let field = |bit_range| {
+        Field::Single(
+            FieldInfo::builder()
+                .name("MODE".to_string())
+                .bit_range(bit_range)
+                .build(ValidateLevel::Strict)
+                .unwrap(),
+        )
+    };
+    let tests = vec![
+        (
+            field(BitRange::from_bit_range("[7:4]").unwrap()),
+            "<field><name>MODE</name><bitRange>[ 7 : 4 ]</bitRange></field>",
+            "<field><name>MODE</name><bitRange>[7:4]</bitRange></field>",
+        ),
+        (
+            field(BitRange::from_msb_lsb(7, 4)),
+            "<field><name>MODE</name><lsb>4</lsb><msb>7</msb></field>",
+            "<field><name>MODE</name><lsb>4</lsb><msb>7</msb></field>",
+        ),
+        (
+            field(BitRange::from_offset_width(4, 4)),
+            "<field><name>MODE</name><bitOffset>4</bitOffset><bitWidth>4</bitWidth></field>",
+            "<field><name>MODE</name><bitOffset>4</bitOffset><bitWidth>4</bitWidth></field>",
         ),
     ];
 
-    for (a, s) in types {
-        let tree1 = Element::parse(s.as_bytes()).unwrap();
-        let value = BitRange::parse(&tree1).unwrap();
-        assert_eq!(value, a, "Parsing `{}` expected `{:?}`", s, a);
-        let mut tree2 = Element::new("fake");
-        tree2.children = value.encode().unwrap();
-        assert_eq!(tree1, tree2, "Encoding {:?} expected {}", a, s);
+    run_test::<Field>(&tests[..], None, None);
+}
+
+#[test]
+fn mask() {
+    let masks = [
+        (BitRange::from_offset_width(0, 1), 0x1),
+        (BitRange::from_offset_width(5, 1), 0x20),
+        (BitRange::from_offset_width(4, 4), 0xF0),
+        (BitRange::from_offset_width(0, 32), 0xFFFF_FFFF),
+        (BitRange::from_offset_width(31, 1), 0x8000_0000),
+        (BitRange::from_offset_width(16, 16), 0xFFFF_0000),
+        (BitRange::from_offset_width(0, 64), u64::MAX),
+        (BitRange::from_offset_width(63, 1), 1 << 63),
+        (BitRange::from_offset_width(4, 0), 0),
+    ];
+    for (bits, mask) in masks {
+        assert_eq!(bits.mask(), mask, "{:?}", bits);
     }
 }
+
+#[test]
+fn display() {
+    let bits = BitRange::from_offset_width(4, 4);
+    assert_eq!((bits.lsb(), bits.msb()), (4, 7));
+    assert_eq!(bits.to_string(), "[7:4]");
+    assert_eq!(BitRange::from_msb_lsb(31, 0).to_string(), "[31:0]");
+    assert_eq!(BitRange::from_offset_width(3, 1).to_string(), "[3:3]");
+}
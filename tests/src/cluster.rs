@@ -0,0 +1,117 @@
+use super::run_test;
+use crate::svd::{
+    Cluster, ClusterInfo, DimElement, Register, RegisterCluster, RegisterInfo, ValidateLevel,
+};
+
+#[test]
+fn decode_encode() {
+    let parse_config = svd_parser::Config::default().validate_level(ValidateLevel::Strict);
+
+    // description is optional, some vendor files omit it
+    let tests = vec![(
+        ClusterInfo::builder()
+            .name("CH".to_string())
+            .address_offset(0x10)
+            .children(vec![RegisterInfo::builder()
+                .name("CTRL".to_string())
+                .address_offset(0)
+                .build(ValidateLevel::Strict)
+                .unwrap()
+                .single()
+                .into()])
+            .build(ValidateLevel::Strict)
+            .unwrap()
+            .single(),
+        "
+        <cluster>
+            <name>CH</name>
+            <addressOffset>0x10</addressOffset>
+            <register>
+                <name>CTRL</name>
+                <addressOffset>0x0</addressOffset>
+            </register>
+        </cluster>",
+        "
+        <cluster>
+            <name>CH</name>
+            <addressOffset>0x10</addressOffset>
+            <register>
+                <name>CTRL</name>
+                <addressOffset>0x0</addressOffset>
+            </register>
+        </cluster>",
+    )];
+
+    run_test::<Cluster>(&tests[..], Some(parse_config), None);
+}
+
+#[test]
+fn register_cluster_conversion() {
+    let register = RegisterInfo::builder()
+        .name("CTRL".to_string())
+        .address_offset(0)
+        .build(ValidateLevel::Strict)
+        .unwrap()
+        .single();
+    let cluster = ClusterInfo::builder()
+        .name("CH".to_string())
+        .address_offset(0x10)
+        .children(vec![register.clone().into()])
+        .build(ValidateLevel::Strict)
+        .unwrap()
+        .single();
+
+    let rc = RegisterCluster::from(register.clone());
+    assert_eq!(Register::try_from(rc.clone()), Ok(register));
+    assert_eq!(Cluster::try_from(rc.clone()), Err(rc));
+
+    let rc = RegisterCluster::from(cluster.clone());
+    assert_eq!(Cluster::try_from(rc.clone()), Ok(cluster));
+    assert_eq!(Register::try_from(rc.clone()), Err(rc));
+}
+
+#[test]
+fn expand_nested() {
+    let register = RegisterInfo::builder()
+        .name("CTRL%s".to_string())
+        .address_offset(0)
+        .build(ValidateLevel::Strict)
+        .unwrap()
+        .array(
+            DimElement::builder()
+                .dim(2)
+                .dim_increment(4)
+                .build(ValidateLevel::Strict)
+                .unwrap(),
+        );
+    let cluster = ClusterInfo::builder()
+        .name("CH%s".to_string())
+        .address_offset(0x10)
+        .children(vec![register.into()])
+        .build(ValidateLevel::Strict)
+        .unwrap()
+        .array(
+            DimElement::builder()
+                .dim(2)
+                .dim_increment(0x20)
+                .build(ValidateLevel::Strict)
+                .unwrap(),
+        );
+
+    let expanded = cluster.expand().unwrap();
+    let layout: Vec<_> = expanded
+        .iter()
+        .map(|c| {
+            let registers: Vec<_> = c
+                .registers()
+                .map(|r| (r.is_single(), r.name.as_str(), r.address_offset))
+                .collect();
+            (c.name.as_str(), c.address_offset, registers)
+        })
+        .collect();
+    let registers = vec![(true, "CTRL0", 0), (true, "CTRL1", 4)];
+    assert_eq!(
+        layout,
+        [("CH0", 0x10, registers.clone()), ("CH1", 0x30, registers)]
+    );
+}
@@ -0,0 +1,62 @@
+//! Deprecated adapters must keep behavior of the releases they come from
+#![allow(deprecated)]
+
+use svd_parser as parser;
+
+const DEVICE: &str = "
+    <device>
+        <name>TEST</name>
+        <peripherals>
+            <peripheral>
+                <name>TIMER</name>
+                <baseAddress>0x40000000</baseAddress>
+                <registers>
+                    <register><name>CR</name><addressOffset>0x0</addressOffset></register>
+                    <cluster>
+                        <name>CH</name>
+                        <addressOffset>0x10</addressOffset>
+                        <register><name>CCR</name><addressOffset>0x0</addressOffset></register>
+                        <cluster>
+                            <name>DMA</name>
+                            <addressOffset>0x4</addressOffset>
+                            <register><name>ADDR</name><addressOffset>0x0</addressOffset></register>
+                        </cluster>
+                        <register><name>CCMR</name><addressOffset>0x8</addressOffset></register>
+                    </cluster>
+                    <register><name>SR</name><addressOffset>0x4</addressOffset></register>
+                </registers>
+            </peripheral>
+        </peripherals>
+    </device>";
+
+/// Order of `reg_iter` in 0.14.8
+const REGISTERS: [&str; 5] = ["CR", "CCR", "ADDR", "CCMR", "SR"];
+
+#[test]
+fn reg_iter() {
+    let mut device = parser::parse(DEVICE).unwrap();
+    let p = &mut device.peripherals[0];
+
+    let names: Vec<_> = p.reg_iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, REGISTERS);
+    assert!(p.reg_iter().eq(p.all_registers()));
+
+    let c = p.get_cluster("CH").unwrap();
+    let names: Vec<_> = c.reg_iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, REGISTERS[1..4]);
+}
+
+#[test]
+fn reg_iter_mut() {
+    let mut device = parser::parse(DEVICE).unwrap();
+    let p = &mut device.peripherals[0];
+
+    for r in p.reg_iter_mut() {
+        r.name.push('_');
+    }
+    for r in p.get_mut_cluster("CH").unwrap().reg_iter_mut() {
+        r.name.push('_');
+    }
+    let names: Vec<_> = p.all_registers().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["CR_", "CCR__", "ADDR__", "CCMR__", "SR_"]);
+}
@@ -0,0 +1,425 @@
+//! Conformance matrix over `data/corpus`: minimal SVD files, one per schema feature.
+//!
+//! Every fixture is parsed in strict mode and compared with a structure built by hand,
+//! encoded and parsed again, and validated without diagnostics. A new feature
+//! adds its fixture and a row to [`CASES`].
+
+use crate::svd::{
+    Access, AddressBlock, AddressBlockUsage, BitRange, Cluster, ClusterInfo, Cpu, Device,
+    DimElement, Endian, EnumeratedValue, EnumeratedValues, Field, FieldInfo, Interrupt,
+    ModifiedWriteValues, PeripheralInfo, Protection, ReadAction, RegisterCluster, RegisterInfo,
+    RegisterProperties, SauAccess, SauRegion, SauRegionsConfig, Usage, ValidateLevel,
+    ValidationConfig, WriteConstraint, WriteConstraintRange,
+};
+use std::path::Path;
+use svd_encoder::encode;
+use svd_parser as parser;
+
+const LVL: ValidateLevel = ValidateLevel::Strict;
+
+/// Check of the parsed device against the expected structure
+type Check = fn(&Device);
+
+/// Fixture files and their checks
+const CASES: &[(&str, Check)] = &[
+    ("basic_device.svd", basic_device),
+    ("cpu_sau.svd", cpu_sau),
+    ("derived_peripheral.svd", derived_peripheral),
+    ("register_array_dimindex.svd", register_array_dimindex),
+    ("nested_cluster_array.svd", nested_cluster_array),
+    ("field_array.svd", field_array),
+    ("enumerated_values.svd", enumerated_values),
+    ("write_constraint.svd", write_constraint),
+    ("modified_write_values.svd", modified_write_values),
+    ("alternate_group.svd", alternate_group),
+    ("address_block_buffer.svd", address_block_buffer),
+    ("protection.svd", protection),
+    ("addresses_64bit.svd", addresses_64bit),
+    ("binary_values.svd", binary_values),
+];
+
+#[test]
+fn corpus() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/corpus");
+    let config = parser::Config::default().validate_level(LVL);
+    for (file, check) in CASES {
+        let xml = std::fs::read_to_string(dir.join(file)).unwrap();
+        let device = parser::parse_with_config(&xml, &config)
+            .unwrap_or_else(|e| panic!("{}: {:#}", file, e));
+        check(&device);
+
+        let encoded = encode(&device).unwrap();
+        let reparsed = parser::parse_with_config(&encoded, &config)
+            .unwrap_or_else(|e| panic!("{}: encoded: {:#}", file, e));
+        assert_eq!(reparsed, device, "{}: round trip", file);
+
+        assert_eq!(
+            device.validate_with(&ValidationConfig::default()),
+            [],
+            "{}",
+            file
+        );
+    }
+}
+
+#[test]
+fn corpus_is_complete() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/corpus");
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    let mut cases: Vec<&str> = CASES.iter().map(|(file, _)| *file).collect();
+    cases.sort();
+    assert_eq!(files, cases, "every fixture needs a row in `CASES`");
+}
+
+fn peripheral(name: &str, base_address: u64, registers: Vec<RegisterCluster>) -> PeripheralInfo {
+    PeripheralInfo::builder()
+        .name(name.into())
+        .base_address(base_address)
+        .registers((!registers.is_empty()).then_some(registers))
+        .build(LVL)
+        .unwrap()
+}
+
+fn register(name: &str, address_offset: u32, fields: Vec<Field>) -> RegisterInfo {
+    RegisterInfo::builder()
+        .name(name.into())
+        .address_offset(address_offset)
+        .fields((!fields.is_empty()).then_some(fields))
+        .build(LVL)
+        .unwrap()
+}
+
+fn field(name: &str, bit_range: BitRange) -> FieldInfo {
+    FieldInfo::builder()
+        .name(name.into())
+        .bit_range(bit_range)
+        .build(LVL)
+        .unwrap()
+}
+
+fn dim(dim: u32, dim_increment: u32) -> DimElement {
+    DimElement::builder()
+        .dim(dim)
+        .dim_increment(dim_increment)
+        .build(LVL)
+        .unwrap()
+}
+
+fn address_block(offset: u32, size: u32, usage: AddressBlockUsage) -> AddressBlock {
+    AddressBlock::builder()
+        .offset(offset)
+        .size(size)
+        .usage(usage)
+        .build(LVL)
+        .unwrap()
+}
+
+fn enumerated_value(name: &str, value: u64) -> EnumeratedValue {
+    EnumeratedValue::builder()
+        .name(name.into())
+        .value(Some(value))
+        .build(LVL)
+        .unwrap()
+}
+
+fn basic_device(device: &Device) {
+    let en = FieldInfo::builder()
+        .name("EN".into())
+        .description(Some("Enable".into()))
+        .bit_range(BitRange::from_offset_width(0, 1))
+        .build(LVL)
+        .unwrap();
+    let cr = RegisterInfo::builder()
+        .name("CR".into())
+        .display_name(Some("Control".into()))
+        .description(Some("Control register".into()))
+        .address_offset(0)
+        .fields(Some(vec![en.single()]))
+        .build(LVL)
+        .unwrap();
+    let timer = PeripheralInfo::builder()
+        .name("TIMER".into())
+        .version(Some("2.1".into()))
+        .description(Some("Timer".into()))
+        .group_name(Some("TIM".into()))
+        .base_address(0x4000_0000)
+        .address_block(Some(vec![address_block(
+            0,
+            0x100,
+            AddressBlockUsage::Registers,
+        )]))
+        .interrupt(Some(vec![Interrupt::builder()
+            .name("TIMER".into())
+            .description(Some("Timer interrupt".into()))
+            .value(3)
+            .build(LVL)
+            .unwrap()]))
+        .registers(Some(vec![cr.single().into()]))
+        .build(LVL)
+        .unwrap();
+    let expected = Device::builder()
+        .vendor(Some("ACME".into()))
+        .vendor_id(Some("ACME".into()))
+        .name("BASIC".into())
+        .series(Some("ACME0".into()))
+        .version(Some("1.0".into()))
+        .description(Some("Device with one peripheral".into()))
+        .license_text(Some("Public domain".into()))
+        .header_system_filename(Some("system_basic".into()))
+        .header_definitions_prefix(Some("ACME_".into()))
+        .address_unit_bits(8)
+        .width(32)
+        .default_register_properties(
+            RegisterProperties::new()
+                .size(Some(32))
+                .access(Some(Access::ReadWrite))
+                .reset_value(Some(0))
+                .reset_mask(Some(0xffff_ffff)),
+        )
+        .peripherals(vec![timer.single()])
+        .schema_version("1.3".into())
+        .no_namespace_schema_location("CMSIS-SVD.xsd".into())
+        .build(LVL)
+        .unwrap();
+    assert_eq!(device, &expected);
+}
+
+fn cpu_sau(device: &Device) {
+    let region = |name: &str, base, limit, access| {
+        SauRegion::builder()
+            .name(Some(name.into()))
+            .base(base)
+            .limit(limit)
+            .access(access)
+            .build(LVL)
+            .unwrap()
+    };
+    let expected = Cpu::builder()
+        .name("CM33".into())
+        .revision("r0p4".into())
+        .endian(Endian::Little)
+        .mpu_present(true)
+        .fpu_present(true)
+        .fpu_double_precision(Some(false))
+        .dsp_present(Some(true))
+        .icache_present(Some(false))
+        .dcache_present(Some(false))
+        .vtor_present(Some(true))
+        .nvic_priority_bits(3)
+        .has_vendor_systick(false)
+        .device_num_interrupts(Some(64))
+        .sau_num_regions(Some(2))
+        .sau_regions_config(Some(
+            SauRegionsConfig::new()
+                .enabled(Some(true))
+                .protection_when_disabled(Some(Protection::Secure))
+                .regions(vec![
+                    region(
+                        "NSC",
+                        0x1000_0000,
+                        0x1000_03E0,
+                        SauAccess::NonSecureCallable,
+                    ),
+                    region("NS", 0x2000_0000, 0x2000_FFE0, SauAccess::NonSecure),
+                ]),
+        ))
+        .build(LVL)
+        .unwrap();
+    assert_eq!(device.cpu.as_ref(), Some(&expected));
+}
+
+fn derived_peripheral(device: &Device) {
+    let interrupt = |name: &str, value| {
+        Interrupt::builder()
+            .name(name.into())
+            .value(value)
+            .build(LVL)
+            .unwrap()
+    };
+    let mut uart0 = peripheral(
+        "UART0",
+        0x4000_0000,
+        vec![register("DATA", 0, vec![]).single().into()],
+    );
+    uart0.description = Some("UART".into());
+    uart0.address_block = Some(vec![address_block(0, 0x400, AddressBlockUsage::Registers)]);
+    uart0.interrupt = vec![interrupt("UART0", 5)];
+    let mut uart1 = peripheral("UART1", 0x4000_0400, vec![]);
+    uart1.derived_from = Some("UART0".into());
+    uart1.interrupt = vec![interrupt("UART1", 6)];
+    assert_eq!(device.peripherals, [uart0.single(), uart1.single()]);
+}
+
+fn register_array_dimindex(device: &Device) {
+    let mut pin = register("PIN%s", 0x10, vec![]);
+    pin.description = Some("Pin %s configuration".into());
+    let mut dim = dim(4, 4);
+    dim.dim_index = DimElement::parse_indexes("3-6");
+    assert_eq!(dim.indexes_as_range(), Some(3..=6));
+    let expected = peripheral("GPIO", 0x4001_0000, vec![pin.array(dim).into()]);
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn nested_cluster_array(device: &Device) {
+    let cluster = |name: &str, description: &str, offset, children| {
+        ClusterInfo::builder()
+            .name(name.into())
+            .description(Some(description.into()))
+            .address_offset(offset)
+            .children(children)
+            .build(LVL)
+            .unwrap()
+    };
+    let ch = cluster(
+        "CH[%s]",
+        "Channel",
+        0x10,
+        vec![
+            register("SRC", 0, vec![]).single().into(),
+            register("DST", 4, vec![]).single().into(),
+        ],
+    );
+    let stream = cluster("STREAM[%s]", "Stream", 0, vec![ch.array(dim(4, 8)).into()]);
+    let stream: Cluster = stream.array(dim(2, 0x40));
+    let expected = peripheral("DMA", 0x4002_0000, vec![stream.into()]);
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn field_array(device: &Device) {
+    let mode = field("MODE%s", BitRange::from_offset_width(0, 2)).array(dim(16, 2));
+    let expected = peripheral(
+        "GPIO",
+        0x4001_0000,
+        vec![register("MODER", 0, vec![mode]).single().into()],
+    );
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn enumerated_values(device: &Device) {
+    let evs = |name: &str, usage, values| {
+        EnumeratedValues::builder()
+            .name(Some(name.into()))
+            .usage(Some(usage))
+            .values(values)
+            .build(LVL)
+            .unwrap()
+    };
+    let mut mode = field("MODE", BitRange::from_bit_range("[1:0]").unwrap());
+    mode.enumerated_values = vec![
+        evs(
+            "ModeRead",
+            Usage::Read,
+            vec![enumerated_value("Idle", 0), enumerated_value("Busy", 1)],
+        ),
+        evs(
+            "ModeWrite",
+            Usage::Write,
+            vec![enumerated_value("Stop", 0), enumerated_value("Start", 1)],
+        ),
+    ];
+    let mut mode2 = field("MODE2", BitRange::from_bit_range("[3:2]").unwrap());
+    mode2.enumerated_values = vec![EnumeratedValues::builder()
+        .derived_from(Some("MODE.ModeRead".into()))
+        .build(LVL)
+        .unwrap()];
+    let expected = peripheral(
+        "ADC",
+        0x4003_0000,
+        vec![register("CR", 0, vec![mode.single(), mode2.single()])
+            .single()
+            .into()],
+    );
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn write_constraint(device: &Device) {
+    let mut value = field("VALUE", BitRange::from_offset_width(0, 8));
+    value.write_constraint = Some(WriteConstraint::Range(WriteConstraintRange {
+        min: 0,
+        max: 100,
+    }));
+    let expected = peripheral(
+        "PWM",
+        0x4004_0000,
+        vec![register("DUTY", 0, vec![value.single()]).single().into()],
+    );
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn modified_write_values(device: &Device) {
+    let mut alarm = field("ALARM", BitRange::from_offset_width(0, 1));
+    alarm.modified_write_values = Some(ModifiedWriteValues::OneToToggle);
+    let mut sr = register("SR", 0, vec![alarm.single()]);
+    sr.modified_write_values = Some(ModifiedWriteValues::OneToClear);
+    sr.read_action = Some(ReadAction::Clear);
+    let expected = peripheral("RTC", 0x4005_0000, vec![sr.single().into()]);
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn alternate_group(device: &Device) {
+    let ccmr1 = |group: &str| -> RegisterCluster {
+        let mut r = register("CCMR1", 0x18, vec![]);
+        r.alternate_group = Some(group.into());
+        r.single().into()
+    };
+    let expected = peripheral("TIM1", 0x4006_0000, vec![ccmr1("Output"), ccmr1("Input")]);
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn address_block_buffer(device: &Device) {
+    let mut expected = peripheral(
+        "USB",
+        0x4007_0000,
+        vec![register("CTRL", 0, vec![]).single().into()],
+    );
+    expected.address_block = Some(vec![
+        address_block(0, 0x100, AddressBlockUsage::Registers),
+        address_block(0x400, 0x400, AddressBlockUsage::Buffer),
+    ]);
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn protection(device: &Device) {
+    let mut lock = register("LOCK", 0, vec![]);
+    lock.properties.protection = Some(Protection::Privileged);
+    let mut secure = peripheral("GPIO_S", 0x5008_0000, vec![lock.single().into()]);
+    secure.default_register_properties.protection = Some(Protection::Secure);
+    let mut block = address_block(0, 0x100, AddressBlockUsage::Registers);
+    block.protection = Some(Protection::Secure);
+    secure.address_block = Some(vec![block]);
+    let mut non_secure = peripheral("GPIO_NS", 0x4008_0000, vec![]);
+    non_secure.default_register_properties.protection = Some(Protection::NonSecure);
+    assert_eq!(device.peripherals, [secure.single(), non_secure.single()]);
+}
+
+fn addresses_64bit(device: &Device) {
+    let mut bar = register("BAR", 0x10, vec![]);
+    bar.properties = RegisterProperties::new()
+        .size(Some(64))
+        .reset_value(Some(0xffff_ffff_0000_0000))
+        .reset_mask(Some(u64::MAX));
+    let mut expected = peripheral("PCIE", 0x40_0000_0000, vec![bar.single().into()]);
+    expected.address_block = Some(vec![address_block(0, 0x1000, AddressBlockUsage::Registers)]);
+    assert_eq!(device.width, 64);
+    assert_eq!(device.peripherals, [expected.single()]);
+}
+
+fn binary_values(device: &Device) {
+    let mut external = enumerated_value("External", 0b100);
+    external.dont_care = Some(0b011);
+    let mut src = field("SRC", BitRange::from_offset_width(0, 3));
+    src.enumerated_values = vec![EnumeratedValues::builder()
+        .values(vec![enumerated_value("Internal", 0), external])
+        .build(LVL)
+        .unwrap()];
+    let expected = peripheral(
+        "CLK",
+        0x4009_0000,
+        vec![register("CFG", 0, vec![src.single()]).single().into()],
+    );
+    assert_eq!(device.peripherals, [expected.single()]);
+}
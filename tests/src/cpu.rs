@@ -1,5 +1,7 @@
 use super::run_test;
-use crate::svd::{Cpu, Endian, ValidateLevel};
+use crate::svd::{
+    Cpu, CpuRevision, Endian, Protection, SauAccess, SauRegion, SauRegionsConfig, ValidateLevel,
+};
 
 #[test]
 fn decode_encode() {
@@ -40,3 +42,283 @@ fn decode_encode() {
 
     run_test::<Cpu>(&tests[..], None, None);
 }
+
+#[test]
+fn capabilities() {
+    use crate::svd::CortexArch::{self, *};
+
+    let cpu = |name: &str| {
+        Cpu::builder()
+            .name(name.to_string())
+            .revision("r0p0".to_string())
+            .endian(Endian::Little)
+            .mpu_present(false)
+            .fpu_present(false)
+            .nvic_priority_bits(3)
+            .has_vendor_systick(false)
+    };
+    // name, architecture, TrustZone, VTOR by default
+    let cores: &[(&str, CortexArch, bool, bool)] = &[
+        ("CM0", ArmV6M, false, false),
+        ("CM0PLUS", ArmV6M, false, true),
+        ("CM0+", ArmV6M, false, true),
+        ("CM1", ArmV6M, false, false),
+        ("SC000", ArmV6M, false, false),
+        ("CM3", ArmV7M, false, true),
+        ("SC300", ArmV7M, false, true),
+        ("CM4", ArmV7EM, false, true),
+        ("CM7", ArmV7EM, false, true),
+        ("CM23", ArmV8MBaseline, true, true),
+        ("ARMV8MBL", ArmV8MBaseline, true, true),
+        ("CM33", ArmV8MMainline, true, true),
+        ("CM35P", ArmV8MMainline, true, true),
+        ("ARMV8MML", ArmV8MMainline, true, true),
+        ("CM52", ArmV81MMainline, true, true),
+        ("CM55", ArmV81MMainline, true, true),
+        ("CM85", ArmV81MMainline, true, true),
+        ("ARMV81MML", ArmV81MMainline, true, true),
+        ("CA5", ArmV7A, false, true),
+        ("CA7", ArmV7A, false, true),
+        ("CA8", ArmV7A, false, true),
+        ("CA9", ArmV7A, false, true),
+        ("CA15", ArmV7A, false, true),
+        ("CA17", ArmV7A, false, true),
+        ("CA53", ArmV8A, false, true),
+        ("CA57", ArmV8A, false, true),
+        ("CA72", ArmV8A, false, true),
+        ("SMC1", Other, false, true),
+        ("other", Other, false, true),
+    ];
+    for &(name, arch, trustzone, vtor) in cores {
+        let cpu = cpu(name).build(ValidateLevel::Strict).unwrap();
+        assert_eq!(cpu.architecture(), Some(arch), "{}", name);
+        assert_eq!(cpu.is_trustzone_capable(), trustzone, "{}", name);
+        assert_eq!(cpu.supports_vtor(), vtor, "{}", name);
+        assert!(!cpu.has_fpu() && !cpu.has_mpu(), "{}", name);
+        assert_eq!(cpu.nvic_levels(), 8);
+        assert_eq!(cpu.capability_conflicts(), Vec::<String>::new(), "{}", name);
+    }
+
+    // explicit `vtorPresent` overrides the default
+    let cm0plus = cpu("CM0PLUS").vtor_present(Some(false));
+    assert!(!cm0plus
+        .build(ValidateLevel::Strict)
+        .unwrap()
+        .supports_vtor());
+
+    // explicit flags win over the architecture, but are reported
+    let cm0 = cpu("CM0")
+        .fpu_present(true)
+        .vtor_present(Some(true))
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    assert!(cm0.has_fpu() && cm0.supports_vtor());
+    assert_eq!(
+        cm0.capability_conflicts(),
+        [
+            "`fpuPresent` is set, but CM0 has no FPU",
+            "`vtorPresent` is set, but CM0 has no VTOR"
+        ]
+    );
+    let cm4 = cpu("CM4")
+        .fpu_present(true)
+        .fpu_double_precision(Some(true))
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    assert!(cm4.has_fpu());
+    assert_eq!(
+        cm4.capability_conflicts(),
+        ["`fpuDP` is set, but FPU of CM4 is single precision only"]
+    );
+}
+
+#[test]
+fn armv8m_fields() {
+    let tests = vec![(
+        Cpu::builder()
+            .name("CM33".to_string())
+            .revision("r0p2".to_string())
+            .endian(Endian::Little)
+            .mpu_present(true)
+            .fpu_present(true)
+            .fpu_double_precision(Some(false))
+            .dsp_present(Some(true))
+            .icache_present(Some(true))
+            .dcache_present(Some(false))
+            .itcm_present(Some(false))
+            .dtcm_present(Some(true))
+            .vtor_present(Some(true))
+            .nvic_priority_bits(3)
+            .has_vendor_systick(false)
+            .device_num_interrupts(Some(96))
+            .sau_num_regions(Some(8))
+            .build(ValidateLevel::Strict)
+            .unwrap(),
+        // booleans may also be written as 1 and 0
+        "
+                <cpu>
+                    <name>CM33</name>
+                    <revision>r0p2</revision>
+                    <endian>little</endian>
+                    <mpuPresent>1</mpuPresent>
+                    <fpuPresent>1</fpuPresent>
+                    <fpuDP>0</fpuDP>
+                    <dspPresent>true</dspPresent>
+                    <icachePresent>1</icachePresent>
+                    <dcachePresent>false</dcachePresent>
+                    <itcmPresent>0</itcmPresent>
+                    <dtcmPresent>true</dtcmPresent>
+                    <vtorPresent>1</vtorPresent>
+                    <nvicPrioBits>3</nvicPrioBits>
+                    <vendorSystickConfig>0</vendorSystickConfig>
+                    <deviceNumInterrupts>96</deviceNumInterrupts>
+                    <sauNumRegions>8</sauNumRegions>
+                </cpu>
+            ",
+        "
+                <cpu>
+                    <name>CM33</name>
+                    <revision>r0p2</revision>
+                    <endian>little</endian>
+                    <mpuPresent>true</mpuPresent>
+                    <fpuPresent>true</fpuPresent>
+                    <fpuDP>false</fpuDP>
+                    <dspPresent>true</dspPresent>
+                    <icachePresent>true</icachePresent>
+                    <dcachePresent>false</dcachePresent>
+                    <itcmPresent>false</itcmPresent>
+                    <dtcmPresent>true</dtcmPresent>
+                    <vtorPresent>true</vtorPresent>
+                    <nvicPrioBits>3</nvicPrioBits>
+                    <vendorSystickConfig>false</vendorSystickConfig>
+                    <deviceNumInterrupts>96</deviceNumInterrupts>
+                    <sauNumRegions>8</sauNumRegions>
+                </cpu>
+            ",
+    )];
+
+    run_test::<Cpu>(&tests[..], None, None);
+}
+
+#[test]
+fn sau_regions_config() {
+    let region = |name: Option<&str>, enabled, base, limit, access| {
+        SauRegion::builder()
+            .name(name.map(String::from))
+            .enabled(enabled)
+            .base(base)
+            .limit(limit)
+            .access(access)
+            .build(ValidateLevel::Strict)
+            .unwrap()
+    };
+    let tests = vec![(
+        Cpu::builder()
+            .name("CM33".to_string())
+            .revision("r0p4".to_string())
+            .endian(Endian::Little)
+            .mpu_present(true)
+            .fpu_present(true)
+            .nvic_priority_bits(3)
+            .has_vendor_systick(false)
+            .sau_num_regions(Some(2))
+            .sau_regions_config(Some(
+                SauRegionsConfig::new()
+                    .enabled(Some(true))
+                    .protection_when_disabled(Some(Protection::Secure))
+                    .regions(vec![
+                        region(
+                            Some("NSC"),
+                            true,
+                            0x1000_0000,
+                            0x1000_03E0,
+                            SauAccess::NonSecureCallable,
+                        ),
+                        region(None, false, 0x2000_0000, 0x2000_FFE0, SauAccess::NonSecure),
+                    ]),
+            ))
+            .build(ValidateLevel::Strict)
+            .unwrap(),
+        // `enabled` of a region is `true` when not given
+        "
+                <cpu>
+                    <name>CM33</name>
+                    <revision>r0p4</revision>
+                    <endian>little</endian>
+                    <mpuPresent>true</mpuPresent>
+                    <fpuPresent>true</fpuPresent>
+                    <nvicPrioBits>3</nvicPrioBits>
+                    <vendorSystickConfig>false</vendorSystickConfig>
+                    <sauNumRegions>2</sauNumRegions>
+                    <sauRegionsConfig enabled=\"1\" protectionWhenDisabled=\"s\">
+                        <region name=\"NSC\">
+                            <base>0x10000000</base>
+                            <limit>0x100003E0</limit>
+                            <access>c</access>
+                        </region>
+                        <region enabled=\"false\">
+                            <base>0x20000000</base>
+                            <limit>0x2000FFE0</limit>
+                            <access>n</access>
+                        </region>
+                    </sauRegionsConfig>
+                </cpu>
+            ",
+        "
+                <cpu>
+                    <name>CM33</name>
+                    <revision>r0p4</revision>
+                    <endian>little</endian>
+                    <mpuPresent>true</mpuPresent>
+                    <fpuPresent>true</fpuPresent>
+                    <nvicPrioBits>3</nvicPrioBits>
+                    <vendorSystickConfig>false</vendorSystickConfig>
+                    <sauNumRegions>2</sauNumRegions>
+                    <sauRegionsConfig enabled=\"true\" protectionWhenDisabled=\"s\">
+                        <region enabled=\"true\" name=\"NSC\">
+                            <base>0x10000000</base>
+                            <limit>0x100003E0</limit>
+                            <access>c</access>
+                        </region>
+                        <region enabled=\"false\">
+                            <base>0x20000000</base>
+                            <limit>0x2000FFE0</limit>
+                            <access>n</access>
+                        </region>
+                    </sauRegionsConfig>
+                </cpu>
+            ",
+    )];
+
+    run_test::<Cpu>(&tests[..], None, None);
+}
+
+#[test]
+fn revision() {
+    let cpu = |revision: &str| {
+        Cpu::builder()
+            .name("CM4".to_string())
+            .revision(revision.to_string())
+            .endian(Endian::Little)
+            .mpu_present(false)
+            .fpu_present(true)
+            .nvic_priority_bits(4)
+            .has_vendor_systick(false)
+            .build(ValidateLevel::Strict)
+            .unwrap()
+    };
+    let r = |major, minor| Some(CpuRevision { major, minor });
+
+    assert_eq!(cpu("r1p2").cpu_revision(), r(1, 2));
+    assert_eq!(cpu("R0P1").cpu_revision(), r(0, 1));
+    assert_eq!(cpu("r10p0").cpu_revision(), r(10, 0));
+    // other forms keep only the raw text
+    for revision in ["1", "0x0001", "r1", "rp1", "r1p", "r+1p2", ""] {
+        let cpu = cpu(revision);
+        assert_eq!(cpu.revision, revision);
+        assert_eq!(cpu.cpu_revision(), None, "{}", revision);
+    }
+
+    assert_eq!(CpuRevision { major: 2, minor: 1 }.to_string(), "r2p1");
+    assert!(r(1, 2) > r(0, 9));
+}
@@ -0,0 +1,2650 @@
+use crate::svd::{
+    Access, BitRangeType, Device, InterruptTableConfig, ModeHeuristics, Peripheral, Severity,
+    ValidateLevel, ValidationConfig, WidthAliasOptions,
+};
+use roxmltree::Document;
+use svd_parser::{self as parser, Parse};
+
+fn device_xml(peripherals: &str) -> String {
+    format!(
+        "
+        <device>
+            <name>TEST</name>
+            <version>1.0</version>
+            <description>Test device</description>
+            <addressUnitBits>8</addressUnitBits>
+            <width>32</width>
+            <peripherals>{}</peripherals>
+        </device>
+        ",
+        peripherals
+    )
+}
+
+const UART_ALIASES: &str = "
+    <peripheral>
+        <name>UART0_S</name>
+        <baseAddress>0x50001000</baseAddress>
+        <interrupt><name>UART0_S</name><value>17</value></interrupt>
+    </peripheral>
+    <peripheral>
+        <name>UART0_NS</name>
+        <baseAddress>0x40001000</baseAddress>
+        <interrupt><name>UART0_NS</name><value>17</value></interrupt>
+    </peripheral>
+";
+
+#[test]
+fn interrupt_table_security_aliases() {
+    let device = parser::parse(&device_xml(UART_ALIASES)).unwrap();
+
+    // Without the option aliases are reported as conflict
+    assert!(device
+        .interrupt_table(&InterruptTableConfig::default())
+        .is_err());
+
+    let config = InterruptTableConfig::default().collapse_security_aliases(true);
+    let table = device.interrupt_table(&config).unwrap();
+    assert_eq!(table.len(), 18);
+    let entry = table[17].as_ref().unwrap();
+    assert_eq!(entry.interrupt.name, "UART0_S");
+    let owners: Vec<_> = entry.peripherals.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(owners, ["UART0_S", "UART0_NS"]);
+    assert!(table[..17].iter().all(Option::is_none));
+}
+
+#[test]
+fn interrupt_table_conflict() {
+    let xml = device_xml(&format!(
+        "{}
+        <peripheral>
+            <name>TIMER</name>
+            <baseAddress>0x40002000</baseAddress>
+            <interrupt><name>TIMER</name><value>17</value></interrupt>
+        </peripheral>",
+        UART_ALIASES
+    ));
+    let device = parser::parse(&xml).unwrap();
+    let config = InterruptTableConfig::default().collapse_security_aliases(true);
+    let err = device.interrupt_table(&config).unwrap_err().to_string();
+    assert!(err.contains("Interrupt 17"), "{}", err);
+    assert!(err.contains("TIMER"), "{}", err);
+}
+
+#[test]
+fn interrupt_table_limit() {
+    let xml = device_xml(
+        "
+        <peripheral>
+            <name>TIMER</name>
+            <baseAddress>0x40002000</baseAddress>
+            <interrupt><name>TIMER</name><value>4294967295</value></interrupt>
+        </peripheral>",
+    );
+    let device = parser::parse(&xml).unwrap();
+    let err = device
+        .interrupt_table(&InterruptTableConfig::default())
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Device error: Interrupt `TIMER` has number 4294967295, the table holds 496 interrupts"
+    );
+    let config = InterruptTableConfig::default().max_interrupts(8);
+    let xml = xml.replace("4294967295", "8");
+    let device = parser::parse(&xml).unwrap();
+    assert!(device.interrupt_table(&config).is_err());
+    let table = device.interrupt_table(&config.max_interrupts(9)).unwrap();
+    assert_eq!(table.len(), 9);
+}
+
+#[test]
+fn interrupts() {
+    let xml = device_xml(&format!(
+        "{}
+        <peripheral>
+            <name>DMA</name>
+            <baseAddress>0x40003000</baseAddress>
+            <interrupt><name>DMA_CH0</name><value>3</value></interrupt>
+            <interrupt><name>DMA_CH1</name><value>4</value></interrupt>
+        </peripheral>
+        <peripheral derivedFrom=\"DMA\">
+            <name>DMA2</name>
+            <baseAddress>0x40004000</baseAddress>
+        </peripheral>",
+        UART_ALIASES
+    ));
+    let device = parser::parse(&xml).unwrap();
+    let interrupts: Vec<_> = device
+        .interrupts()
+        .map(|(p, i)| (p.name.as_str(), i.name.as_str(), i.value))
+        .collect();
+    assert_eq!(
+        interrupts,
+        [
+            ("UART0_S", "UART0_S", 17),
+            ("UART0_NS", "UART0_NS", 17),
+            ("DMA", "DMA_CH0", 3),
+            ("DMA", "DMA_CH1", 4),
+        ]
+    );
+}
+
+#[test]
+fn peripheral_spans() {
+    let xml = device_xml(
+        "
+        <peripheral>
+            <name>GPIO</name>
+            <!-- a comment with </peripheral> inside -->
+            <description><![CDATA[General <purpose> I/O]]></description>
+            <baseAddress>0x40000000</baseAddress>
+        </peripheral>
+        <peripheral derivedFrom=\"GPIO\">
+            <name>GPIO</name>
+            <baseAddress>0x40001000</baseAddress>
+        </peripheral>
+        <peripheral>
+            <name>GPIO#1</name>
+            <baseAddress>0x40003000</baseAddress>
+        </peripheral>
+        <peripheral>
+            <name>UART</name>
+            <baseAddress>0x40002000</baseAddress>
+        </peripheral>",
+    );
+    // BOM should not shift the ranges
+    let xml = format!("\u{feff}{}", xml);
+
+    let output = parser::parse_full(&xml, &parser::Config::default()).unwrap();
+    assert!(output.spans.is_none());
+
+    let config = parser::Config::default().peripheral_spans(true);
+    let output = parser::parse_full(&xml, &config).unwrap();
+    let spans = output.spans.clone().unwrap();
+    // names which look like numbered ones don't collide with repeated names
+    let keys = [("GPIO", 0), ("GPIO", 1), ("GPIO#1", 0), ("UART", 0)];
+    let found: Vec<_> = spans.keys().map(|(n, i)| (n.as_str(), *i)).collect();
+    assert_eq!(found, keys);
+
+    for ((name, i), p) in keys.iter().zip(&output.device.peripherals) {
+        let slice = &xml[spans[&(name.to_string(), *i)].clone()];
+        assert!(slice.starts_with("<peripheral"));
+        assert!(slice.ends_with("</peripheral>"));
+        let doc = Document::parse(slice).unwrap();
+        let parsed = Peripheral::parse(&doc.root_element(), &config).unwrap();
+        assert_eq!(&parsed, p);
+    }
+}
+
+fn registers_xml(registers: &str) -> String {
+    device_xml(&format!(
+        "
+        <peripheral>
+            <name>UART</name>
+            <baseAddress>0x40000000</baseAddress>
+            <size>32</size>
+            <registers>
+                <register>
+                    <name>CTRL</name>
+                    <addressOffset>0x0</addressOffset>
+                </register>
+                <register>
+                    <name>STATUS</name>
+                    <addressOffset>0x6</addressOffset>
+                    <size>16</size>
+                </register>
+                {}
+            </registers>
+        </peripheral>",
+        registers
+    ))
+}
+
+#[test]
+fn alignment_aligned() {
+    let device = parser::parse(&registers_xml(
+        "
+        <cluster>
+            <dim>2</dim>
+            <dimIncrement>0x8</dimIncrement>
+            <name>CH%s</name>
+            <addressOffset>0x10</addressOffset>
+            <register>
+                <name>DATA</name>
+                <addressOffset>0x4</addressOffset>
+            </register>
+        </cluster>",
+    ))
+    .unwrap();
+    assert_eq!(device.validate_with(&ValidationConfig::default()), []);
+}
+
+#[test]
+fn alignment_misaligned() {
+    let device = parser::parse(&registers_xml(
+        "
+        <register>
+            <name>DATA</name>
+            <addressOffset>0xa</addressOffset>
+        </register>",
+    ))
+    .unwrap();
+
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "alignment.register");
+    assert_eq!(diagnostics[0].path, "UART.DATA");
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+    let config = ValidationConfig::default().misaligned_as_error(true);
+    assert_eq!(device.validate_with(&config)[0].severity, Severity::Error);
+
+    let config = ValidationConfig::default().packed_peripherals(vec!["UART".to_string()]);
+    assert_eq!(device.validate_with(&config), []);
+}
+
+#[test]
+fn parse_full() {
+    let xml = device_xml(
+        "
+        <peripheral>
+            <name>GPIO</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register>
+                    <name>MODE</name>
+                    <addressOffset>0x0</addressOffset>
+                    <size>32</size>
+                    <fields>
+                        <field>
+                            <name>MODE</name>
+                            <bitRange>[1:0]</bitRange>
+                            <enumeratedValues>
+                                <enumeratedValue><name>ALL</name><value>-1</value></enumeratedValue>
+                            </enumeratedValues>
+                        </field>
+                    </fields>
+                </register>
+            </registers>
+        </peripheral>",
+    );
+    let config = parser::Config::default().minus_one_as_all_ones(true);
+
+    let output = parser::parse_full(&xml, &config).unwrap();
+    assert_eq!(
+        output.device,
+        parser::parse_with_config(&xml, &config).unwrap()
+    );
+    assert_eq!(output.spans, None);
+    assert_eq!(output.warnings.len(), 1);
+    let line = xml.lines().position(|l| l.contains("-1")).unwrap() + 1;
+    assert_eq!(output.warnings[0].line as usize, line);
+
+    let output = parser::parse_full(&xml, &config.peripheral_spans(true)).unwrap();
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.spans.unwrap().len(), 1);
+
+    // without the option `-1` is an error
+    assert!(parser::parse(&xml).is_err());
+}
+
+#[test]
+fn packing_summary() {
+    let device = parser::parse(&registers_xml(
+        "
+        <cluster>
+            <name>CH</name>
+            <addressOffset>0x10</addressOffset>
+            <size>8</size>
+            <register>
+                <name>DATA</name>
+                <addressOffset>0x0</addressOffset>
+                <fields>
+                    <field><name>D</name><bitRange>[7:0]</bitRange></field>
+                </fields>
+            </register>
+        </cluster>
+        <register>
+            <name>IE</name>
+            <addressOffset>0x8</addressOffset>
+            <fields>
+                <field><name>EN</name><bitRange>[7:0]</bitRange></field>
+            </fields>
+        </register>",
+    ))
+    .unwrap();
+
+    let summary = device.packing_summary();
+    assert_eq!(summary.registers, 2);
+    assert_eq!(summary.utilization, 0.625);
+    assert_eq!(
+        summary.emptiest,
+        [
+            ("UART.IE".to_string(), 0.25),
+            ("UART.CH.DATA".to_string(), 1.0)
+        ]
+    );
+    assert_eq!(device.peripherals[0].packing_summary(32), summary);
+}
+
+#[test]
+fn peripheral_overlap() {
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>UART0</name>
+            <baseAddress>0x40000000</baseAddress>
+            <addressBlock><offset>0</offset><size>0x400</size><usage>registers</usage></addressBlock>
+        </peripheral>
+        <peripheral derivedFrom=\"UART0\">
+            <name>UART1</name>
+            <baseAddress>0x40000000</baseAddress>
+        </peripheral>
+        <peripheral>
+            <name>TIMER</name>
+            <baseAddress>0x40000200</baseAddress>
+        </peripheral>
+        <peripheral>
+            <name>SPI_S</name>
+            <baseAddress>0x40001000</baseAddress>
+        </peripheral>
+        <peripheral>
+            <name>SPI_NS</name>
+            <baseAddress>0x40001000</baseAddress>
+        </peripheral>
+        <peripheral>
+            <name>I2C</name>
+            <version>2.0</version>
+            <baseAddress>0x40002000</baseAddress>
+            <headerStructName>I2C_Type</headerStructName>
+        </peripheral>
+        <peripheral>
+            <name>SMBUS</name>
+            <alternatePeripheral>I2C</alternatePeripheral>
+            <baseAddress>0x40002000</baseAddress>
+        </peripheral>",
+    ))
+    .unwrap();
+    let i2c = device.get_peripheral("I2C").unwrap();
+    assert_eq!(i2c.version.as_deref(), Some("2.0"));
+    assert_eq!(i2c.header_struct_name.as_deref(), Some("I2C_Type"));
+    let smbus = device.get_peripheral("SMBUS").unwrap();
+    assert_eq!(smbus.alternate_peripheral.as_deref(), Some("I2C"));
+    let encoded = svd_encoder::encode(&device).unwrap();
+    assert_eq!(parser::parse(&encoded).unwrap(), device);
+
+    // alternate peripherals share the address
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 3, "{:#?}", diagnostics);
+
+    assert_eq!(diagnostics[0].code, "base_address.duplicate");
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(
+        diagnostics[0].message,
+        "`UART1` is derived from `UART0` and has the same base address 0x40000000"
+    );
+
+    // TIMER overlaps both UARTs
+    assert_eq!(diagnostics[1].code, "overlap.peripheral");
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+    assert_eq!(diagnostics[1].path, "TIMER");
+    assert!(diagnostics[1].message.contains("UART0"));
+    assert!(diagnostics[2].message.contains("UART1"));
+
+    let config = ValidationConfig::default().aliased_peripherals(vec!["TIMER".to_string()]);
+    assert_eq!(device.validate_with(&config).len(), 1);
+    // security aliases are found by configured suffixes
+    let config = config.security_suffixes("_SEC".into(), "_NSEC".into());
+    let diagnostics = device.validate_with(&config);
+    assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+    assert!(diagnostics[1].message.contains("SPI_S"));
+
+    // blocks reaching past the end of the address space
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>LAST</name>
+            <baseAddress>0xFFFFFFFFFFFFF000</baseAddress>
+            <addressBlock><offset>0</offset><size>0x2000</size><usage>registers</usage></addressBlock>
+        </peripheral>
+        <peripheral>
+            <name>END</name>
+            <baseAddress>0xFFFFFFFFFFFFF800</baseAddress>
+        </peripheral>",
+    ))
+    .unwrap();
+    let overlaps: Vec<_> = device
+        .validate_with(&ValidationConfig::default())
+        .into_iter()
+        .filter(|d| d.code == "overlap.peripheral")
+        .map(|d| d.path)
+        .collect();
+    assert_eq!(overlaps, ["END"]);
+}
+
+#[test]
+fn fields_beyond_size() {
+    let mut device = parser::parse(&registers_xml(
+        "
+        <register>
+            <name>DATA</name>
+            <addressOffset>0x8</addressOffset>
+            <fields>
+                <field><name>LOW</name><bitRange>[7:0]</bitRange></field>
+                <field><name>HIGH</name><bitRange>[47:40]</bitRange></field>
+            </fields>
+        </register>",
+    ))
+    .unwrap();
+
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "size.register");
+    assert_eq!(diagnostics[0].path, "UART.DATA");
+
+    let repaired = device.widen_registers_to_fields();
+    assert_eq!(repaired.len(), 1);
+    assert_eq!(repaired[0].severity, Severity::Info);
+    assert_eq!(repaired[0].path, "UART.DATA");
+    let register = device.peripherals[0].get_register("DATA").unwrap();
+    assert_eq!(register.properties.size, Some(64));
+    assert_eq!(device.validate_with(&ValidationConfig::default()), []);
+}
+
+#[test]
+fn annotations() {
+    let xml = device_xml(
+        "
+        <peripheral>
+            <name>TIMER</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register>
+                    <name>CTRL</name>
+                    <addressOffset>0x0</addressOffset>
+                    <vendorExtensions><introducedIn>A0</introducedIn></vendorExtensions>
+                    <fields>
+                        <field>
+                            <name>EN</name>
+                            <bitRange>[0:0]</bitRange>
+                            <vendorExtensions>
+                                <introducedIn>B0</introducedIn>
+                                <note>unused <b>bold</b></note>
+                            </vendorExtensions>
+                        </field>
+                    </fields>
+                </register>
+                <cluster>
+                    <name>CH</name>
+                    <addressOffset>0x10</addressOffset>
+                    <register>
+                        <name>CMP</name>
+                        <addressOffset>0x0</addressOffset>
+                        <vendorExtensions><introducedIn>B0</introducedIn></vendorExtensions>
+                    </register>
+                </cluster>
+                <register>
+                    <name>CNT</name>
+                    <addressOffset>0x4</addressOffset>
+                    <vendorExtensions><introducedIn>A0</introducedIn></vendorExtensions>
+                </register>
+            </registers>
+        </peripheral>",
+    );
+
+    let output = parser::parse_full(&xml, &parser::Config::default()).unwrap();
+    assert!(output.annotations.is_none());
+
+    let config = parser::Config::default().annotations(true);
+    let annotations = parser::parse_full(&xml, &config)
+        .unwrap()
+        .annotations
+        .unwrap();
+    assert_eq!(annotations.get("TIMER.CTRL", "introducedIn"), Some("A0"));
+    assert_eq!(annotations.get("TIMER.CTRL.EN", "note"), None);
+
+    let groups = annotations.group_by("introducedIn");
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups["A0"], ["TIMER.CNT", "TIMER.CTRL"]);
+    assert_eq!(groups["B0"], ["TIMER.CH.CMP", "TIMER.CTRL.EN"]);
+}
+
+#[test]
+fn access_width_aliases() {
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>GPIO</name>
+            <baseAddress>0x40000000</baseAddress>
+            <size>32</size>
+            <registers>
+                <register>
+                    <name>BSRR</name>
+                    <addressOffset>0x0</addressOffset>
+                    <fields>
+                        <field><name>BS</name><bitRange>[15:0]</bitRange></field>
+                        <field><name>BR</name><bitRange>[31:16]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>BSRRH</name>
+                    <addressOffset>0x2</addressOffset>
+                    <size>16</size>
+                    <fields>
+                        <field><name>BR</name><bitRange>[15:0]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>BSRRL</name>
+                    <addressOffset>0x0</addressOffset>
+                    <size>16</size>
+                    <fields>
+                        <field><name>BS</name><bitRange>[15:0]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>MODE</name>
+                    <addressOffset>0x4</addressOffset>
+                    <fields>
+                        <field><name>MODE</name><bitRange>[3:0]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>CFG</name>
+                    <addressOffset>0x4</addressOffset>
+                    <size>8</size>
+                    <fields>
+                        <field><name>CFG</name><bitRange>[1:0]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>DATA</name>
+                    <addressOffset>0x8</addressOffset>
+                    <fields>
+                        <field><name>DATA</name><bitRange>[31:0]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>DATAH</name>
+                    <addressOffset>0x8</addressOffset>
+                    <size>16</size>
+                    <fields>
+                        <field><name>DATA</name><bitRange>[15:0]</bitRange></field>
+                    </fields>
+                </register>
+                <register>
+                    <name>DATAB</name>
+                    <addressOffset>0x9</addressOffset>
+                    <size>8</size>
+                    <fields>
+                        <field><name>DATA</name><bitRange>[7:0]</bitRange></field>
+                    </fields>
+                </register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    let gpio = &device.peripherals[0];
+
+    let groups = gpio.access_width_aliases(&WidthAliasOptions::default());
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].primary, "BSRR");
+    assert_eq!(groups[0].aliases, ["BSRRH", "BSRRL"]);
+    // field of the primary register filling the whole window of the aliases
+    assert_eq!(groups[1].primary, "DATA");
+    assert_eq!(groups[1].aliases, ["DATAH", "DATAB"]);
+
+    // `L` is not a known width suffix
+    let options = WidthAliasOptions::default().require_name_hint(true);
+    let groups = gpio.access_width_aliases(&options);
+    assert_eq!(groups[0].aliases, ["BSRRH"]);
+
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+    assert_eq!(diagnostics[0].code, "overlap.register");
+    assert_eq!(diagnostics[0].path, "GPIO.CFG");
+
+    let config = ValidationConfig::default().width_aliases(options);
+    assert_eq!(device.validate_with(&config).len(), 2);
+}
+
+#[test]
+fn mode_candidates() {
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>TIM1</name>
+            <baseAddress>0x40010000</baseAddress>
+            <size>32</size>
+            <registers>
+                <register>
+                    <name>CCMR1_Output</name>
+                    <alternateGroup>Output</alternateGroup>
+                    <addressOffset>0x18</addressOffset>
+                    <fields>
+                        <field>
+                            <name>CC1S</name>
+                            <bitRange>[1:0]</bitRange>
+                            <enumeratedValues>
+                                <enumeratedValue><name>Output</name><value>0</value></enumeratedValue>
+                                <enumeratedValue><name>Input</name><value>1</value></enumeratedValue>
+                                <enumeratedValue><name>InputAlt</name><value>2</value></enumeratedValue>
+                            </enumeratedValues>
+                        </field>
+                    </fields>
+                </register>
+                <register>
+                    <name>CCMR1_Input</name>
+                    <alternateGroup>Input</alternateGroup>
+                    <addressOffset>0x18</addressOffset>
+                </register>
+            </registers>
+        </peripheral>
+        <peripheral>
+            <name>UART</name>
+            <baseAddress>0x40020000</baseAddress>
+            <registers>
+                <register>
+                    <name>CR</name>
+                    <addressOffset>0x0</addressOffset>
+                    <fields>
+                        <field>
+                            <name>MODE</name>
+                            <bitRange>[1:0]</bitRange>
+                            <enumeratedValues>
+                                <enumeratedValue><name>Async</name><value>0</value></enumeratedValue>
+                                <enumeratedValue><name>Sync</name><value>1</value></enumeratedValue>
+                            </enumeratedValues>
+                        </field>
+                    </fields>
+                </register>
+                <register>
+                    <name>BRR</name>
+                    <description>Baud rate in Async mode</description>
+                    <addressOffset>0x4</addressOffset>
+                </register>
+                <register>
+                    <name>CLK</name>
+                    <description>Clock output in Sync mode</description>
+                    <addressOffset>0x8</addressOffset>
+                </register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    let (tim, uart) = (&device.peripherals[0], &device.peripherals[1]);
+
+    let candidates = tim.mode_candidates(&ModeHeuristics::default());
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].field, "TIM1.CCMR1_Output.CC1S");
+    assert_eq!(
+        candidates[0].mapping,
+        [
+            ("Output".to_string(), "Output".to_string()),
+            ("Input".to_string(), "Input".to_string())
+        ]
+    );
+    assert!((candidates[0].confidence - 2. / 3.).abs() < 1e-6);
+
+    // descriptions are only checked on request
+    assert_eq!(uart.mode_candidates(&ModeHeuristics::default()), []);
+    let heuristics = ModeHeuristics::default().match_descriptions(true);
+    let candidates = uart.mode_candidates(&heuristics);
+    assert_eq!(candidates[0].field, "UART.CR.MODE");
+    assert_eq!(
+        candidates[0].mapping,
+        [
+            ("Async".to_string(), "BRR".to_string()),
+            ("Sync".to_string(), "CLK".to_string())
+        ]
+    );
+    assert_eq!(candidates[0].confidence, 0.5);
+}
+
+#[test]
+fn numeric_bounds() {
+    let base = registers_xml(
+        "
+        <register>
+            <dim>2</dim>
+            <dimIncrement>4</dimIncrement>
+            <name>DATA%s</name>
+            <addressOffset>0x10</addressOffset>
+            <fields>
+                <field><name>D</name><bitOffset>0</bitOffset><bitWidth>8</bitWidth></field>
+            </fields>
+        </register>",
+    )
+    .replace(
+        "<addressUnitBits>",
+        "<cpu>
+            <name>CM4</name>
+            <revision>r0p1</revision>
+            <endian>little</endian>
+            <mpuPresent>true</mpuPresent>
+            <fpuPresent>true</fpuPresent>
+            <nvicPrioBits>4</nvicPrioBits>
+            <vendorSystickConfig>false</vendorSystickConfig>
+        </cpu>
+        <addressUnitBits>",
+    );
+    let strict = parser::Config::default().validate_level(ValidateLevel::Strict);
+    assert_eq!(
+        parser::parse_full(&base, &strict).unwrap().warnings,
+        [],
+        "fixture should be valid"
+    );
+
+    for (from, to, tag) in [
+        ("<dim>2</dim>", "<dim>0</dim>", "dim"),
+        (
+            "<dimIncrement>4</dimIncrement>",
+            "<dimIncrement>0</dimIncrement>",
+            "dimIncrement",
+        ),
+        (
+            "<bitOffset>0</bitOffset>",
+            "<bitOffset>60</bitOffset>",
+            "bitOffset + bitWidth",
+        ),
+        ("<size>32</size>", "<size>2048</size>", "size"),
+        (
+            "<addressUnitBits>8</addressUnitBits>",
+            "<addressUnitBits>12</addressUnitBits>",
+            "addressUnitBits",
+        ),
+        ("<width>32</width>", "<width>24</width>", "width"),
+        (
+            "<nvicPrioBits>4</nvicPrioBits>",
+            "<nvicPrioBits>9</nvicPrioBits>",
+            "nvicPrioBits",
+        ),
+    ] {
+        let xml = base.replacen(from, to, 1);
+        let expected = format!("`{}` value", tag);
+
+        let output = parser::parse_full(&xml, &parser::Config::default()).unwrap();
+        assert_eq!(output.warnings.len(), 1, "{}: {:?}", tag, output.warnings);
+        assert!(output.warnings[0].message.starts_with(&expected), "{}", tag);
+
+        let err = parser::parse_full(&xml, &strict).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains(&expected),
+            "{}: {:#}",
+            tag,
+            err
+        );
+    }
+
+    // zero width is rejected by field validation in any mode
+    let xml = base.replacen("<bitWidth>8</bitWidth>", "<bitWidth>0</bitWidth>", 1);
+    assert!(parser::parse_full(&xml, &parser::Config::default()).is_err());
+    let err = parser::parse_full(&xml, &strict).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("`bitWidth` value 0"),
+        "{:#}",
+        err
+    );
+
+    // unusual size is only a warning
+    let xml = base.replacen("<size>16</size>", "<size>24</size>", 1);
+    let output = parser::parse_full(&xml, &strict).unwrap();
+    assert_eq!(output.warnings[0].message, "Unusual register size 24");
+}
+
+#[test]
+fn parse_bytes() {
+    use parser::EncodingHint;
+
+    let xml = device_xml(
+        "
+        <peripheral>
+            <name>UART</name>
+            <description>Café</description>
+            <baseAddress>0x40000000</baseAddress>
+        </peripheral>",
+    );
+    let expected = parser::parse(&xml).unwrap();
+    let utf16le =
+        |text: &str| -> Vec<u8> { text.encode_utf16().flat_map(u16::to_le_bytes).collect() };
+
+    let with_bom = [b"\xef\xbb\xbf".as_slice(), xml.as_bytes()].concat();
+    let mut utf16 = vec![0xff, 0xfe];
+    utf16.extend(utf16le(&xml));
+    let latin1_xml = format!("<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>{}", xml);
+    let latin1: Vec<u8> = latin1_xml.chars().map(|c| c as u8).collect();
+    let undeclared: Vec<u8> = xml.chars().map(|c| c as u8).collect();
+
+    for (bytes, hint, decoding) in [
+        (xml.as_bytes(), EncodingHint::Auto, "UTF-8"),
+        (&with_bom, EncodingHint::Auto, "UTF-8 (byte order mark)"),
+        (&utf16, EncodingHint::Auto, "UTF-16LE (byte order mark)"),
+        (&latin1, EncodingHint::Auto, "Latin-1 (XML declaration)"),
+        (&undeclared, EncodingHint::Auto, "Latin-1 (invalid UTF-8)"),
+        (&utf16[2..], EncodingHint::Utf16Le, "UTF-16LE (requested)"),
+    ] {
+        let output = parser::parse_bytes_full(bytes, hint, &parser::Config::default());
+        let output = output.unwrap_or_else(|e| panic!("{}: {:#}", decoding, e));
+        assert_eq!(output.device, expected, "{}", decoding);
+        assert_eq!(output.decoding.as_deref(), Some(decoding));
+    }
+
+    assert!(parser::parse_bytes(&latin1, EncodingHint::Utf8Strict).is_err());
+}
+
+#[test]
+fn family() {
+    use parser::family::{self, FamilyLoadOptions};
+    use std::sync::Arc;
+
+    let peripherals = |uart_base: &str, irq: u32, timer_size: &str| {
+        format!(
+            "
+            <peripheral>
+                <name>UART0</name>
+                <baseAddress>{}</baseAddress>
+                <addressBlock><offset>0</offset><size>0x400</size><usage>registers</usage></addressBlock>
+                <interrupt><name>UART0</name><value>{}</value></interrupt>
+                <registers>
+                    <register>
+                        <name>DATA</name>
+                        <addressOffset>0x0</addressOffset>
+                        <size>32</size>
+                    </register>
+                </registers>
+            </peripheral>
+            <peripheral>
+                <name>TIMER</name>
+                <baseAddress>0x40010000</baseAddress>
+                <registers>
+                    <register>
+                        <name>COUNT</name>
+                        <addressOffset>0x4</addressOffset>
+                        <size>{}</size>
+                    </register>
+                </registers>
+            </peripheral>",
+            uart_base, irq, timer_size
+        )
+    };
+    let dir = std::env::temp_dir().join(format!("svd-family-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut paths = Vec::new();
+    for (name, uart_base, irq, timer_size) in [
+        ("DEV_A", "0x40000000", 5, "32"),
+        // same size written differently
+        ("DEV_B", "0x40002000", 7, "0x20"),
+        ("DEV_C", "0x40000000", 5, "16"),
+    ] {
+        let xml = device_xml(&peripherals(uart_base, irq, timer_size))
+            .replace("<name>TEST</name>", &format!("<name>{}</name>", name));
+        let path = dir.join(format!("{}.svd", name));
+        std::fs::write(&path, xml).unwrap();
+        paths.push(path);
+    }
+
+    let options = FamilyLoadOptions::default()
+        .config(parser::Config::default().number_styles(true))
+        .parallel(true);
+    let set = family::load(&paths, options);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let set = set.unwrap();
+    let stats = set.stats();
+    assert_eq!((stats.devices, stats.peripherals), (3, 6));
+    assert_eq!(stats.unique_peripherals, 3);
+
+    let a = set.device("DEV_A").unwrap();
+    let b = set.device("DEV_B").unwrap();
+    let c = set.device("DEV_C").unwrap();
+    let standalone = parser::parse(
+        &device_xml(&peripherals("0x40002000", 7, "0x20"))
+            .replace("<name>TEST</name>", "<name>DEV_B</name>"),
+    )
+    .unwrap();
+    assert_eq!(b.to_device(), standalone);
+    assert_eq!(
+        b.get_register("UART0", "DATA"),
+        standalone
+            .get_peripheral("UART0")
+            .unwrap()
+            .get_register("DATA")
+    );
+    let uart_b = b.get_peripheral("UART0").unwrap();
+    assert_eq!(uart_b.base_address(), 0x40002000);
+    assert_eq!(uart_b.interrupts()[0].value, 7);
+    assert_eq!(b.peripheral_at(0x40002004).unwrap().1, 4);
+
+    let shared = |x: &parser::family::FamilyDeviceView, y: &parser::family::FamilyDeviceView, p| {
+        Arc::ptr_eq(
+            x.get_peripheral(p).unwrap().body(),
+            y.get_peripheral(p).unwrap().body(),
+        )
+    };
+    assert!(shared(&a, &b, "UART0"));
+    assert!(shared(&a, &b, "TIMER"));
+    assert!(shared(&a, &c, "UART0"));
+    assert!(!shared(&a, &c, "TIMER"));
+    assert_eq!(
+        c.get_register("TIMER", "COUNT").unwrap().properties.size,
+        Some(16)
+    );
+}
+
+#[test]
+fn validation_config() {
+    use crate::svd::RuleSeverity;
+
+    let peripheral = |name: &str, base: u32| {
+        format!(
+            "
+            <peripheral>
+                <name>{}</name>
+                <baseAddress>{:#x}</baseAddress>
+                <size>32</size>
+                <registers>
+                    <register>
+                        <name>CTRL</name>
+                        <addressOffset>0x0</addressOffset>
+                    </register>
+                    <register>
+                        <name>MODE</name>
+                        <addressOffset>0x2</addressOffset>
+                        <size>16</size>
+                    </register>
+                    <register>
+                        <name>DATA</name>
+                        <addressOffset>0x5</addressOffset>
+                        <size>16</size>
+                    </register>
+                </registers>
+            </peripheral>",
+            name, base
+        )
+    };
+    let device = parser::parse(&device_xml(&format!(
+        "{}{}{}",
+        peripheral("TIM1", 0x4000_0000),
+        peripheral("TIM2", 0x4000_1000),
+        peripheral("SPI", 0x4000_2000)
+    )))
+    .unwrap();
+    let codes = |diagnostics: &[crate::svd::Diagnostic]| -> Vec<(String, String, Severity)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.code.to_string(), d.path.clone(), d.severity))
+            .collect()
+    };
+
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 6, "{:#?}", diagnostics);
+
+    let config = ValidationConfig::default()
+        .severity("alignment.register", RuleSeverity::Error)
+        .suppress("overlap.register", "TIM*")
+        .suppress("size.register", "ADC*");
+    let diagnostics = device.validate_with(&config);
+    assert_eq!(
+        codes(&diagnostics),
+        [
+            (
+                "alignment.register".into(),
+                "TIM1.DATA".into(),
+                Severity::Error
+            ),
+            (
+                "alignment.register".into(),
+                "TIM2.DATA".into(),
+                Severity::Error
+            ),
+            (
+                "alignment.register".into(),
+                "SPI.DATA".into(),
+                Severity::Error
+            ),
+            (
+                "overlap.register".into(),
+                "SPI.MODE".into(),
+                Severity::Warning
+            ),
+            (
+                "validation.suppressed".into(),
+                "TEST".into(),
+                Severity::Info
+            ),
+            (
+                "validation.unused_suppression".into(),
+                "ADC*".into(),
+                Severity::Info
+            ),
+        ]
+    );
+    assert_eq!(diagnostics[4].message, "2 diagnostics suppressed");
+
+    let config = config
+        .severity("alignment.register", RuleSeverity::Off)
+        .min_severity(Severity::Warning);
+    assert_eq!(
+        codes(&device.validate_with(&config))
+            .into_iter()
+            .map(|(code, _, _)| code)
+            .collect::<Vec<_>>(),
+        [
+            "overlap.register",
+            "validation.suppressed",
+            "validation.unused_suppression"
+        ]
+    );
+}
+
+#[test]
+fn suspicious_cluster_offsets() {
+    let peripheral = |data_offset: &str| {
+        device_xml(&format!(
+            "
+            <peripheral>
+                <name>DMA</name>
+                <baseAddress>0x40000000</baseAddress>
+                <addressBlock><offset>0</offset><size>0x100</size><usage>registers</usage></addressBlock>
+                <registers>
+                    <register>
+                        <name>ISR</name>
+                        <addressOffset>0x0</addressOffset>
+                    </register>
+                    <cluster>
+                        <dim>4</dim>
+                        <dimIncrement>0x10</dimIncrement>
+                        <name>CH%s</name>
+                        <addressOffset>0x20</addressOffset>
+                        <register>
+                            <name>CFG</name>
+                            <addressOffset>0x0</addressOffset>
+                        </register>
+                        <register>
+                            <name>DATA</name>
+                            <addressOffset>{}</addressOffset>
+                        </register>
+                        <cluster>
+                            <name>SUB</name>
+                            <addressOffset>0x8</addressOffset>
+                            <register>
+                                <name>REG</name>
+                                <addressOffset>0x0</addressOffset>
+                            </register>
+                        </cluster>
+                    </cluster>
+                </registers>
+            </peripheral>",
+            data_offset
+        ))
+    };
+
+    let device = parser::parse(&peripheral("0x4")).unwrap();
+    let dma = device.get_peripheral("DMA").unwrap();
+    assert_eq!(dma.suspicious_cluster_offsets(), []);
+    let ch = dma.get_cluster("CH%s").unwrap();
+    let sub = ch.get_cluster("SUB").unwrap();
+    let reg = sub.get_register("REG").unwrap();
+    assert_eq!(reg.offset_in_parent(), 0);
+    assert_eq!(reg.offset_in_peripheral(&[ch, sub]), 0x28);
+
+    let mut device = parser::parse(&peripheral("0x24")).unwrap();
+    let dma = device.get_mut_peripheral("DMA").unwrap();
+    let suspicions = dma.suspicious_cluster_offsets();
+    assert_eq!(suspicions.len(), 1, "{:#?}", suspicions);
+    assert_eq!(suspicions[0].path, "DMA.CH%s.DATA");
+    assert_eq!(suspicions[0].cluster_relative, 0x4000_0044);
+    assert_eq!(suspicions[0].peripheral_relative, 0x4000_0024);
+
+    let actions = dma.rebase_cluster_offsets();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].code, "offset.cluster");
+    assert_eq!(actions[0].severity, Severity::Info);
+    let ch = dma.get_cluster("CH%s").unwrap();
+    assert_eq!(ch.get_register("DATA").unwrap().address_offset, 0x4);
+    assert_eq!(dma.suspicious_cluster_offsets(), []);
+}
+
+#[test]
+fn effective_registers_errors() {
+    use crate::svd::{peripheral, SvdError};
+
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral derivedFrom=\"UART9\">
+            <name>UART0</name>
+            <baseAddress>0x40000000</baseAddress>
+        </peripheral>
+        <peripheral derivedFrom=\"SPI1\">
+            <name>SPI0</name>
+            <baseAddress>0x40001000</baseAddress>
+        </peripheral>
+        <peripheral derivedFrom=\"SPI0\">
+            <name>SPI1</name>
+            <baseAddress>0x40002000</baseAddress>
+        </peripheral>",
+    ))
+    .unwrap();
+    let error = |name: &str| -> SvdError {
+        device
+            .get_peripheral(name)
+            .unwrap()
+            .effective_registers(&device)
+            .err()
+            .unwrap()
+    };
+    assert_eq!(
+        error("UART0"),
+        peripheral::Error::MissingParent("UART0".into(), "UART9".into()).into()
+    );
+    assert_eq!(
+        error("SPI0"),
+        peripheral::Error::DeriveCycle("SPI0 -> SPI1 -> SPI0".into()).into()
+    );
+}
+
+#[test]
+fn suggested_fixes() {
+    use crate::svd::{Fix, MaybeArray};
+
+    let mut device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>UART0</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register>
+                    <name>CTRL</name>
+                    <addressOffset>0x0</addressOffset>
+                    <size>32</size>
+                    <resetValue>0x1ff</resetValue>
+                    <resetMask>0xff</resetMask>
+                </register>
+                <register>
+                    <dim>2</dim>
+                    <dimIncrement>4</dimIncrement>
+                    <dimIndex>00,01</dimIndex>
+                    <name>DATA%s</name>
+                    <addressOffset>0x4</addressOffset>
+                    <size>32</size>
+                </register>
+            </registers>
+        </peripheral>
+        <peripheral derivedFrom=\"UART_0\">
+            <name>UART1</name>
+            <baseAddress>0x40001000</baseAddress>
+        </peripheral>
+        <peripheral derivedFrom=\"SPI\">
+            <name>UART2</name>
+            <baseAddress>0x40002000</baseAddress>
+        </peripheral>",
+    ))
+    .unwrap();
+    // parser rejects such arrays, but they can be built
+    if let Some(MaybeArray::Array(_, dim)) = device
+        .get_mut_peripheral("UART0")
+        .unwrap()
+        .get_mut_register("DATA%s")
+    {
+        dim.dim_index.as_mut().unwrap().push("02".into());
+    }
+
+    let config = ValidationConfig::default();
+    let diagnostics = device.validate_with(&config);
+    let fixes: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.code, d.suggested_fix.clone()))
+        .collect();
+    assert_eq!(
+        fixes,
+        [
+            (
+                "reset.mask",
+                Some(Fix::SetText {
+                    path: "UART0.CTRL".into(),
+                    element: "resetValue".into(),
+                    new_value: "0xff".into()
+                })
+            ),
+            (
+                "dim.index",
+                Some(Fix::SetText {
+                    path: "UART0.DATA%s".into(),
+                    element: "dimIndex".into(),
+                    new_value: "00,01".into()
+                })
+            ),
+            (
+                "reference.derived_from",
+                Some(Fix::SetText {
+                    path: "UART1".into(),
+                    element: "derivedFrom".into(),
+                    new_value: "UART0".into()
+                })
+            ),
+            // no similar name
+            ("reference.derived_from", None),
+        ]
+    );
+
+    for (i, d) in diagnostics.iter().enumerate() {
+        if let Some(fix) = &d.suggested_fix {
+            let mut fixed = device.clone();
+            fixed.apply_fix(fix).unwrap();
+            let mut expected = diagnostics.clone();
+            expected.remove(i);
+            assert_eq!(fixed.validate_with(&config), expected, "{}", d);
+        }
+    }
+
+    // other kinds of fixes
+    device
+        .apply_fix(&Fix::Rename {
+            path: "UART0.CTRL".into(),
+            new_name: "CR".into(),
+        })
+        .unwrap();
+    let uart0 = device.get_peripheral("UART0").unwrap();
+    assert!(uart0.get_register("CR").is_some());
+    device
+        .apply_fix(&Fix::RemoveNode {
+            path: "UART2".into(),
+        })
+        .unwrap();
+    assert!(device.get_peripheral("UART2").is_none());
+    let add = Fix::AddMissingElement {
+        path: "UART1".into(),
+        element: "description".into(),
+        value: "Second UART".into(),
+    };
+    device.apply_fix(&add).unwrap();
+    assert!(device.apply_fix(&add).is_err());
+    assert!(device
+        .apply_fix(&Fix::RemoveNode {
+            path: "UART0.MISSING".into()
+        })
+        .is_err());
+}
+
+#[test]
+fn alternate_groups() {
+    use crate::svd::naming::{CmsisClassic, NameMangler, NamingContext};
+
+    let register = |name: &str, group: &str| {
+        format!(
+            "
+            <register>
+                <name>{}</name>
+                {}
+                <addressOffset>0x18</addressOffset>
+                <size>32</size>
+            </register>",
+            name, group
+        )
+    };
+    let device = parser::parse(&device_xml(&format!(
+        "
+        <peripheral>
+            <name>TIM1</name>
+            <baseAddress>0x40010000</baseAddress>
+            <registers>{}{}{}{}</registers>
+        </peripheral>",
+        register("CCMR1", "<alternateGroup>Output</alternateGroup>"),
+        register("CCMR1", "<alternateGroup>Input</alternateGroup>"),
+        register("CR1", ""),
+        register("CR1", ""),
+    )))
+    .unwrap();
+
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    let duplicates: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == "name.duplicate")
+        .map(|d| d.path.as_str())
+        .collect();
+    assert_eq!(duplicates, ["TIM1.CR1"]);
+
+    // effective name of a register in a group colliding with a plain name
+    let mut colliding = device.clone();
+    let mut plain = colliding.peripherals[0].registers().next().unwrap().clone();
+    plain.name = "CCMR1_Input".into();
+    plain.alternate_group = None;
+    colliding.peripherals[0]
+        .registers
+        .as_mut()
+        .unwrap()
+        .push(plain.into());
+    let diagnostics = colliding.validate_with(&ValidationConfig::default());
+    let collision = diagnostics
+        .iter()
+        .find(|d| d.path == "TIM1.CCMR1_Input")
+        .unwrap();
+    assert_eq!(collision.code, "name.duplicate");
+    assert_eq!(
+        collision.message,
+        "`CCMR1_Input` of register `CCMR1` in alternate group `Input` collides with another name"
+    );
+
+    let input = device.find("TIM1.CCMR1_Input").unwrap();
+    assert_eq!(input.name, "CCMR1");
+    assert_eq!(input.alternate_group.as_deref(), Some("Input"));
+    let output = device.find("TIM1.CCMR1_Output").unwrap();
+    assert_eq!(output.alternate_group.as_deref(), Some("Output"));
+    // the raw name finds the first register named so
+    assert_eq!(device.find("TIM1.CCMR1"), Some(output));
+    assert_eq!(device.find("TIM1.CCMR1_Both"), None);
+
+    // fixes address registers like `find`
+    let mut removed = device.clone();
+    let remove = |path: &str| crate::svd::Fix::RemoveNode { path: path.into() };
+    removed.apply_fix(&remove("TIM1.CCMR1_Input")).unwrap();
+    removed.apply_fix(&remove("TIM1.CCMR1")).unwrap();
+    assert!(removed.apply_fix(&remove("TIM1.CCMR1")).is_err());
+    let names: Vec<&str> = removed.peripherals[0]
+        .registers()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(names, ["CR1", "CR1"]);
+
+    let ctx = NamingContext::new("TIM1", None);
+    let names: Vec<String> = [output, input]
+        .iter()
+        .map(|r| CmsisClassic.register(&ctx.clone().register_info(r)))
+        .collect();
+    assert_eq!(names, ["TIM1_CCMR1_Output", "TIM1_CCMR1_Input"]);
+}
+
+#[test]
+fn mixed_bit_range() {
+    let field = |spec: &str| {
+        registers_xml(&format!(
+            "
+            <register>
+                <name>DATA</name>
+                <addressOffset>0x8</addressOffset>
+                <fields>
+                    <field><name>VALUE</name>{}</field>
+                </fields>
+            </register>",
+            spec
+        ))
+    };
+    let weak = parser::Config::default();
+    let strict = weak.validate_level(ValidateLevel::Strict);
+    let range = |output: &parser::ParseOutput| {
+        let f = output.device.peripherals[0]
+            .get_register("DATA")
+            .unwrap()
+            .get_field("VALUE")
+            .unwrap()
+            .bit_range;
+        (f.offset, f.width, f.range_type)
+    };
+    let messages = |output: &parser::ParseOutput| -> Vec<String> {
+        output.warnings.iter().map(|w| w.message.clone()).collect()
+    };
+
+    // agreeing specifications
+    let xml = field("<bitRange>[7:4]</bitRange><bitOffset>4</bitOffset><bitWidth>4</bitWidth>");
+    for config in [weak, strict] {
+        let output = parser::parse_full(&xml, &config).unwrap();
+        assert_eq!(range(&output), (4, 4, BitRangeType::BitRange));
+    }
+    let output = parser::parse_full(&xml, &weak).unwrap();
+    assert_eq!(
+        messages(&output),
+        [
+            "Redundant bit range specification: bitRange [7:4] (bits 4..=7), \
+          bitOffset 4 bitWidth 4 (bits 4..=7)"
+        ]
+    );
+    assert!(parser::parse_full(&xml, &strict)
+        .unwrap()
+        .warnings
+        .is_empty());
+
+    // conflicting specifications
+    let xml = field(
+        "<bitOffset>0</bitOffset><bitWidth>2</bitWidth><lsb>1</lsb><msb>3</msb><bitRange>[5:4]</bitRange>",
+    );
+    let output = parser::parse_full(&xml, &weak).unwrap();
+    assert_eq!(range(&output), (4, 2, BitRangeType::BitRange));
+    let conflict = "bitRange [5:4] (bits 4..=5), lsb 1 msb 3 (bits 1..=3), \
+                    bitOffset 0 bitWidth 2 (bits 0..=1)";
+    assert_eq!(
+        messages(&output),
+        [format!(
+            "Conflicting bit range specifications: {}; using the first",
+            conflict
+        )]
+    );
+    let error = parser::parse_full(&xml, &strict).unwrap_err();
+    assert_eq!(
+        error.root_cause().to_string(),
+        format!("Bit range invalid, Conflicting({:?})", conflict)
+    );
+    assert!(format!("{:#}", error).contains("Parsing field `VALUE`"));
+
+    // spaces in bitRange
+    let xml = field("<bitRange> [ 7 : 4 ] </bitRange>");
+    for config in [weak, strict] {
+        let output = parser::parse_full(&xml, &config).unwrap();
+        assert_eq!(range(&output), (4, 4, BitRangeType::BitRange));
+        assert!(output.warnings.is_empty());
+    }
+
+    // reversed bitRange
+    let xml = field("<bitRange>[4:7]</bitRange>");
+    for config in [weak, strict] {
+        let error = parser::parse_full(&xml, &config).unwrap_err();
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Bit range invalid, Reversed { msb: 4, lsb: 7 }"
+        );
+        assert!(format!("{:#}", error).contains("Parsing field `VALUE`"));
+    }
+}
+
+#[test]
+fn inherited_size_in_checks() {
+    let xml = |cluster_size: &str| {
+        device_xml(&format!(
+            "
+            <peripheral>
+                <name>DMA</name>
+                <baseAddress>0x40000000</baseAddress>
+                <registers>
+                    <cluster>
+                        <name>CH</name>
+                        <addressOffset>0x0</addressOffset>
+                        {}
+                        <register>
+                            <dim>4</dim>
+                            <dimIncrement>2</dimIncrement>
+                            <name>CFG%s</name>
+                            <addressOffset>0x0</addressOffset>
+                        </register>
+                    </cluster>
+                </registers>
+            </peripheral>",
+            cluster_size
+        ))
+        .replace("<width>32</width>", "<width>32</width><size>32</size>")
+    };
+
+    let device = parser::parse(&xml("<size>16</size>")).unwrap();
+    assert_eq!(device.validate_with(&ValidationConfig::default()), []);
+
+    let device = parser::parse(&xml("")).unwrap();
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    let increment: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == "dim.increment")
+        .collect();
+    assert_eq!(increment.len(), 1);
+    assert_eq!(increment[0].path, "DMA.CH.CFG%s");
+    assert_eq!(
+        increment[0].message,
+        "array increment 0x2 is smaller than 32-bit register"
+    );
+
+    // elements of a cluster array overlap when registers of one end beyond the increment
+    let xml = xml("<size>16</size>").replace(
+        "<name>CH</name>",
+        "<dim>2</dim><dimIncrement>0x4</dimIncrement><name>CH%s</name>",
+    );
+    let device = parser::parse(&xml).unwrap();
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].path, "DMA.CH%s");
+    assert_eq!(
+        diagnostics[0].message,
+        "array increment 0x4 is smaller than 0x8 bytes of cluster registers"
+    );
+}
+
+#[test]
+fn metadata() {
+    let peripheral =
+        "<peripheral><name>UART</name><baseAddress>0x40000000</baseAddress></peripheral>";
+    let config = parser::Config::default().validate_level(ValidateLevel::Strict);
+
+    let xml = format!(
+        "
+        <device>
+            <vendor>STMicroelectronics</vendor>
+            <vendorID>ST</vendorID>
+            <name>STM32F401</name>
+            <series>STM32F4</series>
+            <version>1.2</version>
+            <description>STM32F401</description>
+            <headerSystemFilename>system_stm32f4xx</headerSystemFilename>
+            <headerDefinitionsPrefix>STM32_</headerDefinitionsPrefix>
+            <addressUnitBits>8</addressUnitBits>
+            <width>32</width>
+            <peripherals>{}</peripherals>
+        </device>
+        ",
+        peripheral
+    );
+    let device = parser::parse_with_config(&xml, &config).unwrap();
+    assert_eq!(device.vendor.as_deref(), Some("STMicroelectronics"));
+    assert_eq!(device.vendor_id.as_deref(), Some("ST"));
+    assert_eq!(device.series.as_deref(), Some("STM32F4"));
+    assert_eq!(device.version.as_deref(), Some("1.2"));
+    assert_eq!(device.description.as_deref(), Some("STM32F401"));
+    assert_eq!(
+        device.header_system_filename.as_deref(),
+        Some("system_stm32f4xx")
+    );
+    assert_eq!(device.header_definitions_prefix.as_deref(), Some("STM32_"));
+    let encoded = svd_encoder::encode(&device).unwrap();
+    assert_eq!(
+        parser::parse_with_config(&encoded, &config).unwrap(),
+        device
+    );
+
+    // Community files often have none of them
+    let xml = format!(
+        "
+        <device>
+            <name>TEST</name>
+            <addressUnitBits>8</addressUnitBits>
+            <width>32</width>
+            <peripherals>{}</peripherals>
+        </device>
+        ",
+        peripheral
+    );
+    let device = parser::parse_with_config(&xml, &config).unwrap();
+    assert_eq!(device.vendor, None);
+    assert_eq!(device.vendor_id, None);
+    assert_eq!(device.series, None);
+    assert_eq!(device.version, None);
+    assert_eq!(device.description, None);
+    assert_eq!(device.header_system_filename, None);
+    assert_eq!(device.header_definitions_prefix, None);
+}
+
+#[test]
+fn size_from_width() {
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>UART</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register>
+                    <name>DATA</name>
+                    <addressOffset>0x0</addressOffset>
+                    <fields>
+                        <field><name>LOW</name><bitRange>[7:0]</bitRange></field>
+                        <field><name>HIGH</name><bitRange>[39:32]</bitRange></field>
+                    </fields>
+                </register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    assert_eq!(device.width, 32);
+    assert_eq!(device.default_register_properties.size, None);
+    assert_eq!(device.default_register_size(), 32);
+
+    let register = device.peripherals[0].get_register("DATA").unwrap();
+    let (size, adjustment) =
+        register.effective_size_considering_fields(device.default_register_size());
+    assert_eq!(size, 32);
+    assert_eq!(adjustment.unwrap().required, 64);
+
+    let diagnostics = device.validate_with(&ValidationConfig::default());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "size.register");
+}
+
+#[test]
+fn license_text() {
+    let xml = device_xml("<peripheral><name>UART</name><baseAddress>0x40000000</baseAddress></peripheral>")
+        .replace(
+            "<addressUnitBits>",
+            "<licenseText>\n  Copyright 2024 ACME\\n\\n  Licensed under Apache-2.0  \\n</licenseText><addressUnitBits>",
+        );
+    let device = parser::parse(&xml).unwrap();
+    assert_eq!(
+        device.license().as_deref(),
+        Some("Copyright 2024 ACME\n\nLicensed under Apache-2.0")
+    );
+
+    // surrounding spaces are trimmed like in every element
+    let raw = "Copyright 2024 ACME\\n\\n  Licensed under Apache-2.0  \\n";
+    assert_eq!(device.license_text.as_deref(), Some(raw));
+    let encoded = svd_encoder::encode(&device).unwrap();
+    assert!(encoded.contains(raw));
+    assert_eq!(parser::parse(&encoded).unwrap(), device);
+}
+
+#[test]
+fn schema_version() {
+    let xml = device_xml(
+        "<peripheral><name>UART</name><baseAddress>0x40000000</baseAddress></peripheral>",
+    );
+    let device = parser::parse(&xml).unwrap();
+    assert_eq!(device.schema_version, "1.1");
+    assert!(device.is_at_least(1, 1));
+    assert!(!device.is_at_least(1, 3));
+
+    for (version, at_least_1_3) in [
+        ("1.3", true),
+        ("2", true),
+        ("1.10", true),
+        ("1.x", false),
+        ("", false),
+    ] {
+        let xml = xml.replacen(
+            "<device>",
+            &format!("<device schemaVersion=\"{}\">", version),
+            1,
+        );
+        let device = parser::parse(&xml).unwrap();
+        assert_eq!(device.schema_version, version);
+        assert_eq!(device.is_at_least(1, 3), at_least_1_3, "{}", version);
+        assert!(!device.is_at_least(3, 0));
+    }
+}
+
+#[test]
+fn register_display_name() {
+    let device = parser::parse(&device_xml(
+        "
+        <peripheral>
+            <name>GPIOA</name>
+            <prependToName>GPIO_</prependToName>
+            <appendToName>_A</appendToName>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register><name>ODR</name><addressOffset>0x0</addressOffset></register>
+                <register>
+                    <name>MODE</name>
+                    <alternateGroup>In</alternateGroup>
+                    <addressOffset>0x4</addressOffset>
+                </register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    let gpio = &device.peripherals[0];
+    assert_eq!(gpio.prepend_to_name.as_deref(), Some("GPIO_"));
+    assert_eq!(gpio.append_to_name.as_deref(), Some("_A"));
+
+    let odr = gpio.get_register("ODR").unwrap();
+    assert_eq!(odr.name, "ODR");
+    assert_eq!(gpio.register_display_name(odr), "GPIO_ODR_A");
+    let mode = gpio.get_register("MODE_In").unwrap();
+    assert_eq!(gpio.register_display_name(mode), "GPIO_MODE_In_A");
+}
+
+#[test]
+fn peripheral_array() {
+    let peripheral = |name: &str| {
+        device_xml(&format!(
+            "
+            <peripheral>
+                <dim>2</dim>
+                <dimIncrement>0x1000</dimIncrement>
+                <name>{}</name>
+                <baseAddress>0x40008000</baseAddress>
+            </peripheral>",
+            name
+        ))
+    };
+    let device = parser::parse(&peripheral("DMA%s")).unwrap();
+    let Peripheral::Array(info, dim) = &device.peripherals[0] else {
+        panic!("`DMA%s` is not an array");
+    };
+    assert_eq!(info.name, "DMA%s");
+    assert_eq!((dim.dim, dim.dim_increment), (2, 0x1000));
+    let instances: Vec<_> = crate::svd::peripheral::expand(info, dim)
+        .map(|p| (p.name, p.base_address))
+        .collect();
+    assert_eq!(
+        instances,
+        [
+            ("DMA0".to_string(), 0x40008000),
+            ("DMA1".to_string(), 0x40009000)
+        ]
+    );
+
+    let error = parser::parse(&peripheral("DMA")).unwrap_err();
+    assert!(format!("{:#}", error)
+        .contains("Name `DMA` in tag `peripheral` is missing a %s placeholder"));
+}
+
+#[test]
+fn effective_register_properties() {
+    use crate::svd::Access;
+
+    let xml = device_xml(
+        "
+        <peripheral>
+            <name>ROM</name>
+            <baseAddress>0x40000000</baseAddress>
+            <access>read-only</access>
+            <resetValue>0xff</resetValue>
+        </peripheral>
+        <peripheral>
+            <name>RAM</name>
+            <baseAddress>0x40001000</baseAddress>
+        </peripheral>",
+    )
+    .replace(
+        "<peripherals>",
+        "<size>32</size><access>read-write</access><resetValue>0</resetValue><peripherals>",
+    );
+    let device = parser::parse(&xml).unwrap();
+
+    let rom = device.get_peripheral("ROM").unwrap();
+    assert_eq!(rom.default_register_properties.size, None);
+    let properties = rom.effective_register_properties(&device);
+    assert_eq!(properties.access, Some(Access::ReadOnly));
+    assert_eq!(properties.reset_value, Some(0xff));
+    assert_eq!(properties.size, Some(32));
+
+    let ram = device.get_peripheral("RAM").unwrap();
+    let properties = ram.effective_register_properties(&device);
+    assert_eq!(properties, device.default_register_properties);
+    assert_eq!(properties.access, Some(Access::ReadWrite));
+}
+
+#[test]
+fn malformed_numbers() {
+    let error =
+        |peripheral: &str| format!("{:#}", parser::parse(&device_xml(peripheral)).unwrap_err());
+
+    let err = error(
+        "<peripheral>
+            <name>GPIOA</name>
+            <baseAddress>0xX40021000</baseAddress>
+        </peripheral>",
+    );
+    assert!(err.contains("In peripheral `GPIOA`"), "{}", err);
+    assert!(err.contains("`0xX40021000`"), "{}", err);
+
+    let children = [
+        ("register", ""),
+        (
+            "cluster",
+            "<register><name>R</name><addressOffset>0</addressOffset></register>",
+        ),
+    ];
+    for (tag, children) in children {
+        let err = error(&format!(
+            "<peripheral>
+                <name>GPIOA</name>
+                <baseAddress>0x40021000</baseAddress>
+                <registers>
+                    <{0}>
+                        <name>MODER</name>
+                        <addressOffset>0</addressOffset>
+                        <size>3x2</size>
+                        {1}
+                    </{0}>
+                </registers>
+            </peripheral>",
+            tag, children
+        ));
+        assert!(err.contains("In peripheral `GPIOA`"), "{}", err);
+        assert!(err.contains("`3x2`"), "{}", err);
+    }
+}
+
+#[test]
+fn malformed_defaults() {
+    for (tag, value) in [
+        ("size", "thirty-two"),
+        ("resetValue", "hello"),
+        ("resetMask", "0xFFFFFFFFFFFFFFFFF"),
+        ("access", "read-maybe"),
+    ] {
+        let xml = device_xml("").replace(
+            "<width>32</width>",
+            &format!("<width>32</width><{0}>{1}</{0}>", tag, value),
+        );
+        let err = format!("{:#}", parser::parse(&xml).unwrap_err());
+        assert!(err.contains("In device `TEST`"), "{}", err);
+        assert!(err.contains(tag), "{}", err);
+        assert!(err.contains(value), "{}", err);
+    }
+}
+
+#[test]
+fn invalid_xml() {
+    let err = parser::parse("not xml").unwrap_err().to_string();
+    assert_eq!(err, "Invalid XML: unknown token at 1:1");
+
+    let truncated = "<device>\n  <name>X</name>\n  <peripherals><peripheral><na";
+    let err = parser::parse(truncated).unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "Invalid XML: the root node was opened but never closed at end of input 3:31"
+    );
+}
+
+#[test]
+fn error_position() {
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>RCC</name>
+                <baseAddress>0x40021000</baseAddress>
+                <registers>
+                    <register>
+                        <name>CR</name>
+                    </register>
+                </registers>
+            </peripheral>",
+    );
+    let err = parser::parse(&xml).unwrap_err();
+    let chain: Vec<String> = err.chain().map(ToString::to_string).collect();
+    assert_eq!(
+        chain,
+        [
+            "In device `TEST`",
+            "In peripheral `RCC`",
+            "Parsing register `CR` at 13:21",
+            "Expected a <addressOffset> tag, found none",
+        ]
+    );
+}
+
+#[test]
+fn error_context_path() {
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>TIM1</name>
+                <baseAddress>0x40012C00</baseAddress>
+                <registers>
+                    <cluster>
+                        <dim>4</dim>
+                        <dimIncrement>4</dimIncrement>
+                        <name>CH[%s]</name>
+                        <addressOffset>0x34</addressOffset>
+                        <register>
+                            <name>CCR</name>
+                            <addressOffset>0</addressOffset>
+                            <fields>
+                                <field>
+                                    <name>CCS</name>
+                                    <bitOffset>0</bitOffset>
+                                    <bitWidth>2</bitWidth>
+                                    <enumeratedValues>
+                                        <name>CCS</name>
+                                        <enumeratedValue>
+                                            <name>OUTPUT</name>
+                                            <value>output</value>
+                                        </enumeratedValue>
+                                    </enumeratedValues>
+                                </field>
+                            </fields>
+                        </register>
+                    </cluster>
+                </registers>
+            </peripheral>",
+    );
+    let err = parser::parse(&xml).unwrap_err();
+    let chain: Vec<String> = err.chain().map(ToString::to_string).collect();
+    assert_eq!(
+        chain[..7],
+        [
+            "In device `TEST`",
+            "In peripheral `TIM1`",
+            "In cluster `CH[%s]`",
+            "In register `CCR`",
+            "In field `CCS`",
+            "In enumeratedValues `CCS`",
+            "In enumeratedValue `OUTPUT`",
+        ]
+    );
+    assert!(chain[8].contains("`output`"), "{}", chain[8]);
+}
+
+#[test]
+fn skip_invalid() {
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>GPIO</name>
+                <baseAddress>0x40000000</baseAddress>
+                <registers>
+                    <register>
+                        <name>CTRL</name>
+                        <addressOffset>0</addressOffset>
+                        <fields>
+                            <field><name>EN</name><bitOffset>0</bitOffset><bitWidth>1</bitWidth></field>
+                            <field><name>MODE</name><bitOffset>1</bitOffset><bitWidth>two</bitWidth></field>
+                        </fields>
+                    </register>
+                    <register>
+                        <name>STATUS</name>
+                    </register>
+                    <register>
+                        <name>DATA</name>
+                        <addressOffset>8</addressOffset>
+                    </register>
+                </registers>
+            </peripheral>
+            <peripheral>
+                <name>BROKEN</name>
+                <baseAddress>0x4000_1000</baseAddress>
+            </peripheral>
+            <peripheral>
+                <name>UART</name>
+                <baseAddress>0x40002000</baseAddress>
+            </peripheral>",
+    );
+
+    // fail fast by default
+    assert!(parser::parse(&xml).is_err());
+
+    let config = parser::Config::default().skip_invalid(true);
+    let output = parser::parse_full(&xml, &config).unwrap();
+    let skipped: Vec<(&str, u32)> = output
+        .skipped
+        .iter()
+        .map(|e| (e.message.as_str(), e.line))
+        .collect();
+    assert_eq!(skipped.len(), 3, "{:#?}", output.skipped);
+    assert!(skipped[0].0.contains("field `MODE`"), "{}", skipped[0].0);
+    assert_eq!(skipped[0].1, 18);
+    assert!(
+        skipped[1].0.contains("register `STATUS`"),
+        "{}",
+        skipped[1].0
+    );
+    assert_eq!(skipped[1].1, 21);
+    assert!(
+        skipped[2].0.contains("peripheral `BROKEN`"),
+        "{}",
+        skipped[2].0
+    );
+    assert_eq!(skipped[2].1, 30);
+    assert!(matches!(
+        output.skipped[1].error.error(),
+        parser::SVDError::MissingTag(tag) if tag == "addressOffset"
+    ));
+
+    let device = output.device;
+    let names: Vec<&str> = device.peripherals.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["GPIO", "UART"]);
+    let gpio = device.get_peripheral("GPIO").unwrap();
+    let registers: Vec<&str> = gpio.registers().map(|r| r.name.as_str()).collect();
+    assert_eq!(registers, ["CTRL", "DATA"]);
+    let fields: Vec<&str> = gpio
+        .get_register("CTRL")
+        .unwrap()
+        .fields()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(fields, ["EN"]);
+
+    // elements parsed directly don't skip anything
+    let doc = Document::parse(&xml).unwrap();
+    assert!(Device::parse(&doc.root_element(), &config).is_err());
+}
+
+#[test]
+fn unknown_elements() {
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>GPIO</name>
+                <desciption>General purpose I/O</desciption>
+                <baseAddress>0x40000000</baseAddress>
+                <registers>
+                    <register>
+                        <name>CTRL</name>
+                        <addressOffset>0</addressOffset>
+                        <bitOffset>0</bitOffset>
+                        <fields>
+                            <field>
+                                <name>MODE</name>
+                                <bitRange>[1:0]</bitRange>
+                                <enumeratedValues>
+                                    <enumeratedValue>
+                                        <name>IN</name>
+                                        <value>0</value>
+                                        <default>true</default>
+                                    </enumeratedValue>
+                                </enumeratedValues>
+                            </field>
+                        </fields>
+                    </register>
+                </registers>
+            </peripheral>",
+    )
+    .replace(
+        "<peripherals>",
+        "<vendorExtensions><anything/></vendorExtensions><peripherals>",
+    );
+    let output = parser::parse_full(&xml, &Default::default()).unwrap();
+    let warnings: Vec<(&str, u32)> = output
+        .warnings
+        .iter()
+        .map(|w| (w.message.as_str(), w.line))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            ("Unknown element <desciption> in <peripheral> `GPIO`", 11),
+            ("Unknown element <bitOffset> in <register> `GPIO.CTRL`", 17),
+            (
+                "Unknown element <default> in <enumeratedValue> of `GPIO.CTRL.MODE`",
+                26
+            ),
+        ]
+    );
+    // unknown elements don't change the parsed device
+    assert_eq!(output.device.peripherals[0].description, None);
+}
+
+#[test]
+fn modify_and_encode() {
+    use crate::svd::{EnumeratedValue, EnumeratedValues};
+
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>GPIO</name>
+                <baseAddress>0x40000000</baseAddress>
+                <registers>
+                    <register>
+                        <name>CTRL</name>
+                        <addressOffset>0</addressOffset>
+                        <resetValue>0x5</resetValue>
+                        <fields>
+                            <field><name>MODE</name><bitRange>[1:0]</bitRange></field>
+                        </fields>
+                    </register>
+                </registers>
+            </peripheral>",
+    );
+    let mut device = parser::parse(&xml).unwrap();
+
+    // fix the reset value and add the missing enumerated values
+    let ctrl = device
+        .get_mut_peripheral("GPIO")
+        .unwrap()
+        .get_mut_register("CTRL")
+        .unwrap();
+    ctrl.properties.reset_value = Some(0x1);
+    let values = ["INPUT", "OUTPUT"]
+        .iter()
+        .zip(0..)
+        .map(|(name, value)| {
+            EnumeratedValue::builder()
+                .name(name.to_string())
+                .value(Some(value))
+                .build(ValidateLevel::Strict)
+                .unwrap()
+        })
+        .collect();
+    ctrl.get_mut_field("MODE").unwrap().enumerated_values = vec![EnumeratedValues::builder()
+        .values(values)
+        .build(ValidateLevel::Strict)
+        .unwrap()];
+
+    let encoded = svd_encoder::encode(&device).unwrap();
+    assert!(encoded.starts_with("<?xml version=\"1.0\""), "{}", encoded);
+    let reparsed = parser::parse(&encoded).unwrap();
+    assert_eq!(reparsed, device);
+    let ctrl = reparsed
+        .get_peripheral("GPIO")
+        .unwrap()
+        .get_register("CTRL")
+        .unwrap();
+    assert_eq!(ctrl.properties.reset_value, Some(0x1));
+    assert_eq!(
+        ctrl.get_field("MODE").unwrap().enumerated_values[0]
+            .values
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn number_styles() {
+    use crate::svd::{NumberStyle, Radix};
+
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>GPIO</name>
+                <baseAddress>0x40021000</baseAddress>
+                <resetValue>0</resetValue>
+                <registers>
+                    <register>
+                        <name>CTRL</name>
+                        <addressOffset>0x04</addressOffset>
+                        <size>32</size>
+                        <resetValue>0x0000abcd</resetValue>
+                        <fields>
+                            <field>
+                                <name>MODE</name>
+                                <bitRange>[1:0]</bitRange>
+                                <enumeratedValues>
+                                    <enumeratedValue><name>A</name><value>#01</value></enumeratedValue>
+                                    <enumeratedValue><name>B</name><value>0b10</value></enumeratedValue>
+                                </enumeratedValues>
+                            </field>
+                        </fields>
+                    </register>
+                </registers>
+            </peripheral>",
+    );
+
+    let config = parser::Config::default().number_styles(true);
+    let device = parser::parse_with_config(&xml, &config).unwrap();
+    let ctrl = device.peripherals[0].get_register("CTRL").unwrap();
+    assert_eq!(
+        ctrl.number_styles.get("resetValue"),
+        Some(&NumberStyle {
+            radix: Radix::Hex,
+            digits: 8,
+            uppercase: false
+        })
+    );
+
+    // kept by builders
+    let rebuilt = crate::svd::RegisterInfoBuilder::from((**ctrl).clone())
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    assert_eq!(
+        rebuilt.number_styles.get("resetValue"),
+        ctrl.number_styles.get("resetValue")
+    );
+    // text split by a comment
+    let commented = parser::parse_with_config(
+        &xml.replace("<size>32</size>", "<size><!-- bits -->0x20</size>"),
+        &config,
+    )
+    .unwrap();
+    let size = commented.peripherals[0]
+        .get_register("CTRL")
+        .unwrap()
+        .number_styles
+        .get("size");
+    assert_eq!(size.map(|s| s.radix), Some(Radix::Hex));
+
+    let encoded = svd_encoder::encode(&device).unwrap();
+    for element in [
+        "<baseAddress>0x40021000</baseAddress>",
+        "<resetValue>0</resetValue>",
+        "<addressOffset>0x04</addressOffset>",
+        "<size>32</size>",
+        "<resetValue>0x0000abcd</resetValue>",
+        "<value>#01</value>",
+        "<value>0b10</value>",
+    ] {
+        assert!(encoded.contains(element), "{} in {}", element, encoded);
+    }
+    assert_eq!(
+        parser::parse(&encoded).unwrap(),
+        parser::parse(&xml).unwrap()
+    );
+
+    // changed values keep the style
+    let mut device = device;
+    device.peripherals[0]
+        .get_mut_register("CTRL")
+        .unwrap()
+        .properties
+        .reset_value = Some(0x12);
+    let encoded = svd_encoder::encode(&device).unwrap();
+    assert!(encoded.contains("<resetValue>0x00000012</resetValue>"));
+
+    // not recorded by default
+    let styled = device;
+    let device = parser::parse(&xml).unwrap();
+    assert!(device.peripherals[0].number_styles.is_empty());
+    // styles are left out of comparisons and hashes
+    let hash = |p: &crate::svd::Peripheral| {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        p.hash(&mut hasher);
+        hasher.finish()
+    };
+    let mut unstyled = device.clone();
+    unstyled.peripherals[0]
+        .get_mut_register("CTRL")
+        .unwrap()
+        .properties
+        .reset_value = Some(0x12);
+    assert_eq!(unstyled.peripherals[0], styled.peripherals[0]);
+    assert_eq!(hash(&unstyled.peripherals[0]), hash(&styled.peripherals[0]));
+    let encoded = svd_encoder::encode(&device).unwrap();
+    assert!(
+        encoded.contains("<addressOffset>0x4</addressOffset>"),
+        "{}",
+        encoded
+    );
+}
+
+#[test]
+fn detect_number_style() {
+    use crate::svd::{NumberStyle, Radix};
+
+    let style = |text| NumberStyle::detect(text).map(|s| (s.radix, s.digits, s.uppercase));
+    assert_eq!(style("42"), Some((Radix::Dec, 2, true)));
+    assert_eq!(style(" 0X00FF "), Some((Radix::Hex, 4, true)));
+    assert_eq!(style("0xff"), Some((Radix::Hex, 2, false)));
+    assert_eq!(style("0b0110"), Some((Radix::Bin, 4, true)));
+    assert_eq!(style("#1"), Some((Radix::Hash, 1, true)));
+    for text in ["4k", "-1", "#1x0", "0x", ""] {
+        assert_eq!(style(text), None, "{}", text);
+    }
+    let format = |text, value| NumberStyle::detect(text).unwrap().format(value);
+    assert_eq!(format("0x0000abcd", 0x12), "0x00000012");
+    assert_eq!(format("0X1F", 0xabc), "0xABC");
+    assert_eq!(format("007", 1234), "1234");
+    assert_eq!(format("#0000", 0b11), "#0011");
+}
+
+#[test]
+fn encoder_style() {
+    use svd_encoder::{DerivableSorting, Indent, Sorting};
+
+    let xml = device_xml(
+        "
+            <peripheral>
+                <name>UART</name>
+                <baseAddress>0x40002000</baseAddress>
+                <registers></registers>
+            </peripheral>
+            <peripheral>
+                <name>GPIO</name>
+                <baseAddress>0x40000000</baseAddress>
+            </peripheral>",
+    );
+    let device = parser::parse(&xml).unwrap();
+
+    let default = svd_encoder::encode(&device).unwrap();
+    assert!(default.contains("\n  <name>TEST</name>"), "{}", default);
+    assert!(default.contains("<registers/>"), "{}", default);
+    assert!(default.find("UART").unwrap() < default.find("GPIO").unwrap());
+
+    let config = svd_encoder::Config::default()
+        .indent(Indent::Tab)
+        .self_closing(false)
+        .peripheral_sorting(DerivableSorting::Unchanged(Some(Sorting::Name)));
+    let styled = svd_encoder::encode_with_config(&device, &config).unwrap();
+    assert!(styled.contains("\n\t<name>TEST</name>"), "{}", styled);
+    assert!(!styled.contains("<registers/>"), "{}", styled);
+    assert!(styled.contains("</registers>"), "{}", styled);
+    assert!(styled.find("GPIO").unwrap() < styled.find("UART").unwrap());
+
+    let compact = svd_encoder::Config::default().indent(Indent::None);
+    let compact = svd_encoder::encode_with_config(&device, &compact).unwrap();
+    assert!(!compact.contains("\n"), "{}", compact);
+
+    let mut sorted = device.clone();
+    sorted.peripherals.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(parser::parse(&default).unwrap(), device);
+    assert_eq!(parser::parse(&styled).unwrap(), sorted);
+    assert_eq!(parser::parse(&compact).unwrap(), device);
+
+    let mut config = svd_encoder::Config::default();
+    config.update("indent", "4");
+    config.update("self_closing", "false");
+    assert_eq!(
+        (config.indent, config.self_closing),
+        (Indent::Spaces(4), false)
+    );
+}
+
+#[test]
+fn encode_to_writer() {
+    use std::io;
+
+    struct Full(usize);
+    impl io::Write for Full {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 < buf.len() {
+                return Err(io::Error::other("disk full"));
+            }
+            self.0 -= buf.len();
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let device = parser::parse(&device_xml(UART_ALIASES)).unwrap();
+    let mut written = Vec::new();
+    svd_encoder::encode_to(&device, &mut written).unwrap();
+    assert_eq!(
+        String::from_utf8(written).unwrap(),
+        svd_encoder::encode(&device).unwrap()
+    );
+
+    let err = svd_encoder::encode_to(&device, Full(100)).unwrap_err();
+    assert!(
+        matches!(&err, svd_encoder::EncodeError::Io(e) if e.contains("disk full")),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn effective_properties() {
+    let device = parser::parse(
+        "
+        <device>
+            <name>TEST</name>
+            <size>8</size>
+            <access>read-only</access>
+            <resetValue>0x1</resetValue>
+            <resetMask>0xF</resetMask>
+            <peripherals>
+                <peripheral>
+                    <name>P</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <size>16</size>
+                    <registers>
+                        <register><name>R0</name><addressOffset>0x0</addressOffset></register>
+                        <cluster>
+                            <name>C</name>
+                            <addressOffset>0x10</addressOffset>
+                            <access>write-only</access>
+                            <register><name>R1</name><addressOffset>0x0</addressOffset></register>
+                            <cluster>
+                                <name>D</name>
+                                <addressOffset>0x10</addressOffset>
+                                <resetValue>0x2</resetValue>
+                                <register>
+                                    <name>R2</name>
+                                    <addressOffset>0x0</addressOffset>
+                                    <resetMask>0x3</resetMask>
+                                </register>
+                            </cluster>
+                        </cluster>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    let properties = |device: &Device, path: &str| {
+        device
+            .effective_properties(path)
+            .map(|p| (p.size, p.access, p.reset_value, p.reset_mask))
+    };
+    // the closest level setting a property wins
+    assert_eq!(
+        properties(&device, "P.R0"),
+        Some((16, Access::ReadOnly, 0x1, 0xF))
+    );
+    assert_eq!(
+        properties(&device, "P.C.R1"),
+        Some((16, Access::WriteOnly, 0x1, 0xF))
+    );
+    assert_eq!(
+        properties(&device, "P.C.D.R2"),
+        Some((16, Access::WriteOnly, 0x2, 0x3))
+    );
+    assert_eq!(properties(&device, "P.C.R2"), None);
+
+    // defaults if no level sets them
+    let device = parser::parse(&device_xml(
+        "<peripheral>
+            <name>P</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register><name>R0</name><addressOffset>0x0</addressOffset></register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    assert_eq!(
+        properties(&device, "P.R0"),
+        Some((32, Access::ReadWrite, 0, 0xFFFF_FFFF))
+    );
+}
+
+#[test]
+fn address_map() {
+    let device = parser::parse(&device_xml(
+        "<peripheral>
+            <name>DMA</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register><name>CTRL</name><addressOffset>0x0</addressOffset></register>
+                <cluster>
+                    <dim>2</dim>
+                    <dimIncrement>0x40</dimIncrement>
+                    <name>CH%s</name>
+                    <addressOffset>0x100</addressOffset>
+                    <register><name>CFG</name><addressOffset>0x0</addressOffset></register>
+                    <cluster>
+                        <dim>2</dim>
+                        <dimIncrement>0x10</dimIncrement>
+                        <name>SG%s</name>
+                        <addressOffset>0x20</addressOffset>
+                        <register>
+                            <dim>2</dim>
+                            <dimIncrement>0x4</dimIncrement>
+                            <name>ADDR%s</name>
+                            <addressOffset>0x0</addressOffset>
+                            <size>16</size>
+                        </register>
+                        <register>
+                            <name>LEN</name>
+                            <addressOffset>0x8</addressOffset>
+                            <size>8</size>
+                        </register>
+                    </cluster>
+                </cluster>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    let map: Vec<_> = device
+        .address_map()
+        .into_iter()
+        .map(|e| (e.path, e.address, e.size))
+        .collect();
+    let expected = [
+        ("DMA.CTRL", 0x4000_0000, 4),
+        ("DMA.CH0.CFG", 0x4000_0100, 4),
+        ("DMA.CH0.SG0.ADDR0", 0x4000_0120, 2),
+        ("DMA.CH0.SG0.ADDR1", 0x4000_0124, 2),
+        ("DMA.CH0.SG0.LEN", 0x4000_0128, 1),
+        ("DMA.CH0.SG1.ADDR0", 0x4000_0130, 2),
+        ("DMA.CH0.SG1.ADDR1", 0x4000_0134, 2),
+        ("DMA.CH0.SG1.LEN", 0x4000_0138, 1),
+        ("DMA.CH1.CFG", 0x4000_0140, 4),
+        ("DMA.CH1.SG0.ADDR0", 0x4000_0160, 2),
+        ("DMA.CH1.SG0.ADDR1", 0x4000_0164, 2),
+        ("DMA.CH1.SG0.LEN", 0x4000_0168, 1),
+        ("DMA.CH1.SG1.ADDR0", 0x4000_0170, 2),
+        ("DMA.CH1.SG1.ADDR1", 0x4000_0174, 2),
+        ("DMA.CH1.SG1.LEN", 0x4000_0178, 1),
+    ]
+    .map(|(path, address, size)| (path.to_string(), address, size));
+    assert_eq!(map, expected);
+
+    // registers past the end of the address space are left out
+    let mut device = device;
+    let dma = device.get_mut_peripheral("DMA").unwrap();
+    dma.base_address = u64::MAX - 0x100;
+    dma.get_mut_register("CTRL").unwrap().properties.size = Some(u32::MAX);
+    let map: Vec<_> = device
+        .address_map()
+        .into_iter()
+        .map(|e| (e.path, e.address, e.size))
+        .collect();
+    assert_eq!(
+        map,
+        [
+            ("DMA.CTRL".to_string(), u64::MAX - 0x100, 0x2000_0000),
+            ("DMA.CH0.CFG".to_string(), u64::MAX, 4),
+        ]
+    );
+}
+
+#[test]
+fn get_path() {
+    use crate::svd::PathElement;
+
+    let device = parser::parse(&device_xml(
+        "<peripheral>
+            <name>TIM1</name>
+            <baseAddress>0x40000000</baseAddress>
+            <registers>
+                <register>
+                    <name>CR1</name>
+                    <addressOffset>0x0</addressOffset>
+                    <fields><field><name>CEN</name><bitRange>[0:0]</bitRange></field></fields>
+                </register>
+                <register>
+                    <dim>4</dim>
+                    <dimIncrement>0x4</dimIncrement>
+                    <dimIndex>1-4</dimIndex>
+                    <name>CCR%s</name>
+                    <addressOffset>0x10</addressOffset>
+                    <fields><field><name>CCE</name><bitRange>[0:0]</bitRange></field></fields>
+                </register>
+                <cluster>
+                    <name>CH</name>
+                    <addressOffset>0x20</addressOffset>
+                    <register><name>CTRL</name><addressOffset>0x0</addressOffset></register>
+                </cluster>
+                <register>
+                    <name>CCMR</name>
+                    <description>output</description>
+                    <alternateGroup>Output</alternateGroup>
+                    <addressOffset>0x30</addressOffset>
+                </register>
+                <register>
+                    <name>CCMR</name>
+                    <description>input</description>
+                    <alternateGroup>Input</alternateGroup>
+                    <addressOffset>0x30</addressOffset>
+                </register>
+                <register>
+                    <dim>2</dim>
+                    <dimIncrement>0x4</dimIncrement>
+                    <name>DMA%s_CFG</name>
+                    <addressOffset>0x40</addressOffset>
+                </register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    let name = |element: Option<PathElement>| {
+        element.map(|e| match e {
+            PathElement::Peripheral(p) => format!("peripheral {}", p.name),
+            PathElement::Cluster(c) => format!("cluster {}", c.name),
+            PathElement::Register(r) => format!("register {}", r.name),
+            PathElement::Field(f) => format!("field {}", f.name),
+        })
+    };
+    let get = |path| name(device.get_path(path));
+
+    assert_eq!(get("TIM1").as_deref(), Some("peripheral TIM1"));
+    assert_eq!(get("TIM1.CR1.CEN").as_deref(), Some("field CEN"));
+    assert_eq!(get("TIM1.CCR%s.CCE").as_deref(), Some("field CCE"));
+    assert_eq!(get("TIM1.CCR3").as_deref(), Some("register CCR%s"));
+    assert_eq!(get("TIM1.CH").as_deref(), Some("cluster CH"));
+    assert_eq!(get("TIM1.CH.CTRL").as_deref(), Some("register CTRL"));
+    assert_eq!(get("TIM1.CCR5"), None);
+    assert_eq!(get("TIM1.CCR"), None);
+    assert_eq!(get("TIM1.DMA1_CFG").as_deref(), Some("register DMA%s_CFG"));
+    assert_eq!(get("TIM1.DMA01_CFG"), None);
+    assert_eq!(get("TIM1.DMA2_CFG"), None);
+    assert_eq!(get("TIM1.DMA_CFG"), None);
+    // alternate groups agree with `get_register`
+    let tim1 = device.get_peripheral("TIM1").unwrap();
+    for path in ["CCMR", "CCMR_Input", "CCMR_Output"] {
+        match device.get_path(&format!("TIM1.{}", path)) {
+            Some(PathElement::Register(r)) => assert_eq!(Some(r), tim1.get_register(path)),
+            _ => panic!("{} not found", path),
+        }
+    }
+    assert_eq!(
+        tim1.get_register("CCMR_Input")
+            .unwrap()
+            .description
+            .as_deref(),
+        Some("input")
+    );
+    assert_eq!(get("TIM1.CR1.CEN.X"), None);
+    assert_eq!(get("tim1.cr1.cen"), None);
+    assert_eq!(
+        name(device.get_path_ignore_case("tim1.cr1.cen")).as_deref(),
+        Some("field CEN")
+    );
+
+    let tim1 = device.get_peripheral("TIM1").unwrap();
+    assert_eq!(tim1.get_register("CH.CTRL").unwrap().name, "CTRL");
+    assert_eq!(tim1.get_register("CH.MISSING"), None);
+}
+
+#[test]
+fn find_at_address() {
+    let device = parser::parse(&device_xml(
+        "<peripheral>
+            <name>TIM1</name>
+            <baseAddress>0x40021000</baseAddress>
+            <registers>
+                <register>
+                    <name>CCMR1_Output</name>
+                    <addressOffset>0x0</addressOffset>
+                </register>
+                <register>
+                    <name>CCMR1_Input</name>
+                    <alternateRegister>CCMR1_Output</alternateRegister>
+                    <addressOffset>0x0</addressOffset>
+                </register>
+                <register>
+                    <name>SR</name>
+                    <addressOffset>0x8</addressOffset>
+                    <size>16</size>
+                </register>
+                <register>
+                    <dim>4</dim>
+                    <dimIncrement>0x4</dimIncrement>
+                    <dimIndex>1-4</dimIndex>
+                    <name>CCR%s</name>
+                    <addressOffset>0x10</addressOffset>
+                </register>
+            </registers>
+        </peripheral>",
+    ))
+    .unwrap();
+    let found = |address| {
+        device
+            .find_at_address(address)
+            .map(|m| (m.peripheral, m.entry.register.name, m.entry.index, m.offset))
+    };
+    assert_eq!(
+        found(0x4002_1018),
+        Some(("TIM1".into(), "CCR3".into(), Some("3".into()), 0))
+    );
+    assert_eq!(
+        found(0x4002_101B),
+        Some(("TIM1".into(), "CCR3".into(), Some("3".into()), 3))
+    );
+    assert_eq!(
+        found(0x4002_1009),
+        Some(("TIM1".into(), "SR".into(), None, 1))
+    );
+    // SR is 16 bits wide, the rest of the word is not mapped
+    assert_eq!(found(0x4002_100A), None);
+    assert_eq!(found(0x4002_1020), None);
+
+    let all: Vec<_> = device
+        .find_all_at_address(0x4002_1002)
+        .into_iter()
+        .map(|m| (m.entry.register.name, m.offset))
+        .collect();
+    assert_eq!(all, [("CCMR1_Output".into(), 2), ("CCMR1_Input".into(), 2)]);
+}
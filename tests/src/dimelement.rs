@@ -1,5 +1,6 @@
 use super::run_test;
-use crate::svd::{DimElement, ValidateLevel};
+use crate::svd::{dimelement, DimElement, Register, ValidateLevel};
+use svd_parser::Parse;
 
 #[test]
 fn decode_encode() {
@@ -127,3 +128,124 @@ fn decode_encode() {
 
 #[test]
 fn decode_encode_one_element() {}
+
+#[test]
+fn zero_padded_indexes() {
+    use crate::svd::{register, RegisterInfo};
+
+    let padded: Vec<String> = ["00", "01", "02", "03"].map(String::from).to_vec();
+    assert_eq!(DimElement::parse_indexes("00-03"), Some(padded.clone()));
+    // padding of the start is only kept if both ends have the same width
+    assert_eq!(
+        DimElement::parse_indexes("0-3"),
+        Some(["0", "1", "2", "3"].map(String::from).to_vec())
+    );
+    // lists are kept verbatim
+    assert_eq!(
+        DimElement::parse_indexes("007,08"),
+        Some(vec!["007".to_string(), "08".to_string()])
+    );
+
+    let dim = DimElement::builder()
+        .dim(4)
+        .dim_increment(4)
+        .dim_index(Some(padded))
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    assert_eq!(dim.indexes_as_range(), None);
+    assert_eq!(dim.indexes_as_padded_range(), Some((0..=3, 2)));
+    let xml = "<dimElement>
+            <dim>4</dim>
+            <dimIncrement>0x4</dimIncrement>
+            <dimIndex>00-03</dimIndex>
+        </dimElement>
+        ";
+    run_test::<DimElement>(&[(dim.clone(), xml, xml)], None, None);
+
+    let info = RegisterInfo::builder()
+        .name("UART%s".to_string())
+        .address_offset(0)
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    let names: Vec<String> = register::expand(&info, &dim).map(|r| r.name).collect();
+    assert_eq!(names, ["UART00", "UART01", "UART02", "UART03"]);
+
+    // not a sequence
+    let dim = DimElement::builder()
+        .dim(2)
+        .dim_increment(4)
+        .dim_index(Some(vec!["01".to_string(), "03".to_string()]))
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    assert_eq!(dim.indexes_as_padded_range(), None);
+}
+
+#[test]
+fn malformed() {
+    let register = |dim: &str| {
+        let xml = format!(
+            "<register>
+                <name>CH%s</name>
+                <addressOffset>0</addressOffset>
+                {}
+            </register>",
+            dim
+        );
+        let tree = roxmltree::Document::parse(&xml).unwrap();
+        let config = svd_parser::Config::default().validate_level(ValidateLevel::Disabled);
+        Register::parse(&tree.root_element(), &config)
+            .unwrap_err()
+            .to_string()
+    };
+
+    let err = register("<dim>4</dim><dimIncrement>four</dimIncrement>");
+    assert!(err.contains("`four`"), "{}", err);
+    let err = register("<dim>4</dim><dimIncrement>4</dimIncrement><dimIndex/>");
+    assert!(err.contains("<dimIndex>"), "{}", err);
+    let err = register("<dim>4</dim><dimIncrement>4</dimIncrement><dimIndex>A,B</dimIndex>");
+    assert_eq!(err, "dimIndex tag must contain 4 indexes, found 2");
+    // huge ranges are rejected before they are expanded
+    let err =
+        register("<dim>4</dim><dimIncrement>4</dimIncrement><dimIndex>0-4294967295</dimIndex>");
+    assert_eq!(err, "dimIndex tag must contain 4 indexes, found 4294967296");
+    let err = register(
+        "<dim>4</dim><dimIncrement>4</dimIncrement><dimIndex>0-18446744073709551615</dimIndex>",
+    );
+    assert!(
+        err.starts_with("dimIndex tag must contain 4 indexes"),
+        "{}",
+        err
+    );
+    let err = register("<dim>4</dim><dimIncrement>4</dimIncrement><dimIndex>3-0</dimIndex>");
+    assert_eq!(err, "Failed to parse dimIndex");
+}
+
+#[test]
+fn parse_indexes() {
+    let parse = |text: &str| DimElement::parse_indexes(text);
+    let indexes = |list: &[&str]| Some(list.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+    assert_eq!(parse("0-3"), indexes(&["0", "1", "2", "3"]));
+    assert_eq!(parse("A-D"), indexes(&["A", "B", "C", "D"]));
+    assert_eq!(parse("1,2,3"), indexes(&["1", "2", "3"]));
+    assert_eq!(parse("4-4"), indexes(&["4"]));
+    assert_eq!(parse("08-10"), indexes(&["08", "09", "10"]));
+    // whitespace after commas and ranges among list entries
+    assert_eq!(parse("A, B,\n C"), indexes(&["A", "B", "C"]));
+    assert_eq!(parse("X,0-2,a-b"), indexes(&["X", "0", "1", "2", "a", "b"]));
+    assert_eq!(parse("3-1"), None);
+    assert_eq!(parse("A-3"), None);
+    assert_eq!(parse("AB-CD"), None);
+    // the limit holds for the whole list, not for each range
+    let max = |text: &str, max: u32| DimElement::parse_indexes_max(text, max);
+    assert_eq!(
+        max("0-3,4-4294967295", 8),
+        Err(dimelement::Error::IndexCount(8, 4294967296))
+    );
+    assert_eq!(max("A,B,C", 2), Err(dimelement::Error::IndexCount(2, 3)));
+    assert_eq!(max("0-1,X", 3).map(|v| v.len()), Ok(3));
+    // letter ranges count too
+    assert_eq!(max("A-Z", 4), Err(dimelement::Error::IndexCount(4, 26)));
+    assert_eq!(max("X,a-c", 3), Err(dimelement::Error::IndexCount(3, 4)));
+    assert_eq!(max("a-c", 3).map(|v| v.len()), Ok(3));
+    assert_eq!(parse("0-3,4-4294967295"), None);
+}
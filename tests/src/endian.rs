@@ -24,3 +24,38 @@ fn decode_encode() {
 
     run_test::<Endian>(&tests[..], None, None);
 }
+
+#[test]
+fn display() {
+    for endian in [
+        Endian::Little,
+        Endian::Big,
+        Endian::Selectable,
+        Endian::Other,
+    ] {
+        assert_eq!(Endian::parse_str(&endian.to_string()), Some(endian));
+    }
+}
+
+#[test]
+fn unknown() {
+    let xml = "
+        <device>
+            <name>CHIP</name>
+            <cpu>
+                <name>CM4</name>
+                <revision>r0p1</revision>
+                <endian>middle</endian>
+                <mpuPresent>false</mpuPresent>
+                <fpuPresent>true</fpuPresent>
+                <nvicPrioBits>4</nvicPrioBits>
+                <vendorSystickConfig>false</vendorSystickConfig>
+            </cpu>
+            <addressUnitBits>8</addressUnitBits>
+            <width>32</width>
+            <peripherals></peripherals>
+        </device>";
+    let error = format!("{:#}", svd_parser::parse(xml).unwrap_err());
+    assert!(error.contains("In cpu `CM4`"), "{}", error);
+    assert!(error.contains("Unknown endianness `middle`"), "{}", error);
+}
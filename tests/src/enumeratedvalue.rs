@@ -1,5 +1,7 @@
 use super::run_test;
-use crate::svd::{EnumeratedValue, ValidateLevel};
+use crate::svd::{EnumeratedValue, EnumeratedValues, Field, ValidateLevel};
+use roxmltree::Document;
+use svd_parser::{Parse, SVDErrorAt};
 
 #[test]
 fn decode_encode() {
@@ -62,3 +64,207 @@ fn decode_encode() {
 
     run_test::<EnumeratedValue>(&tests[..], Some(parse_config), Some(encode_config));
 }
+
+fn parse_value(value: &str, config: &svd_parser::Config) -> Result<EnumeratedValue, SVDErrorAt> {
+    let xml = format!(
+        "<enumeratedValue><name>V</name><value>{}</value></enumeratedValue>",
+        value
+    );
+    let doc = Document::parse(&xml).unwrap();
+    EnumeratedValue::parse(&doc.root_element(), config)
+}
+
+#[test]
+fn value_forms() {
+    let config = svd_parser::Config::default();
+    for (text, expected) in [
+        ("5", 5),
+        ("+5", 5),
+        ("0x1F", 0x1f),
+        ("0X1f", 0x1f),
+        ("0b101", 0b101),
+        ("#101", 0b101),
+        (" 0x01 ", 1),
+        ("2k", 2048),
+        ("1M", 1 << 20),
+        ("0x100000000", 0x1_0000_0000),
+        ("0xFFFFFFFFFFFFFFFF", u64::MAX),
+    ] {
+        let ev = parse_value(text, &config).unwrap();
+        assert_eq!(ev.value, Some(expected), "{}", text);
+        assert_eq!(ev.dont_care, None, "{}", text);
+    }
+    assert!(parse_value("0x", &config).is_err());
+}
+
+#[test]
+fn dont_care() {
+    let config = svd_parser::Config::default();
+    for text in ["#1x0", "0b1X0"] {
+        let ev = parse_value(text, &config).unwrap();
+        assert_eq!(ev.value, Some(0b100));
+        assert_eq!(ev.dont_care, Some(0b010));
+    }
+
+    let tests = vec![(
+        EnumeratedValue::builder()
+            .name("ANY".to_string())
+            .value(Some(0b1001))
+            .dont_care(Some(0b0110))
+            .build(ValidateLevel::Strict)
+            .unwrap(),
+        "<enumeratedValue><name>ANY</name><value>0b1xx1</value></enumeratedValue>",
+        "<enumeratedValue><name>ANY</name><value>#1xx1</value></enumeratedValue>",
+    )];
+    run_test::<EnumeratedValue>(&tests[..], None, None);
+}
+
+#[test]
+fn minus_one() {
+    let err = parse_value("-1", &svd_parser::Config::default()).unwrap_err();
+    assert!(err.to_string().contains("Negative"), "{}", err);
+
+    let config = svd_parser::Config::default().minus_one_as_all_ones(true);
+    assert!(parse_value("-2", &config).is_err());
+
+    let xml = "
+        <field>
+            <name>MODE</name>
+            <bitRange>[3:0]</bitRange>
+            <enumeratedValues>
+                <enumeratedValue><name>ALL</name><value>-1</value></enumeratedValue>
+            </enumeratedValues>
+        </field>";
+    let doc = Document::parse(xml).unwrap();
+    assert!(Field::parse(&doc.root_element(), &svd_parser::Config::default()).is_err());
+    let field = Field::parse(&doc.root_element(), &config).unwrap();
+    assert_eq!(field.enumerated_values[0].values[0].value, Some(0xf));
+    // only the `-1` form is read as all ones of the field width
+    let xml = xml.replace("-1", "0xFFFFFFFFFFFFFFFF");
+    let doc = Document::parse(&xml).unwrap();
+    let config = config.validate_level(ValidateLevel::Disabled);
+    let field = Field::parse(&doc.root_element(), &config).unwrap();
+    assert_eq!(field.enumerated_values[0].values[0].value, Some(u64::MAX));
+}
+
+fn ev(name: &str, value: Option<u64>, dont_care: Option<u64>) -> EnumeratedValue {
+    EnumeratedValue::builder()
+        .name(name.to_string())
+        .value(value)
+        .dont_care(dont_care)
+        .is_default(value.map_or(Some(true), |_| None))
+        .build(ValidateLevel::Strict)
+        .unwrap()
+}
+
+#[test]
+fn values_lookup() {
+    let evs = EnumeratedValues::builder()
+        .values(vec![
+            ev("OTHER", None, None),
+            ev("HIGH", Some(0b100), Some(0b011)),
+            ev("ONE", Some(1), None),
+            ev("ZERO", Some(0), None),
+        ])
+        .build(ValidateLevel::Strict)
+        .unwrap();
+
+    let names: Vec<_> = evs
+        .values_sorted()
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect();
+    assert_eq!(names, ["ZERO", "ONE", "HIGH", "OTHER"]);
+
+    let index = evs.index();
+    for (value, name) in [
+        (0, Some("ZERO")),
+        (1, Some("ONE")),
+        (2, None),
+        (6, Some("HIGH")),
+    ] {
+        assert_eq!(evs.get_by_value(value).map(|e| e.name.as_str()), name);
+        assert_eq!(index.get(value).map(|e| e.name.as_str()), name);
+    }
+    assert_eq!(index.get_or_default(2).unwrap().name, "OTHER");
+}
+
+#[test]
+fn values_lookup_order() {
+    // overlapping values are matched in document order by both lookups
+    let evs = EnumeratedValues::builder()
+        .values(vec![
+            ev("ANY", Some(0b100), Some(0b011)),
+            ev("FIVE", Some(0b101), None),
+            ev("SIX", Some(0b110), None),
+        ])
+        .build(ValidateLevel::Disabled)
+        .unwrap();
+    let index = evs.index();
+    for value in [0b100, 0b101, 0b110] {
+        assert_eq!(evs.get_by_value(value).unwrap().name, "ANY");
+        assert_eq!(index.get(value).unwrap().name, "ANY");
+    }
+
+    let evs = EnumeratedValues::builder()
+        .values(vec![
+            ev("FIVE", Some(0b101), None),
+            ev("ANY", Some(0b100), Some(0b011)),
+        ])
+        .build(ValidateLevel::Disabled)
+        .unwrap();
+    let index = evs.index();
+    for (value, name) in [(0b101, "FIVE"), (0b110, "ANY")] {
+        assert_eq!(evs.get_by_value(value).unwrap().name, name);
+        assert_eq!(index.get(value).unwrap().name, name);
+    }
+}
+
+#[test]
+fn values_insert() {
+    let mut evs = EnumeratedValues::builder()
+        .values(vec![ev("HIGH", Some(0b100), Some(0b011))])
+        .build(ValidateLevel::Strict)
+        .unwrap();
+
+    assert!(evs.insert(ev("HIGH", Some(0), None)).is_err());
+    // 0b101 is matched by HIGH
+    assert!(evs.insert(ev("FIVE", Some(0b101), None)).is_err());
+    assert!(evs.insert(ev("ANY_ODD", Some(1), Some(0b110))).is_err());
+    evs.insert(ev("ONE", Some(1), None)).unwrap();
+    evs.insert(ev("OTHER", None, None)).unwrap();
+    assert!(evs.insert(ev("OTHER2", None, None)).is_err());
+
+    assert_eq!(evs.remove("HIGH").unwrap().name, "HIGH");
+    assert!(evs.remove("HIGH").is_none());
+    evs.insert(ev("FIVE", Some(0b101), None)).unwrap();
+    assert_eq!(evs.index().get(5).unwrap().name, "FIVE");
+}
+
+#[test]
+fn values_lookup_cache() {
+    let mut evs = EnumeratedValues::builder()
+        .values(vec![ev("ONE", Some(1), None), ev("TWO", Some(2), None)])
+        .build(ValidateLevel::Strict)
+        .unwrap();
+    assert_eq!(evs.get_by_value(1).unwrap().name, "ONE");
+
+    // cache is dropped by mutation
+    evs.get_mut_value("ONE").unwrap().value = Some(3);
+    assert!(evs.get_by_value(1).is_none());
+    assert_eq!(evs.get_by_value(3).unwrap().name, "ONE");
+    evs.values_mut().swap(0, 1);
+    assert_eq!(evs.get_by_value(3).unwrap().name, "ONE");
+    assert_eq!(evs.get_by_value(2).unwrap().name, "TWO");
+
+    // values pushed directly are found too
+    evs.values.push(ev("FOUR", Some(4), None));
+    assert_eq!(evs.get_by_value(4).unwrap().name, "FOUR");
+    evs.values.clear();
+    assert!(evs.get_by_value(2).is_none());
+
+    // cache doesn't take part in comparison
+    let cached = evs.clone();
+    cached.get_by_value(0);
+    assert_eq!(cached, evs);
+}
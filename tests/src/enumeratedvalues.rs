@@ -1,90 +1,175 @@
-use crate::encode::Encode;
-use crate::parse::Parse;
-use crate::svd::{EnumeratedValue, EnumeratedValues};
-use anyhow::Result;
-use xmltree::Element;
+use super::run_test;
+use crate::svd::{EnumeratedValue, EnumeratedValues, ValidateLevel};
+use roxmltree::Document;
+use svd_parser::Parse;
+
+fn ev(name: &str, value: Option<u64>) -> EnumeratedValue {
+    EnumeratedValue::builder()
+        .name(name.to_string())
+        .value(value)
+        .is_default(value.map_or(Some(true), |_| None))
+        .build(ValidateLevel::Strict)
+        .unwrap()
+}
 
 #[test]
 fn decode_encode() {
-    let example = String::from(
-        "
-        <enumeratedValues derivedFrom=\"fake_derivation\">
-            <enumeratedValue>
-                <name>WS0</name>
-                <description>Zero wait-states inserted in fetch or read transfers</description>
-                <isDefault>true</isDefault>
-            </enumeratedValue>
-            <enumeratedValue>
-                <name>WS1</name>
-                <description>One wait-state inserted for each fetch or read transfer. See Flash Wait-States table for details</description>
-                <value>1</value>
-            </enumeratedValue>
-        </enumeratedValues>
-    ",
-    );
-
-    let expected = EnumeratedValues::builder()
-        .derived_from(Some("fake_derivation".to_string()))
-        .values(vec![
-            EnumeratedValue::builder()
-                .name("WS0".to_string())
-                .description(Some(
-                    "Zero wait-states inserted in fetch or read transfers".to_string()
-                ))
-                .is_default(Some(true))
-                .build()
+    let tests = vec![
+        (
+            EnumeratedValues::builder()
+                .derived_from(Some("fake_derivation".to_string()))
+                .values(vec![
+                    EnumeratedValue::builder()
+                        .name("WS0".to_string())
+                        .description(Some(
+                            "Zero wait-states inserted in fetch or read transfers".to_string()
+                        ))
+                        .is_default(Some(true))
+                        .build(ValidateLevel::Strict)
+                        .unwrap(),
+                    EnumeratedValue::builder()
+                        .name("WS1".to_string())
+                        .description(Some(
+                            "One wait-state inserted for each fetch or read transfer. See Flash Wait-States table for details".to_string()
+                        ))
+                        .value(Some(1))
+                        .build(ValidateLevel::Strict)
+                        .unwrap(),
+                ])
+                .build(ValidateLevel::Strict)
                 .unwrap(),
-            EnumeratedValue::builder()
-                .name("WS1".to_string())
-                .description(Some(
-                    "One wait-state inserted for each fetch or read transfer. See Flash Wait-States table for details".to_string()
-                ))
-                .value(Some(1))
-                .build()
+            "
+            <enumeratedValues derivedFrom=\"fake_derivation\">
+                <enumeratedValue>
+                    <name>WS0</name>
+                    <description>Zero wait-states inserted in fetch or read transfers</description>
+                    <isDefault>true</isDefault>
+                </enumeratedValue>
+                <enumeratedValue>
+                    <name>WS1</name>
+                    <description>One wait-state inserted for each fetch or read transfer. See Flash Wait-States table for details</description>
+                    <value>1</value>
+                </enumeratedValue>
+            </enumeratedValues>",
+            "
+            <enumeratedValues derivedFrom=\"fake_derivation\">
+                <enumeratedValue>
+                    <name>WS0</name>
+                    <description>Zero wait-states inserted in fetch or read transfers</description>
+                    <isDefault>true</isDefault>
+                </enumeratedValue>
+                <enumeratedValue>
+                    <name>WS1</name>
+                    <description>One wait-state inserted for each fetch or read transfer. See Flash Wait-States table for details</description>
+                    <value>1</value>
+                </enumeratedValue>
+            </enumeratedValues>",
+        ),
+        (
+            // three values and a catch-all for the rest
+            EnumeratedValues::builder()
+                .values(vec![
+                    ev("Low", Some(0)),
+                    ev("Medium", Some(1)),
+                    ev("High", Some(2)),
+                    ev("Reserved", None),
+                ])
+                .build(ValidateLevel::Strict)
                 .unwrap(),
-        ])
-        .build()
-        .unwrap();
-
-    // TODO: move to test! macro
-    let tree1 = Element::parse(example.as_bytes()).unwrap();
+            "
+            <enumeratedValues>
+                <enumeratedValue><name>Low</name><value>0</value></enumeratedValue>
+                <enumeratedValue><name>Medium</name><value>1</value></enumeratedValue>
+                <enumeratedValue><name>High</name><value>2</value></enumeratedValue>
+                <enumeratedValue><name>Reserved</name><isDefault>true</isDefault></enumeratedValue>
+            </enumeratedValues>",
+            "
+            <enumeratedValues>
+                <enumeratedValue><name>Low</name><value>0</value></enumeratedValue>
+                <enumeratedValue><name>Medium</name><value>1</value></enumeratedValue>
+                <enumeratedValue><name>High</name><value>2</value></enumeratedValue>
+                <enumeratedValue><name>Reserved</name><isDefault>true</isDefault></enumeratedValue>
+            </enumeratedValues>",
+        ),
+    ];
 
-    let parsed = EnumeratedValues::parse(&tree1).unwrap();
-    assert_eq!(parsed, expected, "Parsing tree failed");
-
-    let tree2 = parsed.encode().unwrap();
-    assert_eq!(tree1, tree2, "Encoding value failed");
+    run_test::<EnumeratedValues>(&tests[..], None, None);
 }
 
 #[test]
 fn valid_children() {
-    fn parse(contents: String) -> Result<EnumeratedValues> {
-        let example = String::from("<enumeratedValues>") + &contents + "</enumeratedValues>";
-        let tree = Element::parse(example.as_bytes()).unwrap();
-        EnumeratedValues::parse(&tree)
+    fn parse(contents: &str) -> Result<EnumeratedValues, svd_parser::SVDErrorAt> {
+        let example = format!("<enumeratedValues>{}</enumeratedValues>", contents);
+        let tree = Document::parse(&example).unwrap();
+        EnumeratedValues::parse(&tree.root_element(), &Default::default())
     }
 
     // `enumeratedValue` occurrence: 1..*
-    parse("".into()).expect_err("must contain at least one <enumeratedValue>");
+    parse("").expect_err("must contain at least one <enumeratedValue>");
 
-    let value = String::from(
-        "
+    let value = "
         <enumeratedValue>
             <name>WS0</name>
             <description>Zero wait-states inserted in fetch or read transfers</description>
             <value>0</value>
-        </enumeratedValue>",
-    );
+        </enumeratedValue>";
 
     // Valid tags
-    parse(value.clone() + "<name>foo</name>").expect("<name> is valid");
-    parse(value.clone() + "<headerEnumName>foo</headerEnumName>")
+    parse(&format!("{}<name>foo</name>", value)).expect("<name> is valid");
+    parse(&format!("{}<headerEnumName>foo</headerEnumName>", value))
         .expect("<headerEnumName> is valid");
-    parse(value.clone() + "<usage>read</usage>").expect("<usage> is valid");
+    parse(&format!("{}<usage>read</usage>", value)).expect("<usage> is valid");
 
     // Invalid tags
-    parse(value.clone() + "<enumerateValue></enumerateValue>")
+    parse(&format!("{}<enumerateValue></enumerateValue>", value))
         .expect_err("<enumerateValue> in invalid here");
-    parse(value.clone() + "<enumeratedValues></enumeratedValues>")
+    parse(&format!("{}<enumeratedValues></enumeratedValues>", value))
         .expect_err("<enumeratedValues> in invalid here");
+
+    // An entry needs a value or isDefault
+    parse("<enumeratedValue><name>WS0</name></enumeratedValue>")
+        .expect_err("<enumeratedValue> without <value> or <isDefault>");
+}
+
+#[test]
+fn names() {
+    let values = "<enumeratedValue><name>Off</name><value>0</value></enumeratedValue>";
+    let config = svd_parser::Config::default().validate_level(ValidateLevel::Strict);
+    let parse = |children: &str| {
+        let xml = format!(
+            "<enumeratedValues>{}{}</enumeratedValues>",
+            children, values
+        );
+        let tree = Document::parse(&xml).unwrap();
+        EnumeratedValues::parse(&tree.root_element(), &config).unwrap()
+    };
+
+    let evs = parse("<headerEnumName>MODE_Enum</headerEnumName>");
+    assert_eq!(evs.name, None);
+    assert_eq!(evs.header_enum_name.as_deref(), Some("MODE_Enum"));
+
+    let evs = parse("<name>Mode</name>");
+    assert_eq!(evs.name.as_deref(), Some("Mode"));
+    assert_eq!(evs.header_enum_name, None);
+
+    let evs = parse("");
+    assert_eq!((evs.name, evs.header_enum_name), (None, None));
+
+    let tests = vec![(
+        EnumeratedValues::builder()
+            .name(Some("Mode".to_string()))
+            .header_enum_name(Some("MODE_Enum".to_string()))
+            .derived_from(Some("CR.MODE.Mode".to_string()))
+            .build(ValidateLevel::Strict)
+            .unwrap(),
+        "<enumeratedValues derivedFrom=\"CR.MODE.Mode\">
+            <name>Mode</name>
+            <headerEnumName>MODE_Enum</headerEnumName>
+        </enumeratedValues>",
+        "<enumeratedValues derivedFrom=\"CR.MODE.Mode\">
+            <name>Mode</name>
+            <headerEnumName>MODE_Enum</headerEnumName>
+        </enumeratedValues>",
+    )];
+    run_test::<EnumeratedValues>(&tests[..], Some(config), None);
 }
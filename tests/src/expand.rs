@@ -0,0 +1,925 @@
+use crate::svd::{Access, Device, Protection, RegisterInfo};
+use svd_encoder::encode;
+use svd_parser as parser;
+
+const SVD: &str = "
+<device>
+    <name>TEST</name>
+    <addressUnitBits>8</addressUnitBits>
+    <width>32</width>
+    <size>32</size>
+    <access>read-write</access>
+    <peripherals>
+        <peripheral>
+            <name>TIMER</name>
+            <baseAddress>0x40000000</baseAddress>
+            <resetValue>0</resetValue>
+            <registers>
+                <register>
+                    <name>CNT</name>
+                    <addressOffset>0x0</addressOffset>
+                    <size>16</size>
+                </register>
+                <cluster>
+                    <name>CH</name>
+                    <addressOffset>0x4</addressOffset>
+                    <size>8</size>
+                    <register>
+                        <name>CMP</name>
+                        <addressOffset>0x0</addressOffset>
+                        <size>16</size>
+                        <resetValue>1</resetValue>
+                    </register>
+                </cluster>
+            </registers>
+        </peripheral>
+        <peripheral>
+            <name>UART</name>
+            <baseAddress>0x40001000</baseAddress>
+            <size>16</size>
+            <registers>
+                <register>
+                    <name>DATA</name>
+                    <addressOffset>0x0</addressOffset>
+                </register>
+            </registers>
+        </peripheral>
+        <peripheral derivedFrom=\"UART\">
+            <name>UART1</name>
+            <baseAddress>0x40002000</baseAddress>
+        </peripheral>
+    </peripherals>
+</device>";
+
+fn resolve(device: &Device) -> Device {
+    let mut device = parser::expand(device).unwrap();
+    parser::expand_properties(&mut device);
+    device
+}
+
+/// Registers with their effective properties
+fn registers(device: &Device) -> Vec<&RegisterInfo> {
+    device
+        .peripherals
+        .iter()
+        .flat_map(|p| p.all_registers().map(|r| &**r))
+        .collect()
+}
+
+#[test]
+fn compress_properties() {
+    let device = parser::parse(SVD).unwrap();
+    let resolved = resolve(&device);
+
+    let mut compressed = resolved.clone();
+    parser::compress_properties(&mut compressed);
+    assert_eq!(compressed.default_register_properties.size, Some(16));
+    assert_eq!(
+        compressed.default_register_properties.access,
+        Some(Access::ReadWrite)
+    );
+    // reset values differ
+    assert_eq!(compressed.default_register_properties.reset_value, None);
+    for p in &compressed.peripherals {
+        assert_eq!(p.default_register_properties.size, None);
+        for r in p.all_registers() {
+            assert_eq!(r.properties.size, None);
+            assert_eq!(r.properties.access, None);
+        }
+    }
+    let xml = encode(&compressed).unwrap();
+    assert_eq!(
+        registers(&resolve(&parser::parse(&xml).unwrap())),
+        registers(&resolved)
+    );
+
+    // without expanding arrays and clusters
+    let mut compressed = device;
+    parser::compress_properties(&mut compressed);
+    let ch = compressed.peripherals[0].get_cluster("CH").unwrap();
+    assert_eq!(ch.default_register_properties.size, None);
+    assert_eq!(registers(&resolve(&compressed)), registers(&resolved));
+}
+
+#[test]
+fn effective_registers() {
+    let peripheral = |name: &str, derived_from: Option<&str>, registers: &[&str]| {
+        let registers: String = registers
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                // `NAME:GROUP` is a register of an alternate group
+                let (r, group) = match r.split_once(':') {
+                    Some((r, group)) => (r, format!("<alternateGroup>{}</alternateGroup>", group)),
+                    None => (*r, String::new()),
+                };
+                format!(
+                    "<register><name>{}</name>{}<addressOffset>{:#x}</addressOffset><size>32</size></register>",
+                    r,
+                    group,
+                    i * 4
+                )
+            })
+            .collect();
+        format!(
+            "<peripheral{}><name>{}</name><baseAddress>0x40000000</baseAddress>{}</peripheral>",
+            derived_from
+                .map(|d| format!(" derivedFrom=\"{}\"", d))
+                .unwrap_or_default(),
+            name,
+            if registers.is_empty() {
+                String::new()
+            } else {
+                format!("<registers>{}</registers>", registers)
+            }
+        )
+    };
+    let fixtures = [
+        // no derivation
+        vec![peripheral("A", None, &["CTRL", "DATA"])],
+        // one level, inherited and overridden
+        vec![
+            peripheral("A", None, &["CTRL", "DATA"]),
+            peripheral("B", Some("A"), &[]),
+            peripheral("C", Some("A"), &["DATA", "STATUS"]),
+        ],
+        // two levels, with override at each level
+        vec![
+            peripheral("A", None, &["CTRL", "DATA"]),
+            peripheral("B", Some("A"), &["DATA"]),
+            peripheral("C", Some("B"), &[]),
+            peripheral("D", Some("B"), &["CTRL"]),
+            peripheral("E", Some("C"), &[]),
+        ],
+        // alternate groups of one register are different registers
+        vec![
+            peripheral("A", None, &["CCMR:Input", "CCMR:Output"]),
+            peripheral("B", Some("A"), &["CCMR:Output"]),
+        ],
+    ];
+    for peripherals in fixtures {
+        let xml = SVD.replace(
+            &SVD[SVD.find("<peripherals>").unwrap()..SVD.find("</peripherals>").unwrap()],
+            &format!("<peripherals>{}", peripherals.concat()),
+        );
+        let device = parser::parse(&xml).unwrap();
+        let resolved = parser::expand(&device).unwrap();
+        for p in &device.peripherals {
+            let expected: Vec<_> = resolved
+                .get_peripheral(&p.name)
+                .unwrap()
+                .registers()
+                .collect();
+            let effective: Vec<_> = p.effective_registers(&device).unwrap().collect();
+            assert_eq!(effective, expected, "{}", p.name);
+            for r in &expected {
+                assert_eq!(p.effective_register(&device, &r.effective_name()), Some(*r));
+            }
+            assert_eq!(p.effective_register(&device, "MISSING"), None);
+        }
+    }
+}
+
+#[test]
+fn provenance() {
+    use crate::svd::Fix;
+
+    let xml = SVD.replace(
+        &SVD[SVD.find("<peripherals>").unwrap()..SVD.find("</peripherals>").unwrap()],
+        "<peripherals>
+        <peripheral>
+            <dim>2</dim>
+            <dimIncrement>0x1000</dimIncrement>
+            <name>UART[%s]</name>
+            <baseAddress>0x40000000</baseAddress>
+            <size>8</size>
+            <resetValue>0</resetValue>
+            <registers>
+                <register>
+                    <name>CTRL</name>
+                    <addressOffset>0x0</addressOffset>
+                    <fields>
+                        <field><name>DIV</name><bitOffset>0</bitOffset><bitWidth>4</bitWidth></field>
+                        <field><name>EN</name><bitOffset>4</bitOffset><bitWidth>1</bitWidth></field>
+                    </fields>
+                </register>
+                <register derivedFrom=\"CTRL\">
+                    <name>CTRL2</name>
+                    <addressOffset>0x4</addressOffset>
+                </register>
+                <cluster>
+                    <name>CH</name>
+                    <addressOffset>0x8</addressOffset>
+                    <register>
+                        <name>CMP</name>
+                        <addressOffset>0x0</addressOffset>
+                    </register>
+                </cluster>
+            </registers>
+        </peripheral>",
+    );
+    let pipeline = |enable: bool| {
+        let mut device = parser::parse(&xml).unwrap();
+        if enable {
+            device.enable_provenance();
+        }
+        for (path, element, value) in [
+            ("UART[%s].CTRL.DIV", "bitWidth", "12"),
+            ("UART[%s].CH.CMP", "resetValue", "0x5"),
+        ] {
+            device
+                .apply_fix(&Fix::SetText {
+                    path: path.into(),
+                    element: element.into(),
+                    new_value: value.into(),
+                })
+                .unwrap();
+        }
+        device.widen_registers_to_fields();
+        parser::expand_properties(&mut device);
+        parser::expand(&device).unwrap()
+    };
+
+    let device = pipeline(true);
+    let passes =
+        |path: &str| -> Vec<&str> { device.provenance(path).iter().map(|e| e.pass).collect() };
+    for uart in ["UART0", "UART1"] {
+        let path = |name: &str| format!("{}.{}", uart, name);
+        assert_eq!(passes(&path("CTRL.DIV")), ["apply_fix"]);
+        assert_eq!(
+            passes(&path("CTRL")),
+            ["widen_registers_to_fields", "expand_properties"]
+        );
+        assert_eq!(passes(&path("CTRL2")), ["expand"]);
+        assert_eq!(passes(&path("CH_CMP")), ["apply_fix", "expand_properties"]);
+        assert_eq!(passes(&path("CTRL.EN")), Vec::<&str>::new());
+    }
+    let div = device.provenance("UART1.CTRL.DIV");
+    assert_eq!(div[0].detail, "set `bitWidth` to `12`");
+    let ctrl = device.provenance("UART0.CTRL");
+    assert!(div[0].timestamp_counter < ctrl[0].timestamp_counter);
+    assert_eq!(ctrl[1].detail, "inherited access, resetValue");
+    assert!(device.provenance("UART[%s].CTRL").is_empty());
+    assert!(device
+        .provenance_report()
+        .contains("UART0.CTRL2: #6 expand: derived from `CTRL`"));
+
+    assert_eq!(pipeline(false).provenance_report(), "");
+    // history doesn't make devices differ
+    assert_eq!(pipeline(false), device);
+}
+
+#[test]
+fn disable_condition() {
+    let device = parser::parse(
+        "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>UART0</name>
+                    <disableCondition>RCC->APB1ENR.UART0EN == 0</disableCondition>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <register><name>DATA</name><addressOffset>0x0</addressOffset></register>
+                    </registers>
+                </peripheral>
+                <peripheral derivedFrom=\"UART0\">
+                    <name>UART1</name>
+                    <baseAddress>0x40001000</baseAddress>
+                </peripheral>
+                <peripheral derivedFrom=\"UART0\">
+                    <name>UART2</name>
+                    <disableCondition>RCC->APB1ENR.UART2EN == 0</disableCondition>
+                    <baseAddress>0x40002000</baseAddress>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    assert_eq!(device.peripherals[1].disable_condition, None);
+    assert_eq!(parser::parse(&encode(&device).unwrap()).unwrap(), device);
+
+    let device = resolve(&device);
+    let conditions: Vec<_> = device
+        .peripherals
+        .iter()
+        .map(|p| p.disable_condition.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        conditions,
+        [
+            "RCC->APB1ENR.UART0EN == 0",
+            "RCC->APB1ENR.UART0EN == 0",
+            "RCC->APB1ENR.UART2EN == 0"
+        ]
+    );
+}
+
+#[test]
+fn derived_cluster() {
+    let device = parser::parse(
+        "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>DMA</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <cluster>
+                            <name>CH0</name>
+                            <description>Channel</description>
+                            <addressOffset>0x0</addressOffset>
+                            <register><name>CTRL</name><addressOffset>0x0</addressOffset></register>
+                        </cluster>
+                        <cluster derivedFrom=\"CH0\">
+                            <name>CH1</name>
+                            <addressOffset>0x10</addressOffset>
+                        </cluster>
+                        <cluster derivedFrom=\"DMA.CH0\">
+                            <name>CH2</name>
+                            <alternateCluster>CH1</alternateCluster>
+                            <addressOffset>0x10</addressOffset>
+                        </cluster>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    let dma = &device.peripherals[0];
+    let ch2 = dma.get_cluster("CH2").unwrap();
+    assert_eq!(ch2.derived_from.as_deref(), Some("DMA.CH0"));
+    assert_eq!(ch2.alternate_cluster.as_deref(), Some("CH1"));
+    assert_eq!(parser::parse(&encode(&device).unwrap()).unwrap(), device);
+
+    // clusters are flattened, derived ones get registers of `CH0`
+    let device = resolve(&device);
+    let registers: Vec<_> = registers(&device)
+        .into_iter()
+        .map(|r| (r.name.as_str(), r.address_offset))
+        .collect();
+    assert_eq!(
+        registers,
+        [("CH0_CTRL", 0x0), ("CH1_CTRL", 0x10), ("CH2_CTRL", 0x10)]
+    );
+}
+
+#[test]
+fn derived_field() {
+    let device = parser::parse(
+        "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>GPIO</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <register>
+                            <name>MODER</name>
+                            <addressOffset>0x0</addressOffset>
+                            <fields>
+                                <field>
+                                    <name>MODE0</name>
+                                    <description>Mode of pin</description>
+                                    <bitRange>[1:0]</bitRange>
+                                    <access>read-write</access>
+                                    <writeConstraint><range><minimum>0</minimum><maximum>2</maximum></range></writeConstraint>
+                                    <enumeratedValues>
+                                        <enumeratedValue><name>Input</name><value>0</value></enumeratedValue>
+                                        <enumeratedValue><name>Output</name><value>1</value></enumeratedValue>
+                                    </enumeratedValues>
+                                </field>
+                            </fields>
+                        </register>
+                        <register>
+                            <name>ALTMODER</name>
+                            <addressOffset>0x4</addressOffset>
+                            <fields>
+                                <field derivedFrom=\"GPIO.MODER.MODE0\">
+                                    <name>MODE1</name>
+                                    <bitRange>[3:2]</bitRange>
+                                </field>
+                            </fields>
+                        </register>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    let device = resolve(&device);
+    let gpio = &device.peripherals[0];
+    let base = gpio
+        .get_register("MODER")
+        .unwrap()
+        .get_field("MODE0")
+        .unwrap();
+    let derived = gpio
+        .get_register("ALTMODER")
+        .unwrap()
+        .get_field("MODE1")
+        .unwrap();
+    let mut expected = base.clone();
+    expected.name = "MODE1".into();
+    expected.bit_range.offset = 2;
+    assert_eq!(derived, &expected);
+}
+
+#[test]
+fn derived_enumerated_values() {
+    let svd = |reference: &str| {
+        format!(
+            "
+            <device>
+                <name>TEST</name>
+                <peripherals>
+                    <peripheral>
+                        <name>TIMER0</name>
+                        <baseAddress>0x40000000</baseAddress>
+                        <registers>
+                            <register>
+                                <name>CR</name>
+                                <addressOffset>0x0</addressOffset>
+                                <fields>
+                                    <field>
+                                        <name>MODE</name>
+                                        <bitRange>[1:0]</bitRange>
+                                        <enumeratedValues>
+                                            <name>MODE_ENUM</name>
+                                            <enumeratedValue><name>Stop</name><value>0</value></enumeratedValue>
+                                            <enumeratedValue><name>Run</name><value>1</value></enumeratedValue>
+                                        </enumeratedValues>
+                                    </field>
+                                    <field>
+                                        <name>NEXT</name>
+                                        <bitRange>[3:2]</bitRange>
+                                        <enumeratedValues derivedFrom=\"{}\"></enumeratedValues>
+                                    </field>
+                                </fields>
+                            </register>
+                        </registers>
+                    </peripheral>
+                </peripherals>
+            </device>",
+            reference
+        )
+    };
+    for reference in [
+        "MODE_ENUM",
+        "MODE.MODE_ENUM",
+        "CR.MODE.MODE_ENUM",
+        "TIMER0.CR.MODE.MODE_ENUM",
+    ] {
+        let device = resolve(&parser::parse(&svd(reference)).unwrap());
+        let cr = device.peripherals[0].get_register("CR").unwrap();
+        let values = &cr.get_field("NEXT").unwrap().enumerated_values[0].values;
+        let names: Vec<_> = values.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["Stop", "Run"], "{}", reference);
+    }
+
+    let device = parser::parse(&svd("TIMER0.CR.MOD.MODE_ENUM")).unwrap();
+    let error = format!("{:#}", parser::expand(&device).unwrap_err());
+    assert!(
+        error.contains(
+            "enumeratedValues TIMER0.CR.MOD.MODE_ENUM of field TIMER0.CR.NEXT not found, \
+             looked for TIMER0.CR.MOD.MODE_ENUM"
+        ),
+        "{}",
+        error
+    );
+    let device = parser::parse(&svd("OTHER_ENUM")).unwrap();
+    let error = format!("{:#}", parser::expand(&device).unwrap_err());
+    assert!(
+        error.contains("looked for fields of register TIMER0.CR"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn protection() {
+    // Musca-B1 like secure and non-secure views of one block
+    let device = parser::parse(
+        "
+        <device>
+            <name>MUSCA</name>
+            <addressUnitBits>8</addressUnitBits>
+            <width>32</width>
+            <size>32</size>
+            <protection>n</protection>
+            <peripherals>
+                <peripheral>
+                    <name>SYSCTRL</name>
+                    <baseAddress>0x50080000</baseAddress>
+                    <registers>
+                        <register>
+                            <name>SECDBGSTAT</name>
+                            <addressOffset>0x0</addressOffset>
+                            <protection>s</protection>
+                        </register>
+                        <register>
+                            <name>RESET_SYNDROME</name>
+                            <addressOffset>0x100</addressOffset>
+                        </register>
+                        <cluster>
+                            <name>PWR</name>
+                            <addressOffset>0x200</addressOffset>
+                            <protection>p</protection>
+                            <register>
+                                <name>CTRL</name>
+                                <addressOffset>0x0</addressOffset>
+                            </register>
+                        </cluster>
+                    </registers>
+                </peripheral>
+                <peripheral>
+                    <name>SCC</name>
+                    <baseAddress>0x50081000</baseAddress>
+                    <protection>s</protection>
+                    <registers>
+                        <register>
+                            <name>CLK_CTRL_SEL</name>
+                            <addressOffset>0x0</addressOffset>
+                        </register>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    assert_eq!(
+        device.default_register_properties.protection,
+        Some(Protection::NonSecure)
+    );
+    let scc = device.get_peripheral("SCC").unwrap();
+    assert_eq!(
+        scc.default_register_properties.protection,
+        Some(Protection::Secure)
+    );
+    assert_eq!(
+        scc.effective_register_properties(&device).protection,
+        Some(Protection::Secure)
+    );
+
+    let device = resolve(&device);
+    let protection: Vec<_> = registers(&device)
+        .iter()
+        .map(|r| (r.name.as_str(), r.properties.protection))
+        .collect();
+    assert_eq!(
+        protection,
+        [
+            ("SECDBGSTAT", Some(Protection::Secure)),
+            ("RESET_SYNDROME", Some(Protection::NonSecure)),
+            ("PWR_CTRL", Some(Protection::Privileged)),
+            ("CLK_CTRL_SEL", Some(Protection::Secure)),
+        ]
+    );
+}
+
+#[test]
+fn resolve_derived_peripherals() {
+    let svd = |peripherals: &str| {
+        format!(
+            "
+            <device>
+                <name>TEST</name>
+                <peripherals>{}</peripherals>
+            </device>",
+            peripherals
+        )
+    };
+    let peripheral = |name: &str, derived_from: Option<&str>, body: &str| {
+        let attr = derived_from
+            .map(|base| format!(" derivedFrom=\"{}\"", base))
+            .unwrap_or_default();
+        format!(
+            "<peripheral{}><name>{}</name><baseAddress>0</baseAddress>{}</peripheral>",
+            attr, name, body
+        )
+    };
+    let uart = "
+        <description>UART</description>
+        <groupName>UART</groupName>
+        <registers>
+            <register><name>DATA</name><addressOffset>0x0</addressOffset></register>
+        </registers>";
+
+    // forward reference and a chain: UART2 -> UART1 -> UART0
+    let mut device = parser::parse(&svd(&[
+        peripheral("UART2", Some("UART1"), ""),
+        peripheral(
+            "UART1",
+            Some("UART0"),
+            "<description>Second UART</description>",
+        ),
+        peripheral("UART0", None, uart),
+    ]
+    .concat()))
+    .unwrap();
+    device.resolve_derived_peripherals().unwrap();
+    let uart0 = &device.peripherals[2];
+    // UART2 gets the description of UART1, which overrides the one of UART0
+    for p in &device.peripherals[..2] {
+        assert_eq!(p.derived_from, None);
+        assert_eq!(p.description.as_deref(), Some("Second UART"));
+        assert_eq!(p.group_name, uart0.group_name);
+        assert_eq!(p.registers, uart0.registers);
+    }
+
+    let mut device = parser::parse(&svd(&peripheral("UART1", Some("UART0"), ""))).unwrap();
+    assert_eq!(
+        device
+            .resolve_derived_peripherals()
+            .unwrap_err()
+            .to_string(),
+        "`Peripheral error: Peripheral `UART1` is derived from missing peripheral `UART0`"
+    );
+
+    let mut device = parser::parse(&svd(&[
+        peripheral("A", Some("B"), ""),
+        peripheral("B", Some("A"), ""),
+    ]
+    .concat()))
+    .unwrap();
+    assert_eq!(
+        device
+            .resolve_derived_peripherals()
+            .unwrap_err()
+            .to_string(),
+        "`Peripheral error: Peripheral derivation cycle: A -> B -> A"
+    );
+}
+
+#[test]
+fn resolve_derived_registers() {
+    let mut device = parser::parse(
+        "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>USART1</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <register derivedFrom=\"CR2\">
+                            <name>CR3</name>
+                            <addressOffset>0x8</addressOffset>
+                        </register>
+                        <register>
+                            <name>CR1</name>
+                            <description>Control register</description>
+                            <addressOffset>0x0</addressOffset>
+                            <size>16</size>
+                            <access>read-write</access>
+                            <resetValue>0x1</resetValue>
+                            <fields>
+                                <field><name>UE</name><bitRange>[0:0]</bitRange></field>
+                            </fields>
+                        </register>
+                        <register derivedFrom=\"CR1\">
+                            <name>CR2</name>
+                            <addressOffset>0x4</addressOffset>
+                        </register>
+                        <cluster>
+                            <name>CH</name>
+                            <addressOffset>0x10</addressOffset>
+                            <register derivedFrom=\"USART1.CR1\">
+                                <name>CR</name>
+                                <description>Channel control</description>
+                                <addressOffset>0x0</addressOffset>
+                            </register>
+                        </cluster>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    device.resolve_derived().unwrap();
+    let usart = &device.peripherals[0];
+    let cr1 = usart.get_register("CR1").unwrap();
+    for (name, offset) in [("CR2", 0x4), ("CR3", 0x8)] {
+        let mut expected = cr1.clone();
+        expected.name = name.into();
+        expected.address_offset = offset;
+        assert_eq!(usart.get_register(name).unwrap(), &expected);
+    }
+    let mut expected = cr1.clone();
+    expected.name = "CR".into();
+    expected.description = Some("Channel control".into());
+    assert_eq!(device.find("USART1.CH.CR").unwrap(), &expected);
+
+    let svd = |base: &str| {
+        format!(
+            "
+            <device>
+                <name>TEST</name>
+                <peripherals>
+                    <peripheral>
+                        <name>USART1</name>
+                        <baseAddress>0x40000000</baseAddress>
+                        <registers>
+                            <register derivedFrom=\"{}\">
+                                <name>CR1</name>
+                                <addressOffset>0x0</addressOffset>
+                            </register>
+                        </registers>
+                    </peripheral>
+                </peripherals>
+            </device>",
+            base
+        )
+    };
+    let mut device = parser::parse(&svd("USART2.CR1")).unwrap();
+    assert_eq!(
+        device.resolve_derived().unwrap_err().to_string(),
+        "`Device error: Register `USART1.CR1` is derived from unknown register `USART2.CR1`"
+    );
+    let mut device = parser::parse(&svd("CR1")).unwrap();
+    assert_eq!(
+        device.resolve_derived().unwrap_err().to_string(),
+        "`Device error: Cyclic derivation of `USART1.CR1` from `CR1`"
+    );
+}
+
+#[test]
+fn resolve_derived_registers_sharing_name() {
+    let mut device = parser::parse(
+        "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>TIM1</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <register>
+                            <name>CCR</name>
+                            <addressOffset>0x0</addressOffset>
+                            <resetValue>0x5</resetValue>
+                        </register>
+                        <register derivedFrom=\"CCR\">
+                            <name>CCMR</name>
+                            <addressOffset>0x4</addressOffset>
+                        </register>
+                        <register derivedFrom=\"CCR\">
+                            <name>CCMR</name>
+                            <addressOffset>0x4</addressOffset>
+                            <alternateRegister>CCMR</alternateRegister>
+                        </register>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+    )
+    .unwrap();
+    device.resolve_derived_registers().unwrap();
+    for r in device.peripherals[0].registers() {
+        assert_eq!(r.derived_from, None);
+        assert_eq!(r.properties.reset_value, Some(0x5));
+    }
+}
+
+#[test]
+fn derived_cluster_keeps_alternate_groups() {
+    use crate::svd::DeriveFrom;
+
+    let register = |group: &str, reset: u32| {
+        format!(
+            "<register>
+                <name>CCMR</name>
+                <alternateGroup>{}</alternateGroup>
+                <addressOffset>0x0</addressOffset>
+                <resetValue>{:#x}</resetValue>
+            </register>",
+            group, reset
+        )
+    };
+    let device = parser::parse(&format!(
+        "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>TIM1</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <cluster>
+                            <name>BASE</name>
+                            <addressOffset>0x0</addressOffset>
+                            {}{}
+                        </cluster>
+                        <cluster>
+                            <name>OWN</name>
+                            <addressOffset>0x10</addressOffset>
+                            {}
+                        </cluster>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>",
+        register("Input", 1),
+        register("Output", 2),
+        register("Output", 3),
+    ))
+    .unwrap();
+    let p = &device.peripherals[0];
+    let derived = p
+        .get_cluster("OWN")
+        .unwrap()
+        .derive_from(p.get_cluster("BASE").unwrap());
+    let registers: Vec<(String, Option<u64>)> = derived
+        .registers()
+        .map(|r| (r.effective_name().into(), r.properties.reset_value))
+        .collect();
+    assert_eq!(
+        registers,
+        [
+            ("CCMR_Input".into(), Some(1)),
+            ("CCMR_Output".into(), Some(3))
+        ]
+    );
+}
+
+#[test]
+fn derived_peripheral_overrides_register() {
+    let xml = "
+        <device>
+            <name>TEST</name>
+            <peripherals>
+                <peripheral>
+                    <name>UART1</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <registers>
+                        <register>
+                            <name>CR</name>
+                            <addressOffset>0x0</addressOffset>
+                        </register>
+                        <register>
+                            <name>BRR</name>
+                            <description>Baud rate register</description>
+                            <addressOffset>0x4</addressOffset>
+                            <resetValue>0x0</resetValue>
+                            <fields>
+                                <field><name>DIV</name><bitRange>[15:0]</bitRange></field>
+                            </fields>
+                        </register>
+                        <register>
+                            <name>DR</name>
+                            <addressOffset>0x8</addressOffset>
+                        </register>
+                    </registers>
+                </peripheral>
+                <peripheral derivedFrom=\"UART1\">
+                    <name>UART2</name>
+                    <baseAddress>0x40001000</baseAddress>
+                    <registers>
+                        <register>
+                            <name>BRR</name>
+                            <description>Baud rate register</description>
+                            <addressOffset>0x4</addressOffset>
+                            <resetValue>0x1A0</resetValue>
+                            <fields>
+                                <field><name>DIV</name><bitRange>[15:0]</bitRange></field>
+                            </fields>
+                        </register>
+                    </registers>
+                </peripheral>
+            </peripherals>
+        </device>";
+    let unresolved = parser::parse(xml).unwrap();
+    let mut device = unresolved.clone();
+    device.resolve_derived_peripherals().unwrap();
+    let uart1 = &device.peripherals[0];
+    let uart2 = &device.peripherals[1];
+    let names: Vec<_> = uart2.registers().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["CR", "BRR", "DR"]);
+
+    let mut brr = uart1.get_register("BRR").unwrap().clone();
+    brr.properties.reset_value = Some(0x1A0);
+    assert_eq!(uart2.get_register("BRR").unwrap(), &brr);
+    assert_eq!(uart2.get_register("DR"), uart1.get_register("DR"));
+}
+
+#[test]
+fn address_map_of_derived_peripheral() {
+    let mut device = parser::parse(SVD).unwrap();
+    device.resolve_derived().unwrap();
+    let map: Vec<_> = device
+        .address_map()
+        .into_iter()
+        .filter(|e| e.path.starts_with("UART"))
+        .map(|e| (e.path, e.address, e.size))
+        .collect();
+    assert_eq!(
+        map,
+        [
+            ("UART.DATA".to_string(), 0x4000_1000, 2),
+            ("UART1.DATA".to_string(), 0x4000_2000, 2),
+        ]
+    );
+}
@@ -169,3 +169,52 @@ fn decode_encode_info() {
 
     run_test::<FieldInfo>(&tests[..], None, None);
 }
+
+#[test]
+fn enumerated_values_usage() {
+    use crate::svd::Usage;
+    use svd_parser::Parse;
+
+    let field = |usages: [&str; 2]| {
+        let containers: String = usages
+            .iter()
+            .map(|usage| {
+                format!(
+                    "<enumeratedValues>{}<enumeratedValue><name>A</name><value>0</value></enumeratedValue></enumeratedValues>",
+                    usage
+                )
+            })
+            .collect();
+        let xml = format!(
+            "<register><name>CR</name><addressOffset>0</addressOffset><fields>
+                <field><name>MODE</name><bitRange>[1:0]</bitRange>{}</field>
+            </fields></register>",
+            containers
+        );
+        let tree = roxmltree::Document::parse(&xml).unwrap();
+        let config = svd_parser::Config::default().validate_level(ValidateLevel::Strict);
+        crate::svd::Register::parse(&tree.root_element(), &config)
+    };
+
+    let register = field(["<usage>read</usage>", "<usage>write</usage>"]).unwrap();
+    let mode = register.get_field("MODE").unwrap();
+    assert!(mode.get_enumerated_values(Usage::Read).is_some());
+    assert!(mode.get_enumerated_values(Usage::Write).is_some());
+
+    // a container without usage is read-write
+    let register = field(["", "<usage>write</usage>"]);
+    assert!(register.is_err());
+
+    for usages in [
+        ["<usage>read</usage>"; 2],
+        ["<usage>write</usage>"; 2],
+        [""; 2],
+    ] {
+        let error = format!("{:#}", field(usages).unwrap_err());
+        assert!(
+            error.contains("Field `MODE` can have 0, 1 or 2 enumeratedValues with different usage"),
+            "{}",
+            error
+        );
+    }
+}
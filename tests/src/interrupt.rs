@@ -53,3 +53,50 @@ fn decode_encode() {
 
     run_test::<Interrupt>(&tests[..], Some(parse_config), Some(encode_config));
 }
+
+#[test]
+fn no_description() {
+    let tests = vec![(
+        Interrupt::builder()
+            .name("UART0".to_string())
+            .value(5)
+            .build(ValidateLevel::Strict)
+            .unwrap(),
+        "
+            <interrupt>
+                <name>UART0</name>
+                <value>5</value>
+            </interrupt>",
+        "
+            <interrupt>
+                <name>UART0</name>
+                <value>5</value>
+            </interrupt>",
+    )];
+
+    run_test::<Interrupt>(&tests[..], None, None);
+}
+
+#[test]
+fn missing_value() {
+    let xml = "
+        <device>
+            <name>CHIP</name>
+            <addressUnitBits>8</addressUnitBits>
+            <width>32</width>
+            <peripherals>
+                <peripheral>
+                    <name>UART</name>
+                    <baseAddress>0x40000000</baseAddress>
+                    <interrupt>
+                        <name>UART0</name>
+                        <description>UART 0 global interrupt</description>
+                    </interrupt>
+                </peripheral>
+            </peripherals>
+        </device>";
+    let error = format!("{:#}", svd_parser::parse(xml).unwrap_err());
+    assert!(error.contains("Parsing interrupt `UART0`"), "{}", error);
+    assert!(error.contains("In peripheral `UART`"), "{}", error);
+    assert!(error.contains("<value>"), "{}", error);
+}
@@ -1,4 +1,5 @@
 #![cfg(test)]
+#![allow(clippy::useless_vec)]
 
 use core::str;
 use roxmltree::Document;
@@ -27,7 +28,7 @@ pub fn run_test<
         let rotree = Document::parse(t.1).unwrap();
         let elem = T::parse(
             &rotree.root().first_element_child().unwrap(),
-            &parser_config.unwrap_or(Config::default()),
+            &parser_config.unwrap_or_default(),
         )
         .unwrap();
         assert_eq!(
@@ -37,7 +38,7 @@ pub fn run_test<
 
         let tree1 = Element::parse(t.2.as_bytes()).unwrap();
         let tree2 = elem
-            .encode_with_config(&encoder_config.unwrap_or(svd_encoder::Config::default()))
+            .encode_with_config(&encoder_config.unwrap_or_default())
             .unwrap();
         assert_eq!(
             tree1, tree2,
@@ -48,19 +49,35 @@ pub fn run_test<
 
 mod access;
 mod addressblock;
-//mod bitrange;
+mod bitrange;
+mod cluster;
+mod compat;
+mod corpus;
 mod cpu;
+mod device;
 mod dimelement;
 mod endian;
 mod enumeratedvalue;
-//mod enumeratedvalues;
+mod enumeratedvalues;
 mod field;
 mod interrupt;
 mod modifiedwritevalues;
+mod naming;
+mod readaction;
 mod register;
-//mod registerproperties;
+mod registerproperties;
+mod template;
 mod usage;
 mod writeconstraint;
 
 #[cfg(feature = "unstable-riscv")]
 mod riscv;
+
+#[cfg(feature = "pack")]
+mod pack;
+
+#[cfg(feature = "expand")]
+mod expand;
+
+#[cfg(feature = "serde")]
+mod serde;
@@ -0,0 +1,166 @@
+use crate::svd::naming::{
+    sanitize, CmsisClassic, LowerSnake, NameMangler, NamingContext, RustConst, Scope,
+};
+
+fn contexts() -> Vec<NamingContext> {
+    let timer = NamingContext::new("TIMER", None).header_definitions_prefix(Some("ACME_"));
+    vec![
+        timer
+            .clone()
+            .register("CR")
+            .field("EN")
+            .enum_value("Enabled"),
+        timer
+            .clone()
+            .index("1")
+            .cluster("CH%s")
+            .register("CCR")
+            .field("VAL"),
+        NamingContext::new("UART[%s]", Some("0"))
+            .affixes(Some("U_"), None)
+            .register("DATA")
+            .field("RX DATA")
+            .enum_value("DataReady"),
+    ]
+}
+
+fn render(mangler: &dyn NameMangler) -> String {
+    contexts()
+        .iter()
+        .map(|ctx| {
+            let mut names = vec![
+                mangler.peripheral(ctx),
+                mangler.register(ctx),
+                mangler.field_mask(ctx),
+                mangler.field_pos(ctx),
+            ];
+            if ctx.enum_value.is_some() {
+                names.push(mangler.enum_value(ctx));
+            }
+            names.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn presets() {
+    assert_eq!(
+        render(&CmsisClassic),
+        "\
+ACME_TIMER ACME_TIMER_CR ACME_TIMER_CR_EN_Msk ACME_TIMER_CR_EN_Pos ACME_TIMER_CR_EN_Enabled
+ACME_TIMER ACME_TIMER_CH1_CCR ACME_TIMER_CH1_CCR_VAL_Msk ACME_TIMER_CH1_CCR_VAL_Pos
+UART0 UART0_U_DATA UART0_U_DATA_RX_DATA_Msk UART0_U_DATA_RX_DATA_Pos UART0_U_DATA_RX_DATA_DataReady"
+    );
+    assert_eq!(
+        render(&RustConst),
+        "\
+TIMER TIMER_CR TIMER_CR_EN_MASK TIMER_CR_EN_OFFSET TIMER_CR_EN_ENABLED
+TIMER TIMER_CH1_CCR TIMER_CH1_CCR_VAL_MASK TIMER_CH1_CCR_VAL_OFFSET
+UART0 UART0_U_DATA UART0_U_DATA_RX_DATA_MASK UART0_U_DATA_RX_DATA_OFFSET UART0_U_DATA_RX_DATA_DATA_READY"
+    );
+    assert_eq!(
+        render(&LowerSnake),
+        "\
+timer timer_cr timer_cr_en_mask timer_cr_en_pos timer_cr_en_enabled
+timer timer_ch1_ccr timer_ch1_ccr_val_mask timer_ch1_ccr_val_pos
+uart0 uart0_u_data uart0_u_data_rx_data_mask uart0_u_data_rx_data_pos uart0_u_data_rx_data_dataready"
+    );
+}
+
+/// In-house style: types in camel case, constants scoped by them
+struct Custom;
+
+impl Custom {
+    fn camel(parts: &[&str]) -> String {
+        parts
+            .iter()
+            .map(|p| {
+                let p = p.to_lowercase();
+                let mut chars = p.chars();
+                match chars.next() {
+                    Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl NameMangler for Custom {
+    fn peripheral(&self, ctx: &NamingContext) -> String {
+        Self::camel(&[&ctx.peripheral])
+    }
+    fn register(&self, ctx: &NamingContext) -> String {
+        let mut parts: Vec<&str> = ctx.clusters.iter().map(String::as_str).collect();
+        parts.extend(ctx.prepend_to_name.as_deref());
+        parts.extend(ctx.register.as_deref());
+        parts.extend(ctx.append_to_name.as_deref());
+        format!("{}::{}", self.peripheral(ctx), Self::camel(&parts))
+    }
+    fn field_mask(&self, ctx: &NamingContext) -> String {
+        format!(
+            "{}::{}_MASK",
+            self.register(ctx),
+            ctx.field.as_deref().unwrap()
+        )
+    }
+    fn field_pos(&self, ctx: &NamingContext) -> String {
+        // index of the innermost array
+        let index = ctx
+            .indexes
+            .values()
+            .last()
+            .map(|i| format!(" (element {})", i));
+        format!(
+            "{}::{}_POS{}",
+            self.register(ctx),
+            ctx.field.as_deref().unwrap(),
+            index.unwrap_or_default()
+        )
+    }
+    fn enum_value(&self, ctx: &NamingContext) -> String {
+        format!(
+            "{}{}::{}",
+            ctx.header_definitions_prefix.as_deref().unwrap_or_default(),
+            self.register(ctx),
+            ctx.enum_value.as_deref().unwrap()
+        )
+    }
+}
+
+#[test]
+fn custom() {
+    assert_eq!(
+        render(&Custom),
+        "\
+Timer Timer::Cr Timer::Cr::EN_MASK Timer::Cr::EN_POS ACME_Timer::Cr::Enabled
+Timer Timer::Ch1Ccr Timer::Ch1Ccr::VAL_MASK Timer::Ch1Ccr::VAL_POS (element 1)
+Uart0 Uart0::U_Data Uart0::U_Data::RX_DATA_MASK Uart0::U_Data::RX_DATA_POS (element 0) Uart0::U_Data::DataReady"
+    );
+}
+
+#[test]
+fn scoped_indexes() {
+    let ctx = NamingContext::new("UART[%s]", Some("2"))
+        .index("1")
+        .cluster("CH%s")
+        .register("CTRL%s")
+        .index("A")
+        .field("EN%s");
+    assert_eq!(CmsisClassic.field_mask(&ctx), "UART2_CH1_CTRL_ENA_Msk");
+    assert_eq!(ctx.index_of(Scope::Peripheral), Some("2"));
+    assert_eq!(ctx.index_of(Scope::Cluster(0)), Some("1"));
+    assert_eq!(ctx.index_of(Scope::Register), None);
+    assert_eq!(ctx.index_of(Scope::Field), Some("A"));
+}
+
+#[test]
+fn sanitized_names() {
+    assert_eq!(sanitize("RX DATA", None), "RX_DATA");
+    assert_eq!(sanitize("CH%s", Some("1")), "CH1");
+    // identifiers can't start with a digit
+    assert_eq!(sanitize("1WIRE", None), "_1WIRE");
+    assert_eq!(sanitize("%s_CTRL", Some("2")), "_2_CTRL");
+    assert_eq!(sanitize("0", None), "_0");
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+use svd_parser::pack;
+
+#[test]
+fn pack_devices() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/ACME.Test_DFP.pack");
+    let pack = pack::open(&path).unwrap();
+
+    let devices: Vec<_> = pack
+        .devices()
+        .into_iter()
+        .map(|d| (d.device_name, d.svd_path_in_archive, d.processor))
+        .collect();
+    assert_eq!(
+        devices,
+        [
+            (
+                "ACME1".to_string(),
+                "SVD/ACME1.svd".to_string(),
+                Some("Cortex-M4".to_string())
+            ),
+            (
+                "ACME2".to_string(),
+                "SVD/ACME2.svd".to_string(),
+                Some("Cortex-M33".to_string())
+            ),
+        ]
+    );
+
+    for name in ["ACME1", "ACME2"] {
+        let device = pack.parse_device(name).unwrap();
+        assert_eq!(device.name, name);
+        assert_eq!(device.peripherals[0].name, "GPIO");
+    }
+    assert!(matches!(
+        pack.parse_device("ACME3"),
+        Err(svd_parser::SVDError::InvalidPack(_))
+    ));
+}
+
+#[test]
+fn pack_missing() {
+    let err = pack::open(Path::new("missing.pack")).err().unwrap();
+    assert!(matches!(err, svd_parser::SVDError::Io(_)));
+}
@@ -0,0 +1,41 @@
+use super::run_test;
+use crate::svd::ReadAction;
+use svd_parser::Parse;
+
+#[test]
+fn decode_encode() {
+    let tests = vec![
+        (
+            ReadAction::Clear,
+            "<readAction>clear</readAction>",
+            "<readAction>clear</readAction>",
+        ),
+        (
+            ReadAction::Set,
+            "<readAction>set</readAction>",
+            "<readAction>set</readAction>",
+        ),
+        (
+            ReadAction::Modify,
+            "<readAction>modify</readAction>",
+            "<readAction>modify</readAction>",
+        ),
+        (
+            ReadAction::ModifyExternal,
+            "<readAction>modifyExternal</readAction>",
+            "<readAction>modifyExternal</readAction>",
+        ),
+    ];
+
+    run_test::<ReadAction>(&tests[..], None, None);
+}
+
+#[test]
+fn unknown() {
+    let tree = roxmltree::Document::parse("<readAction>clearOnRead</readAction>").unwrap();
+    let error = ReadAction::parse(&tree.root_element(), &Default::default()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid readAction variant, found clearOnRead"
+    );
+}
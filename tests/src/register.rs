@@ -1,8 +1,10 @@
 use super::run_test;
 use crate::svd::{
-    Access, BitRange, BitRangeType, DimElement, Field, FieldInfo, ModifiedWriteValues, Register,
-    RegisterInfo, ValidateLevel,
+    layout, validation, Access, BitRange, BitRangeType, BuilderConfig, BuilderProfile, ClusterInfo,
+    DataType, DimElement, Field, FieldInfo, ModifiedWriteValues, Register, RegisterCluster,
+    RegisterInfo, RegisterProperties, SvdError, ValidateLevel,
 };
+use svd_parser::Parse;
 
 #[test]
 fn decode_encode() {
@@ -208,3 +210,460 @@ fn decode_encode_info() {
 
     run_test::<RegisterInfo>(&tests[..], Some(parse_config), Some(encode_config));
 }
+
+#[test]
+fn build_profiles() {
+    let builder = RegisterInfo::builder()
+        .name("CTRL".to_string())
+        .address_offset(0)
+        .fields(Some(Vec::new()));
+
+    // validate-on-build
+    assert!(builder.clone().build(ValidateLevel::Strict).is_err());
+
+    // construct-then-validate
+    let mut register = builder.clone().build(ValidateLevel::Disabled).unwrap();
+    assert!(register.validate(ValidateLevel::Strict).is_err());
+    register
+        .modify_from(
+            RegisterInfo::builder().fields(Some(vec![FieldInfo::builder()
+                .name("EN".to_string())
+                .bit_range(BitRange::from_offset_width(0, 1))
+                .build(ValidateLevel::Strict)
+                .unwrap()
+                .single()])),
+            ValidateLevel::Strict,
+        )
+        .unwrap();
+    assert_eq!(register.validate_all(ValidateLevel::Strict), Ok(()));
+
+    // required values are checked even without validation
+    assert!(RegisterInfo::builder()
+        .address_offset(0)
+        .build(ValidateLevel::Disabled)
+        .is_err());
+}
+
+#[test]
+fn builder_config_profiles() {
+    // every context-free rule fails on this register but `name.duplicate`
+    let builder = RegisterInfo::builder()
+        .name("CH%s".to_string())
+        .address_offset(0)
+        .properties(
+            RegisterProperties::new()
+                .size(Some(8))
+                .reset_value(Some(0xff))
+                .reset_mask(Some(0x0f)),
+        )
+        .fields(Some(vec![FieldInfo::builder()
+            .name("DATA".to_string())
+            .bit_range(BitRange::from_offset_width(0, 16))
+            .build(ValidateLevel::Disabled)
+            .unwrap()
+            .single()]));
+    let dim = DimElement::builder()
+        .dim(2)
+        .dim_increment(0)
+        .dim_index(Some(vec!["A".into(), "B".into(), "C".into()]))
+        .build(ValidateLevel::Disabled)
+        .unwrap();
+    let strict = BuilderConfig::default().profile(BuilderProfile::Strict);
+    let codes = |err: SvdError| match err {
+        SvdError::Validation(validation::Error::Diagnostics(diagnostics)) => {
+            let mut codes: Vec<_> = diagnostics.iter().map(|d| d.code).collect();
+            codes.sort_unstable();
+            codes.dedup();
+            codes
+        }
+        err => panic!("{}", err),
+    };
+
+    let register = builder
+        .clone()
+        .build_with(Some(dim.clone()), &BuilderConfig::default())
+        .unwrap();
+    assert!(register.is_array());
+    let err = builder
+        .clone()
+        .build_with(Some(dim.clone()), &strict)
+        .unwrap_err();
+    assert_eq!(
+        codes(err),
+        ["dim.increment", "dim.index", "reset.mask", "size.register"]
+    );
+
+    // a cluster with the register twice runs the whole context-free rule list
+    let cluster = ClusterInfo::builder()
+        .name("GROUP".to_string())
+        .address_offset(0)
+        .children(vec![RegisterCluster::Register(register); 2]);
+    assert!(cluster
+        .clone()
+        .build_with(None, &BuilderConfig::default())
+        .is_ok());
+    let mut rules = validation::CONTEXT_FREE_RULES.to_vec();
+    rules.sort_unstable();
+    assert_eq!(codes(cluster.build_with(None, &strict).unwrap_err()), rules);
+
+    // arrays need a placeholder in both profiles
+    let builder = builder.name("CH".to_string());
+    assert!(builder
+        .build_with(Some(dim), &BuilderConfig::default())
+        .is_err());
+}
+
+fn register_with_fields(fields: &[(&str, u32, u32)]) -> RegisterInfo {
+    RegisterInfo::builder()
+        .name("REG".to_string())
+        .address_offset(0)
+        .fields(Some(
+            fields
+                .iter()
+                .map(|&(name, offset, width)| {
+                    FieldInfo::builder()
+                        .name(name.to_string())
+                        .bit_range(BitRange::from_offset_width(offset, width))
+                        .build(ValidateLevel::Strict)
+                        .unwrap()
+                        .single()
+                })
+                .collect(),
+        ))
+        .build(ValidateLevel::Strict)
+        .unwrap()
+}
+
+#[test]
+fn packing() {
+    let full = register_with_fields(&[("LOW", 0, 8), ("HIGH", 8, 8)]).packing(16);
+    assert_eq!(full.defined_bits, 16);
+    assert_eq!(full.utilization, 1.0);
+    assert_eq!(full.largest_gap, None);
+    assert!(full.byte_crossing_fields.is_empty());
+    assert!(!full.overlapping);
+
+    let status = register_with_fields(&[("BUSY", 0, 1), ("ERR", 1, 1), ("DONE", 4, 1)]);
+    let info = status.packing(32);
+    assert_eq!(info.defined_bits, 3);
+    assert_eq!(info.utilization, 3. / 32.);
+    assert_eq!(info.largest_gap, Some(BitRange::from_offset_width(5, 27)));
+
+    let info = register_with_fields(&[("MODE", 6, 4), ("EN", 8, 1)]).packing(32);
+    assert_eq!(info.byte_crossing_fields, ["MODE"]);
+    assert!(info.overlapping);
+    assert_eq!(info.defined_bits, 4);
+}
+
+#[test]
+fn fields_beyond_size() {
+    let mut register = register_with_fields(&[("LOW", 0, 8), ("HIGH", 40, 8)]);
+
+    let (size, adjustment) = register.effective_size_considering_fields(32);
+    assert_eq!(size, 32);
+    let adjustment = adjustment.unwrap();
+    assert_eq!(adjustment.declared, 32);
+    assert_eq!(adjustment.required, 64);
+    assert_eq!(adjustment.fields, ["HIGH"]);
+    assert_eq!(register.effective_size_considering_fields(64), (64, None));
+
+    assert_eq!(
+        register.decode(0xab00_0000_0012, 32),
+        Err(layout::Error::FieldOutsideRegister("HIGH".to_string(), 47, 32).into())
+    );
+
+    assert_eq!(register.widen_to_fields(32), Some(adjustment));
+    assert_eq!(register.properties.size, Some(64));
+    assert_eq!(register.widen_to_fields(32), None);
+    assert_eq!(
+        register.decode(0xab00_0000_0012, 32).unwrap(),
+        [("LOW".to_string(), 0x12), ("HIGH".to_string(), 0xab)]
+    );
+}
+
+#[test]
+fn static_layout() {
+    use crate::svd::layout::{FieldDef, RegisterDef};
+
+    static CTRL: RegisterDef = RegisterDef::new(
+        "CTRL",
+        0,
+        16,
+        &[FieldDef::new("EN", 0, 1), FieldDef::new("MODE", 4, 4)],
+    );
+    assert_eq!(CTRL.validate(), Ok(()));
+    assert_eq!(CTRL.decode(0x51), Ok(vec![("EN", 1), ("MODE", 5)]));
+    assert_eq!(CTRL.encode(&[("MODE", 5), ("EN", 1)]), Ok(0x51));
+    assert_eq!(
+        CTRL.reserved(),
+        [
+            BitRange::from_offset_width(1, 3),
+            BitRange::from_offset_width(8, 8)
+        ]
+    );
+    assert_eq!(
+        CTRL.render(),
+        "[15:8]  -\n[7:4]   MODE\n[3:1]   -\n[0:0]   EN\n"
+    );
+    let info = RegisterInfo::from(&CTRL);
+    assert_eq!(info.properties.size, Some(16));
+    assert_eq!(info.decode(0x51, 32).unwrap().len(), 2);
+
+    static BAD: RegisterDef = RegisterDef::new(
+        "BAD",
+        0,
+        8,
+        &[FieldDef::new("A", 0, 4), FieldDef::new("B", 2, 8)],
+    );
+    assert_eq!(
+        BAD.validate(),
+        Err(layout::Error::FieldOutsideRegister("B".to_string(), 9, 8))
+    );
+    assert!(BAD.decode(0).is_err());
+
+    static EMPTY: RegisterDef = RegisterDef::new("EMPTY", 0, 8, &[FieldDef::new("A", 0, 0)]);
+    assert_eq!(
+        EMPTY.validate(),
+        Err(layout::Error::ZeroWidth("A".to_string()))
+    );
+    assert_eq!(EMPTY.reserved(), [BitRange::from_offset_width(0, 8)]);
+}
+
+#[test]
+fn data_type() {
+    let register = |data_type: &str| {
+        let xml = format!(
+            "<register><name>DATA</name><addressOffset>0</addressOffset><dataType>{}</dataType></register>",
+            data_type
+        );
+        let tree = roxmltree::Document::parse(&xml).unwrap();
+        RegisterInfo::parse(&tree.root_element(), &svd_parser::Config::default())
+    };
+    for (text, data_type) in [
+        ("uint8_t", DataType::U8),
+        ("uint32_t *", DataType::U32Ptr),
+        ("uint32_t*", DataType::U32Ptr),
+        ("int16_t  *", DataType::I16Ptr),
+    ] {
+        assert_eq!(
+            register(text).unwrap().datatype,
+            Some(data_type),
+            "{}",
+            text
+        );
+    }
+
+    let device = format!(
+        "<device><name>TEST</name><peripherals><peripheral>
+            <name>UART</name><baseAddress>0</baseAddress>
+            <registers>{}</registers>
+        </peripheral></peripherals></device>",
+        "<register><name>DATA</name><addressOffset>0</addressOffset><dataType>u32</dataType></register>"
+    );
+    let error = format!("{:#}", svd_parser::parse(&device).unwrap_err());
+    assert!(error.contains("register `DATA`"), "{}", error);
+    assert!(
+        error.contains("Invalid datatype variant, found u32"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn display_name_of_array() {
+    let xml = "
+        <register>
+            <dim>2</dim>
+            <dimIncrement>4</dimIncrement>
+            <name>CH%s_CTRL</name>
+            <displayName>Channel %s control</displayName>
+            <addressOffset>0</addressOffset>
+        </register>";
+    let tree = roxmltree::Document::parse(xml).unwrap();
+    let register = Register::parse(&tree.root_element(), &svd_parser::Config::default()).unwrap();
+    let Register::Array(info, dim) = &register else {
+        panic!("`CH%s_CTRL` is not an array");
+    };
+    assert_eq!(info.display_name.as_deref(), Some("Channel %s control"));
+
+    let names: Vec<_> = crate::svd::register::expand(info, dim)
+        .map(|r| (r.name, r.display_name.unwrap()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("CH0_CTRL".to_string(), "Channel 0 control".to_string()),
+            ("CH1_CTRL".to_string(), "Channel 1 control".to_string())
+        ]
+    );
+}
+
+#[test]
+fn reset_value_64bit() {
+    let tests = vec![(
+        RegisterInfo::builder()
+            .name("TIMESTAMP".to_string())
+            .address_offset(0x10)
+            .size(Some(64))
+            .reset_value(Some(0xFFFF_FFFF_0000_0001))
+            .reset_mask(Some(u64::MAX))
+            .build(ValidateLevel::Strict)
+            .unwrap(),
+        "
+        <register>
+            <name>TIMESTAMP</name>
+            <addressOffset>0x10</addressOffset>
+            <size>64</size>
+            <resetValue>0xFFFFFFFF00000001</resetValue>
+            <resetMask>0xFFFFFFFFFFFFFFFF</resetMask>
+        </register>
+        ",
+        "
+        <register>
+            <name>TIMESTAMP</name>
+            <addressOffset>0x10</addressOffset>
+            <size>0x40</size>
+            <resetValue>0xFFFFFFFF00000001</resetValue>
+            <resetMask>0xFFFFFFFFFFFFFFFF</resetMask>
+        </register>
+        ",
+    )];
+    run_test::<RegisterInfo>(&tests[..], None, None);
+
+    // the value must still fit in the register
+    let xml = "
+        <register>
+            <name>STATUS</name>
+            <addressOffset>0x0</addressOffset>
+            <size>32</size>
+            <resetValue>0x100000000</resetValue>
+        </register>";
+    let tree = roxmltree::Document::parse(xml).unwrap();
+    let config = svd_parser::Config::default().validate_level(ValidateLevel::Strict);
+    RegisterInfo::parse(&tree.root_element(), &config).unwrap_err();
+}
+
+#[test]
+fn text_formatting() {
+    let xml = "
+        <register>
+            <name>
+                CTRL<!-- control -->
+            </name>
+            <description><![CDATA[Control <main>]]> register</description>
+            <addressOffset>
+                0x04
+            </addressOffset>
+            <size> 32 </size>
+        </register>";
+    let tree = roxmltree::Document::parse(xml).unwrap();
+    let register = RegisterInfo::parse(&tree.root_element(), &Default::default()).unwrap();
+    assert_eq!(register.name, "CTRL");
+    assert_eq!(
+        register.description.as_deref(),
+        Some("Control <main> register")
+    );
+    assert_eq!(register.address_offset, 4);
+    assert_eq!(register.properties.size, Some(32));
+}
+
+#[test]
+fn array_name_placeholder() {
+    let array = |name: &str| {
+        Register::Array(
+            RegisterInfo::builder()
+                .name(name.to_string())
+                .address_offset(0)
+                .build(ValidateLevel::Strict)
+                .unwrap(),
+            DimElement::builder()
+                .dim(2)
+                .dim_increment(4)
+                .build(ValidateLevel::Strict)
+                .unwrap(),
+        )
+    };
+    assert!(array("CH%s").validate_all(ValidateLevel::Strict).is_ok());
+    assert!(array("CH[%s]").validate_all(ValidateLevel::Weak).is_ok());
+    let err = array("CH").validate_all(ValidateLevel::Weak).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Name check error: Name `CH` in tag `register` is missing a %s placeholder"
+    );
+    assert!(array("CH").validate_all(ValidateLevel::Disabled).is_ok());
+}
+
+#[test]
+fn expand_array() {
+    let array = |name: &str, dim_index: Option<&[&str]>| {
+        let mut info = RegisterInfo::builder()
+            .name(name.to_string())
+            .description(Some("Channel %s".to_string()))
+            .address_offset(0x10)
+            .build(ValidateLevel::Disabled)
+            .unwrap();
+        info.display_name = Some("CH[%s]".to_string());
+        let dim = DimElement::builder()
+            .dim(2)
+            .dim_increment(4)
+            .dim_index(dim_index.map(|i| i.iter().map(|s| s.to_string()).collect()))
+            .build(ValidateLevel::Disabled)
+            .unwrap();
+        info.array(dim)
+    };
+
+    let expanded: Vec<_> = array("CH%s", Some(&["A", "B"]))
+        .expand()
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.name, r.display_name, r.description, r.address_offset))
+        .collect();
+    assert_eq!(
+        expanded,
+        [
+            (
+                "CHA".to_string(),
+                Some("CHA".to_string()),
+                Some("Channel A".to_string()),
+                0x10
+            ),
+            (
+                "CHB".to_string(),
+                Some("CHB".to_string()),
+                Some("Channel B".to_string()),
+                0x14
+            ),
+        ]
+    );
+    let names: Vec<_> = array("CH%s", None)
+        .expand()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.name)
+        .collect();
+    assert_eq!(names, ["CH0", "CH1"]);
+
+    assert_eq!(
+        array("CH%s", Some(&["A"]))
+            .expand()
+            .unwrap_err()
+            .to_string(),
+        "`DimElement error: dimIndex must contain 2 indexes, found 1"
+    );
+    assert_eq!(
+        array("CH", None).expand().unwrap_err().to_string(),
+        "`Name check error: Name `CH` in tag `register` is missing a %s placeholder"
+    );
+    let mut overflowing = array("CH%s", None);
+    overflowing.address_offset = u32::MAX - 2;
+    assert_eq!(
+        overflowing.expand().unwrap_err().to_string(),
+        "`DimElement error: 2 elements 0x4 apart from offset 0xfffffffd overflow"
+    );
+    if let Register::Array(info, dim) = &overflowing {
+        assert_eq!(
+            crate::svd::register::address_offsets(info, dim).collect::<Vec<_>>(),
+            [u32::MAX - 2]
+        );
+    }
+}
@@ -1,33 +1,53 @@
-use crate::EncodeChildren;
-use crate::parse::Parse;
 use crate::svd::{Access, RegisterProperties};
+use roxmltree::Document;
+use svd_encoder::EncodeChildren;
+use svd_parser::Parse;
 use xmltree::Element;
 
+fn parse(xml: &str) -> Result<RegisterProperties, svd_parser::SVDErrorAt> {
+    let tree = Document::parse(xml).unwrap();
+    RegisterProperties::parse(&tree.root_element(), &Default::default())
+}
+
 #[test]
 fn decode_encode() {
-    let example = String::from(
-        "
+    let example = "
         <mock>
-            <size>64</size>
+            <size>0x40</size>
             <access>read-only</access>
             <resetValue>0x11223344</resetValue>
             <resetMask>0xFFFFFFFF</resetMask>
-        </mock>
-    ",
-    );
+        </mock>";
 
-    let mut expected = RegisterProperties::default();
-    expected.size = Some(64);
-    expected.reset_value = Some(0x11223344);
-    expected.reset_mask = Some(0xffffffff);
-    expected.access = Some(Access::ReadOnly);
+    let expected = RegisterProperties::new()
+        .size(Some(64))
+        .access(Some(Access::ReadOnly))
+        .reset_value(Some(0x11223344))
+        .reset_mask(Some(0xffffffff));
 
-    let tree1 = Element::parse(example.as_bytes()).unwrap();
-
-    let parsed = RegisterProperties::parse(&tree1).unwrap();
+    let parsed = parse(example).unwrap();
     assert_eq!(parsed, expected, "Parsing tree failed");
 
+    let tree1 = Element::parse(example.as_bytes()).unwrap();
     let mut tree2 = Element::new("mock");
     tree2.children = parsed.encode().unwrap();
     assert_eq!(tree1, tree2, "Encoding value failed");
 }
+
+#[test]
+fn numbers() {
+    let reset_value = |text: &str| {
+        parse(&format!("<mock><resetValue>{}</resetValue></mock>", text))
+            .map(|p| p.reset_value.unwrap())
+    };
+
+    for text in ["12", "+12", "0xC", "0XC", "0b1100", "#1100", "#11xx"] {
+        assert_eq!(reset_value(text).unwrap(), 12, "{}", text);
+    }
+    assert_eq!(reset_value("4k").unwrap(), 4096);
+
+    for text in ["0b12", "#102", "0xG", "12a"] {
+        let err = reset_value(text).unwrap_err().to_string();
+        assert!(err.contains(&format!("`{}`", text)), "{}: {}", text, err);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::svd::Device;
+use std::path::Path;
+
+#[test]
+fn json_round_trip() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/corpus");
+    let mut files: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "svd"))
+        .collect();
+    files.sort();
+    assert!(!files.is_empty());
+    for file in files {
+        let xml = std::fs::read_to_string(&file).unwrap();
+        let device = svd_parser::parse(&xml).unwrap();
+        let json = serde_json::to_string(&device).unwrap();
+        let decoded: Device = serde_json::from_str(&json)
+            .unwrap_or_else(|e| panic!("{}: {}\n{}", file.display(), e, json));
+        assert_eq!(decoded, device, "{}", file.display());
+    }
+}
+
+#[test]
+fn cmsis_svd_layout() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("data/cmsis_svd");
+    let xml = std::fs::read_to_string(dir.join("device.svd")).unwrap();
+    let device = svd_parser::parse(&xml).unwrap();
+    let json = serde_json::to_value(device.to_cmsis_svd()).unwrap();
+    let golden: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("device.json")).unwrap()).unwrap();
+    assert_eq!(json, golden);
+}
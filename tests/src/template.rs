@@ -0,0 +1,74 @@
+use crate::svd::{
+    template::{self, Error},
+    ArrayStyle, NameTemplate,
+};
+
+fn parse(name: &str) -> NameTemplate {
+    NameTemplate::parse(name).unwrap().unwrap()
+}
+
+#[test]
+fn parse_styles() {
+    assert_eq!(NameTemplate::parse("CTRL"), Ok(None));
+    assert_eq!(
+        NameTemplate::parse("A%sB%s"),
+        Err(Error::MultiplePlaceholders("A%sB%s".into()))
+    );
+    assert_eq!(
+        NameTemplate::parse("BUF[%s]%s"),
+        Err(Error::MultiplePlaceholders("BUF[%s]%s".into()))
+    );
+
+    for (name, style, stripped, substituted) in [
+        ("CH%s", ArrayStyle::Suffix, "CH", "CH3"),
+        ("CH%s_CTRL", ArrayStyle::Infix, "CH_CTRL", "CH3_CTRL"),
+        ("%sCTRL", ArrayStyle::Infix, "CTRL", "3CTRL"),
+        ("BUF[%s]", ArrayStyle::Array, "BUF", "BUF3"),
+        ("BUF[%s]_X", ArrayStyle::Infix, "BUF_X", "BUF3_X"),
+    ] {
+        let template = parse(name);
+        assert_eq!(template.style(), style, "{}", name);
+        assert_eq!(template.strip(), stripped, "{}", name);
+        assert_eq!(template.substitute("3"), substituted, "{}", name);
+        assert_eq!(template.to_string(), name);
+        // substitution agrees with the one used for descriptions
+        assert_eq!(template::substitute(name, "3"), substituted);
+    }
+}
+
+#[test]
+fn match_instance() {
+    let ch = parse("CH%s_CTRL");
+    assert_eq!(ch.match_instance("CH10_CTRL"), Some("10".into()));
+    assert_eq!(ch.match_instance("CH1_CTRL"), Some("1".into()));
+    assert_eq!(ch.match_instance("CH1_CTRLX"), None);
+    assert_eq!(ch.match_instance("XCH1_CTRL"), None);
+    assert_eq!(ch.match_instance("CH_CTRL"), None);
+    // index may contain the surrounding parts
+    assert_eq!(ch.match_instance("CH_CTRL_CTRL"), Some("_CTRL".into()));
+
+    let suffix = parse("UART%s");
+    assert_eq!(suffix.match_instance("UART0"), Some("0".into()));
+    assert_eq!(suffix.match_instance("UARTA"), Some("A".into()));
+    assert_eq!(suffix.match_instance("UART"), None);
+    assert_eq!(suffix.match_instance("USART1"), None);
+
+    let array = parse("BUF[%s]");
+    assert_eq!(array.match_instance("BUF12"), Some("12".into()));
+    assert_eq!(array.match_instance("BUF"), None);
+
+    // both parts of the template overlap in the name
+    assert_eq!(parse("A%sA").match_instance("A"), None);
+    assert_eq!(parse("A%sA").match_instance("AA"), None);
+    assert_eq!(parse("A%sA").match_instance("ABA"), Some("B".into()));
+}
+
+#[test]
+fn substitute_text() {
+    assert!(template::has_placeholder("Channel %s"));
+    assert!(!template::has_placeholder("Channel"));
+    assert_eq!(
+        template::substitute("Channel %s of DMA, see CH[%s]", "2"),
+        "Channel 2 of DMA, see CH2"
+    );
+}
@@ -23,3 +23,37 @@ fn decode_encode() {
 
     run_test::<WriteConstraint>(&tests[..], None, None);
 }
+
+#[test]
+fn forms() {
+    use svd_parser::Parse;
+
+    let parse = |xml: &str| {
+        let tree = roxmltree::Document::parse(xml).unwrap();
+        WriteConstraint::parse(&tree.root_element(), &svd_parser::Config::default())
+    };
+    assert_eq!(
+        parse("<writeConstraint><range><minimum>0x10</minimum><maximum>0xFF</maximum></range></writeConstraint>")
+            .unwrap(),
+        WriteConstraint::Range(WriteConstraintRange {
+            min: 0x10,
+            max: 0xff
+        })
+    );
+
+    let error = parse(
+        "<writeConstraint><writeAsRead>true</writeAsRead><useEnumeratedValues>true</useEnumeratedValues></writeConstraint>",
+    )
+    .unwrap_err();
+    assert_eq!(error.to_string(), "Multiple wc found");
+
+    for xml in [
+        "<writeConstraint></writeConstraint>",
+        "<writeConstraint><values>true</values></writeConstraint>",
+    ] {
+        assert_eq!(
+            parse(xml).unwrap_err().to_string(),
+            "Unknown write constraint"
+        );
+    }
+}